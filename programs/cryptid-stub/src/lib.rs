@@ -0,0 +1,88 @@
+//! A stand-in for the Cryptid program, used in tests only.
+//! It is loaded at the Cryptid program address, and signs CPIs with the default Cryptid account
+//! of a DID, but, unlike Cryptid, it does not check that the transaction is signed by an authority on the DID.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use std::str::FromStr;
+
+declare_id!("cryptJTh61jY5kbUmBEXyc86tBUyueBDrLuNSZWmUcs");
+
+pub const CRYPTID_ACCOUNT_SEED: &[u8] = b"cryptid_account";
+
+#[program]
+pub mod cryptid_stub {
+    use super::*;
+
+    /// Invoke an instruction on the target program, signed by the default Cryptid account of the DID.
+    /// The accounts of the target instruction are passed as remaining accounts.
+    pub fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, Execute<'info>>,
+        instruction_data: Vec<u8>,
+    ) -> Result<()> {
+        let did_program = did_program_id();
+        let did = ctx.accounts.did.key();
+        let index = 0u32.to_le_bytes();
+        let (cryptid_account, bump) = Pubkey::find_program_address(
+            &[
+                CRYPTID_ACCOUNT_SEED,
+                did_program.as_ref(),
+                did.as_ref(),
+                &index,
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(ctx.accounts.cryptid_account.key(), cryptid_account);
+
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer || *account.key == cryptid_account,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: ctx.accounts.target_program.key(),
+            accounts,
+            data: instruction_data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.target_program.to_account_info());
+
+        invoke_signed(
+            &instruction,
+            &account_infos,
+            &[&[
+                CRYPTID_ACCOUNT_SEED,
+                did_program.as_ref(),
+                did.as_ref(),
+                &index,
+                &[bump],
+            ]],
+        )?;
+
+        Ok(())
+    }
+}
+
+fn did_program_id() -> Pubkey {
+    Pubkey::from_str("didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc").unwrap()
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// The default Cryptid account of the DID, which signs the target instruction
+    /// CHECK: Checked against the derived address
+    pub cryptid_account: UncheckedAccount<'info>,
+    /// The DID that owns the Cryptid account
+    /// CHECK: This can be any public key
+    pub did: UncheckedAccount<'info>,
+    /// The program to invoke
+    /// CHECK: This can be any program
+    pub target_program: UncheckedAccount<'info>,
+}
@@ -0,0 +1,16 @@
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+    mut,
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    constraint = config.pending_admin == pending_admin.key() @ ErrorCode::NotPendingAdmin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    /// The key proposed as the next admin
+    pub pending_admin: Signer<'info>,
+}
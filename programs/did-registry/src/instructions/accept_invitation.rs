@@ -0,0 +1,60 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{
+    key_registry::KeyRegistry, registration_inbox::RegistrationInbox,
+    registration_invitation::RegistrationInvitation,
+};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Accept an invitation to register a DID, adding it to the key's registry
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct AcceptInvitation<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The key that was invited, which owns the registry
+    pub authority: Signer<'info>,
+    /// The invitation, whose rent and deposit are refunded to the inviter
+    #[account(
+    mut,
+    close = inviter,
+    has_one = inviter,
+    seeds = [RegistrationInvitation::SEED_PREFIX, authority.key().as_ref(), invitation.did.as_ref()],
+    bump,
+    )]
+    pub invitation: Account<'info, RegistrationInvitation>,
+    /// The inbox of the key, which lists the invitation
+    #[account(
+    mut,
+    seeds = [RegistrationInbox::SEED_PREFIX, authority.key().as_ref()],
+    bump,
+    )]
+    pub inbox: Account<'info, RegistrationInbox>,
+    /// The account that paid for the invitation
+    /// CHECK: Checked against the invitation
+    #[account(mut)]
+    pub inviter: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, invitation.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
+}
@@ -0,0 +1,29 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{
+    registration_inbox::RegistrationInbox, registration_invitation::RegistrationInvitation,
+};
+use anchor_lang::prelude::*;
+
+/// Cancel an invitation to register a DID, as the account that paid for it
+#[derive(Accounts)]
+pub struct CancelInvitation<'info> {
+    pub config: UnpausedConfig<'info>,
+    /// The account that paid for the invitation, which receives its rent and deposit back
+    #[account(mut)]
+    pub inviter: Signer<'info>,
+    #[account(
+    mut,
+    close = inviter,
+    has_one = inviter,
+    seeds = [RegistrationInvitation::SEED_PREFIX, invitation.key.as_ref(), invitation.did.as_ref()],
+    bump,
+    )]
+    pub invitation: Account<'info, RegistrationInvitation>,
+    /// The inbox of the key, which lists the invitation
+    #[account(
+    mut,
+    seeds = [RegistrationInbox::SEED_PREFIX, invitation.key.as_ref()],
+    bump,
+    )]
+    pub inbox: Account<'info, RegistrationInbox>,
+}
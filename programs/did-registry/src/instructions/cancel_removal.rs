@@ -0,0 +1,42 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::pending_removal::PendingRemoval;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Cancel a pending removal from either a key or a controller registry.
+/// This can be done by any authority on the DID being removed.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct CancelRemoval<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    close = payer,
+    has_one = did,
+    has_one = payer,
+    )]
+    pub pending_removal: Account<'info, PendingRemoval>,
+    /// The account that paid for the pending removal, which receives its rent back
+    /// CHECK: Checked against the pending removal
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    /// An authority on the DID being removed
+    pub authority: Signer<'info>,
+    /// The DID being removed. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: Checked against the pending removal
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
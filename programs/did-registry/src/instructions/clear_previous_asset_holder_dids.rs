@@ -0,0 +1,24 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::asset_registry::AssetRegistry;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Remove all DIDs registered by previous holders of an asset. Only the current holder can do this.
+#[derive(Accounts)]
+pub struct ClearPreviousAssetHolderDids<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [AssetRegistry::SEED_PREFIX, registry.mint.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, AssetRegistry>,
+    /// The current holder of the asset
+    pub holder: Signer<'info>,
+    /// The token account of the holder that holds the asset
+    #[account(
+    constraint = token_account.mint == registry.mint && token_account.owner == holder.key() && token_account.amount > 0 @ ErrorCode::NotAssetHolder,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+}
@@ -11,6 +11,7 @@ pub struct CloseControllerRegistry<'info> {
     #[account(
     mut,
     close = payer,
+    has_one = did,
     )]
     pub registry: Account<'info, ControllerRegistry>,
     #[account(mut)]
@@ -0,0 +1,38 @@
+use crate::state::key_registry::KeyRegistry;
+use crate::state::message_nonce::MessageNonce;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Close a key's registry, authorised by the key signing a message rather than the transaction.
+#[derive(Accounts)]
+pub struct CloseKeyRegistryWithSignedMessage<'info> {
+    #[account(
+    mut,
+    close = payer,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// Receives the registry rent that is not owed to its rent payers. It is included in the signed message.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The key that owns the registry. It does not sign the transaction,
+    /// but signs the close message in the preceding ed25519 instruction
+    /// CHECK: The signature is checked in the program
+    pub authority: UncheckedAccount<'info>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the ed25519 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,32 @@
+use crate::state::message_nonce::MessageNonce;
+use crate::state::passkey_registry::PasskeyRegistry;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+#[derive(Accounts)]
+pub struct ClosePasskeyRegistry<'info> {
+    #[account(
+    mut,
+    close = payer,
+    seeds = [PasskeyRegistry::SEED_PREFIX, &PasskeyRegistry::key_seed(&registry.key)],
+    bump,
+    )]
+    pub registry: Account<'info, PasskeyRegistry>,
+    /// Receives the registry rent that is not owed to a rent payer. It is included in the passkey's challenge.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the secp256r1 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
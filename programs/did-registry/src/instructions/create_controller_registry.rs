@@ -1,4 +1,7 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::controller_registry::ControllerRegistry;
+use crate::state::key_registry::KeyRegistry;
+use crate::state::program_config::ProgramConfig;
 use crate::{SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
 
@@ -8,13 +11,16 @@ use anchor_lang::prelude::*;
 bump: u8,
 /// The bump seed for the did account
 did_bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
 )]
 pub struct CreateControllerRegistry<'info> {
+    pub config: UnpausedFeeConfig<'info>,
     #[account(
     init,
     payer = payer,
-    space = 8 + ControllerRegistry::INITIAL_SIZE,
-    seeds = [ControllerRegistry::SEED_PREFIX, did.key().as_ref()],
+    space = 8 + ControllerRegistry::calculate_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [ControllerRegistry::SEED_PREFIX, did.key().as_ref(), KeyRegistry::namespace_seed(&namespace)?],
     bump,
     )]
     pub registry: Account<'info, ControllerRegistry>,
@@ -0,0 +1,42 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{controller_registry::ControllerRegistry, registry_history::RegistryHistory};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(
+/// The maximum number of entries kept in the history
+capacity: u32,
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct CreateControllerRegistryHistory<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = RegistryHistory::calculate_size(capacity),
+    seeds = [RegistryHistory::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub history: Account<'info, RegistryHistory>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// The account containing the DID document of the registry's DID
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,13 +1,22 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::key_registry::KeyRegistry;
+use crate::state::program_config::ProgramConfig;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
+#[instruction(
+/// The bump seed for the registry
+bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
+)]
 pub struct CreateKeyRegistry<'info> {
+    pub config: UnpausedFeeConfig<'info>,
     #[account(
     init,
     payer = payer,
-    space = 8 + KeyRegistry::INITIAL_SIZE,
-    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref()],
+    space = 8 + KeyRegistry::calculate_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), KeyRegistry::namespace_seed(&namespace)?],
     bump,
     )]
     pub registry: Account<'info, KeyRegistry>,
@@ -0,0 +1,28 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{key_registry::KeyRegistry, registry_history::RegistryHistory};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(capacity: u32)]
+pub struct CreateKeyRegistryHistory<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = RegistryHistory::calculate_size(capacity),
+    seeds = [RegistryHistory::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub history: Account<'info, RegistryHistory>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,23 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{controller_registry::ControllerRegistry, pending_removal::PendingRemoval};
+use anchor_lang::prelude::*;
+
+/// Finalising a removal is permissionless - an authority on the registry DID or the controlled DID
+/// has already requested it, and the delay has elapsed without it being cancelled.
+#[derive(Accounts)]
+pub struct FinalizeControlledDidRemoval<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(mut)]
+    pub registry: Account<'info, ControllerRegistry>,
+    #[account(
+    mut,
+    close = payer,
+    has_one = registry,
+    has_one = payer,
+    )]
+    pub pending_removal: Account<'info, PendingRemoval>,
+    /// The account that paid for the pending removal, which receives its rent back
+    /// CHECK: Checked against the pending removal
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
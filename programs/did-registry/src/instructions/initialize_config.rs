@@ -0,0 +1,23 @@
+use crate::program::DidRegistry;
+use crate::state::program_config::ProgramConfig;
+use anchor_lang::prelude::*;
+
+/// Only the upgrade authority of the program can initialise its config, becoming its first admin
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+    init,
+    payer = admin,
+    space = ProgramConfig::SIZE,
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, DidRegistry>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()))]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{
+    registration_inbox::RegistrationInbox, registration_invitation::RegistrationInvitation,
+};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Invite a key to register a DID, as an authority on the DID
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct InviteDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    init,
+    payer = inviter,
+    space = RegistrationInvitation::SIZE,
+    seeds = [RegistrationInvitation::SEED_PREFIX, key.key().as_ref(), did.key().as_ref()],
+    bump,
+    )]
+    pub invitation: Account<'info, RegistrationInvitation>,
+    /// The inbox of the key, which lists the invitation
+    #[account(
+    init_if_needed,
+    payer = inviter,
+    space = RegistrationInbox::SIZE,
+    seeds = [RegistrationInbox::SEED_PREFIX, key.key().as_ref()],
+    bump,
+    )]
+    pub inbox: Account<'info, RegistrationInbox>,
+    /// Pays the rent and deposit for the invitation
+    #[account(mut)]
+    pub inviter: Signer<'info>,
+    /// An authority on the DID
+    pub authority: Signer<'info>,
+    /// The key invited to register the DID
+    /// CHECK: This can be any public key
+    pub key: UncheckedAccount<'info>,
+    /// The DID to register. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,16 @@
+use crate::instructions::unpaused_config::*;
+use anchor_lang::prelude::*;
+
+/// Migrate a controller registry from the layout of the first release of the program. Anyone can migrate a registry.
+#[derive(Accounts)]
+pub struct MigrateControllerRegistry<'info> {
+    pub config: UnpausedConfig<'info>,
+    /// A registry in the layout of the first release, which cannot be loaded as a ControllerRegistry
+    /// CHECK: The discriminator and layout are checked in the instruction
+    #[account(mut, owner = crate::ID)]
+    pub registry: UncheckedAccount<'info>,
+    /// Pays the rent for the space of the fields appended to the registry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
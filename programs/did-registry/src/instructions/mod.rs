@@ -1,26 +1,148 @@
+mod accept_admin;
+mod accept_invitation;
+mod cancel_invitation;
+mod cancel_removal;
+mod clear_previous_asset_holder_dids;
 mod close_controller_registry;
 mod close_key_registry;
+mod close_key_registry_with_signed_message;
+mod close_passkey_registry;
 mod create_controller_registry;
+mod create_controller_registry_history;
 mod create_key_registry;
+mod create_key_registry_history;
+mod finalize_controlled_did_removal;
+mod finalize_did_removal;
+mod initialize_config;
+mod invite_did;
+mod migrate_controller_registry;
+mod migrate_key_registry;
+mod prune_controlled_did;
+mod prune_did;
 mod register_controlled_did;
 mod register_did;
+mod register_did_for_asset;
+mod register_did_for_chain_account;
 mod register_did_for_eth_address;
+mod register_did_for_external_controller;
+mod register_did_for_keys;
+mod register_did_signed_by_btc_address;
 mod register_did_signed_by_eth_address;
+mod register_did_signed_by_eth_address_via_precompile;
+mod register_did_with_passkey;
+mod register_did_with_signed_message;
+mod reject_invitation;
 mod remove_controlled_did;
 mod remove_did;
+mod remove_did_for_asset;
+mod remove_did_for_btc_address;
+mod remove_did_for_chain_account;
+mod remove_did_for_external_controller;
+mod remove_did_for_keys;
+mod remove_did_with_passkey;
+mod remove_did_with_signed_message;
+mod replace_controlled_did;
+mod replace_did;
+mod request_controlled_did_removal;
+mod request_did_removal;
+mod resize_asset_registry;
+mod resize_chain_account_registry;
 mod resize_controller_registry;
+mod resize_external_controller_registry;
 mod resize_key_registry;
+mod resize_key_registry_with_signed_message;
+mod resize_passkey_registry;
+mod resize_registry_history;
+mod revoke_did_successor;
+mod self_register_controlled_did;
+mod self_remove_controlled_did;
+mod self_request_controlled_did_removal;
+mod set_controller_registry_removal_delay;
+mod set_controller_registry_required_flags;
+mod set_did_successor;
+mod set_key_registry_removal_delay;
+mod set_key_registry_required_flags;
+mod sweep_expired_dids;
+mod unpaused_config;
+mod update_config;
+mod update_did_successor;
+mod withdraw_fees;
 
+pub use accept_invitation::*;
+pub use cancel_invitation::*;
 pub use close_key_registry::*;
+pub use close_key_registry_with_signed_message::*;
 pub use create_key_registry::*;
+pub use create_key_registry_history::*;
+pub use finalize_did_removal::*;
+pub use invite_did::*;
+pub use migrate_key_registry::*;
+pub use prune_did::*;
 pub use register_did::*;
 pub use register_did_for_eth_address::*;
+pub use register_did_for_keys::*;
+pub use register_did_signed_by_btc_address::*;
 pub use register_did_signed_by_eth_address::*;
+pub use register_did_signed_by_eth_address_via_precompile::*;
+pub use register_did_with_signed_message::*;
+pub use reject_invitation::*;
 pub use remove_did::*;
+pub use remove_did_for_btc_address::*;
+pub use remove_did_for_keys::*;
+pub use remove_did_with_signed_message::*;
+pub use replace_did::*;
+pub use request_did_removal::*;
 pub use resize_key_registry::*;
+pub use resize_key_registry_with_signed_message::*;
+pub use set_key_registry_removal_delay::*;
+pub use set_key_registry_required_flags::*;
+pub use sweep_expired_dids::*;
 
 pub use close_controller_registry::*;
 pub use create_controller_registry::*;
+pub use create_controller_registry_history::*;
+pub use finalize_controlled_did_removal::*;
+pub use migrate_controller_registry::*;
+pub use prune_controlled_did::*;
 pub use register_controlled_did::*;
 pub use remove_controlled_did::*;
+pub use replace_controlled_did::*;
+pub use request_controlled_did_removal::*;
 pub use resize_controller_registry::*;
+pub use self_register_controlled_did::*;
+pub use self_remove_controlled_did::*;
+pub use self_request_controlled_did_removal::*;
+pub use set_controller_registry_removal_delay::*;
+pub use set_controller_registry_required_flags::*;
+
+pub use close_passkey_registry::*;
+pub use register_did_with_passkey::*;
+pub use remove_did_with_passkey::*;
+pub use resize_passkey_registry::*;
+
+pub use register_did_for_chain_account::*;
+pub use remove_did_for_chain_account::*;
+pub use resize_chain_account_registry::*;
+
+pub use register_did_for_external_controller::*;
+pub use remove_did_for_external_controller::*;
+pub use resize_external_controller_registry::*;
+
+pub use clear_previous_asset_holder_dids::*;
+pub use register_did_for_asset::*;
+pub use remove_did_for_asset::*;
+pub use resize_asset_registry::*;
+
+pub use revoke_did_successor::*;
+pub use set_did_successor::*;
+pub use update_did_successor::*;
+
+pub use resize_registry_history::*;
+
+pub use cancel_removal::*;
+
+pub use accept_admin::*;
+pub use initialize_config::*;
+pub use unpaused_config::*;
+pub use update_config::*;
+pub use withdraw_fees::*;
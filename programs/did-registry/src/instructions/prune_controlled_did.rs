@@ -0,0 +1,31 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::controller_registry::ControllerRegistry;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Remove DIDs from a controller registry, once they are no longer controlled by the registry's DID.
+/// Anyone can prune a registry.
+/// The controlled DIDs and their accounts are passed in the remaining accounts, as (did, did account) pairs.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the registry did account
+did_bump: u8,
+)]
+pub struct PruneControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// The account containing the DID document that this registry applies to.
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
@@ -0,0 +1,35 @@
+use crate::instructions::unpaused_config::*;
+use crate::{state::key_registry::KeyRegistry, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Remove a DID from a key's registry, once the key is no longer an authority on it.
+/// Anyone can prune a registry.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct PruneDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, registry.authority.as_ref(), registry.namespace.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The account pruning the registry, which receives any rent freed by shrinking it that is not owed to a rent payer
+    #[account(mut)]
+    pub pruner: Signer<'info>,
+    /// The DID to remove from the registry
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document. This may have been closed.
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
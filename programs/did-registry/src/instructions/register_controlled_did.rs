@@ -1,3 +1,4 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::controller_registry::ControllerRegistry;
 use crate::{SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
@@ -11,9 +12,10 @@ did_bump: u8,
 controlled_did_bump: u8,
 )]
 pub struct RegisterControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref()],
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
     bump
     )]
     pub registry: Account<'info, ControllerRegistry>,
@@ -45,4 +47,8 @@ pub struct RegisterControlledDid<'info> {
     seeds::program = SolDID::id()
     )]
     pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
 }
@@ -1,3 +1,4 @@
+use crate::instructions::unpaused_config::*;
 use crate::{state::key_registry::KeyRegistry, SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
 
@@ -7,9 +8,10 @@ use anchor_lang::prelude::*;
 did_bump: u8,
 )]
 pub struct RegisterDid<'info> {
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref()],
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
     bump,
     has_one = authority
     )]
@@ -31,4 +33,8 @@ pub struct RegisterDid<'info> {
     seeds::program = SolDID::id()
     )]
     pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
 }
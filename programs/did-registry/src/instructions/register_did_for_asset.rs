@@ -0,0 +1,57 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::asset_registry::AssetRegistry;
+use crate::{ErrorCode, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+/// Add a DID to an asset's registry. The holder must hold the asset, and be an authority on the DID.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RegisterDidForAsset<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = 8 + AssetRegistry::INITIAL_SIZE,
+    seeds = [AssetRegistry::SEED_PREFIX, mint.key().as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, AssetRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The current holder of the asset
+    pub holder: Signer<'info>,
+    /// The mint of the asset. Only NFTs (a supply of 1, with no decimals) can have a registry.
+    #[account(
+    constraint = mint.supply == 1 && mint.decimals == 0 @ ErrorCode::InvalidAsset,
+    )]
+    pub mint: Account<'info, Mint>,
+    /// The token account of the holder that holds the asset
+    #[account(
+    constraint = token_account.mint == mint.key() && token_account.owner == holder.key() && token_account.amount > 0 @ ErrorCode::NotAssetHolder,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// note - this may or may not be the same as the holder.
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the holder as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the holder, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
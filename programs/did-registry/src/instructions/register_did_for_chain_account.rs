@@ -0,0 +1,43 @@
+use crate::instructions::unpaused_config::*;
+use crate::{state::chain_account_registry::ChainAccountRegistry, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+use sol_did::state::DidAccount;
+
+/// Add a DID to a chain account's registry, authorised by a signature of the chain account
+#[derive(Accounts)]
+#[instruction(
+/// The CAIP-10 account id that the registry is for
+account_id: String,
+/// A signature of the registration message by the chain account, in the format of its namespace
+signature: Vec<u8>,
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RegisterDidForChainAccount<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = 8 + ChainAccountRegistry::calculate_size(account_id.len(), ChainAccountRegistry::INITIAL_DID_COUNT),
+    seeds = [ChainAccountRegistry::SEED_PREFIX, &ChainAccountRegistry::account_id_seed(&account_id)],
+    bump,
+    )]
+    pub registry: Account<'info, ChainAccountRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// note - this may or may not be the same as the payer.
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// This can safely be a DidAccount, rather than UncheckedAccount,
+    /// since, for the DID to include a chain account it must be a non-generative DID.
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: Account<'info, DidAccount>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,3 +1,5 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::program_config::ProgramConfig;
 use crate::{state::key_registry::KeyRegistry, SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
 
@@ -7,13 +9,16 @@ use anchor_lang::prelude::*;
 eth_address: [u8; 20],
 /// The bump seed for the did account
 did_bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
 )]
 pub struct RegisterDidForEthAddress<'info> {
+    pub config: UnpausedFeeConfig<'info>,
     #[account(
     init_if_needed,
     payer = authority,
-    space = 8 + KeyRegistry::INITIAL_SIZE,
-    seeds = [KeyRegistry::ETH_SEED_PREFIX, &eth_address],
+    space = KeyRegistry::calculate_eth_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::ETH_SEED_PREFIX, &eth_address, KeyRegistry::namespace_seed(&namespace)?],
     bump,
     )]
     pub registry: Account<'info, KeyRegistry>,
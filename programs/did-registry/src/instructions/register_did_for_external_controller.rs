@@ -0,0 +1,48 @@
+use crate::instructions::unpaused_config::*;
+use crate::{
+    state::external_controller_registry::ExternalControllerRegistry, SolDID, DID_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+use sol_did::state::DidAccount;
+
+/// Add a DID to the registry of a non-did:sol controller DID that it lists in its other_controllers
+#[derive(Accounts)]
+#[instruction(
+/// The controller DID, e.g. "did:web:example.com"
+controller: String,
+/// The bump seed for the controlled did account
+controlled_did_bump: u8,
+)]
+pub struct RegisterDidForExternalController<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = 8 + ExternalControllerRegistry::calculate_size(controller.len(), ExternalControllerRegistry::INITIAL_DID_COUNT),
+    seeds = [ExternalControllerRegistry::SEED_PREFIX, &ExternalControllerRegistry::controller_seed(&controller)],
+    bump,
+    )]
+    pub registry: Account<'info, ExternalControllerRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// An authority on the controlled DID
+    pub authority: Signer<'info>,
+    /// The controlled did to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: This can be any public key. But it should derive the controlled_did_account
+    pub controlled_did: UncheckedAccount<'info>,
+    /// The account containing the controlled DID document
+    /// This can safely be a DidAccount, rather than UncheckedAccount,
+    /// since, for the DID to list other controllers it must be a non-generative DID.
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, controlled_did.key().as_ref()],
+    bump = controlled_did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub controlled_did_account: Account<'info, DidAccount>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
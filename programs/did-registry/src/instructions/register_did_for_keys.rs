@@ -0,0 +1,34 @@
+use crate::instructions::unpaused_config::*;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Add a DID to the registries of many keys at once.
+/// The keys and their registries are passed in the remaining accounts, as (key, registry) pairs.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+/// The application namespace of the registries (empty = the default registry)
+namespace: Vec<u8>,
+)]
+pub struct RegisterDidForKeys<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    /// Pays for any registries that do not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to add to the registries. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has each key as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::program_config::ProgramConfig;
+use crate::{
+    state::key_registry::KeyRegistry, util::btc_signing::BtcAddressType, SolDID, DID_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+use sol_did::state::DidAccount;
+
+/// Add a DID to a Bitcoin address's registry, authorised by a BIP-137 signature of the address
+#[derive(Accounts)]
+#[instruction(
+/// The type of the Bitcoin address
+address_type: BtcAddressType,
+/// The public key hash (hash160) encoded in the Bitcoin address
+pubkey_hash: [u8; 20],
+/// A BIP-137 signature of the registration message by the address
+signature: [u8; 65],
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RegisterDidSignedByBtcAddress<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = KeyRegistry::calculate_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::BTC_SEED_PREFIX, &address_type.seed(), &pubkey_hash],
+    bump,
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// note - this may or may not be the same as the payer.
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// This can safely be a DidAccount, rather than UncheckedAccount,
+    /// since, for the DID to include a Bitcoin address it must be a non-generative DID.
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: Account<'info, DidAccount>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,3 +1,5 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::program_config::ProgramConfig;
 use crate::{
     state::key_registry::KeyRegistry, util::eth_signing::Secp256k1RawSignature, SolDID,
     DID_ACCOUNT_SEED,
@@ -13,13 +15,16 @@ eth_address: [u8; 20],
 eth_signature: Secp256k1RawSignature,
 /// The bump seed for the did account
 did_bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
 )]
 pub struct RegisterDidSignedByEthAddress<'info> {
+    pub config: UnpausedFeeConfig<'info>,
     #[account(
     init_if_needed,
     payer = payer,
-    space = 8 + KeyRegistry::INITIAL_SIZE,
-    seeds = [KeyRegistry::ETH_SEED_PREFIX, &eth_address],
+    space = KeyRegistry::calculate_eth_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::ETH_SEED_PREFIX, &eth_address, KeyRegistry::namespace_seed(&namespace)?],
     bump,
     )]
     pub registry: Account<'info, KeyRegistry>,
@@ -0,0 +1,60 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::program_config::ProgramConfig;
+use crate::{state::key_registry::KeyRegistry, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use sol_did::state::DidAccount;
+
+/// Add a DID to an eth address's registry, where the eth signature is verified by the secp256k1 program
+/// in the preceding instruction, rather than recovered in this program.
+#[derive(Accounts)]
+#[instruction(
+/// The eth address that the registry is being created for
+eth_address: [u8; 20],
+/// The bump seed for the did account
+did_bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
+)]
+pub struct RegisterDidSignedByEthAddressViaPrecompile<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = KeyRegistry::calculate_eth_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::ETH_SEED_PREFIX, &eth_address, KeyRegistry::namespace_seed(&namespace)?],
+    bump,
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// note - this may or may not be the same as the payer.
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// This can safely be a DidAccount, rather than UncheckedAccount,
+    /// since, for the DID to include an eth address it must be a non-generative DID.
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: Account<'info, DidAccount>,
+    /// The instructions sysvar, used to find the secp256k1 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    pub system_program: Program<'info, System>,
+}
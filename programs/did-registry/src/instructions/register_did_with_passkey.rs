@@ -0,0 +1,62 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::{
+    state::passkey_registry::PasskeyRegistry, util::webauthn::WebAuthnAssertion, SolDID,
+    DID_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Add a DID to a passkey's registry, authorised by a WebAuthn assertion of the passkey,
+/// whose signature is verified by the secp256r1 program in the preceding instruction.
+#[derive(Accounts)]
+#[instruction(
+/// The compressed secp256r1 public key of the passkey
+key: [u8; 33],
+/// The WebAuthn assertion over the registration challenge
+assertion: WebAuthnAssertion,
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RegisterDidWithPasskey<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = 8 + PasskeyRegistry::INITIAL_SIZE,
+    seeds = [PasskeyRegistry::SEED_PREFIX, &PasskeyRegistry::key_seed(&key)],
+    bump,
+    )]
+    pub registry: Account<'info, PasskeyRegistry>,
+    /// Pays for the transaction and, if needed, the registry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the passkey as a verification method
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the secp256r1 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
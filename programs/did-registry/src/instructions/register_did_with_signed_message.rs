@@ -0,0 +1,67 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::program_config::ProgramConfig;
+use crate::{state::key_registry::KeyRegistry, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Add a DID to a key's registry, authorised by the key signing a message rather than the transaction.
+/// This allows a relayer to pay for, and submit, the registration.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+/// The application namespace of the registry (empty = the default registry)
+namespace: Vec<u8>,
+)]
+pub struct RegisterDidWithSignedMessage<'info> {
+    pub config: UnpausedFeeConfig<'info>,
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = 8 + KeyRegistry::calculate_size(ProgramConfig::load(&config)?.initial_capacity),
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), KeyRegistry::namespace_seed(&namespace)?],
+    bump,
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The relayer, paying for the transaction and, if needed, the registry
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The key that owns the registry. It does not sign the transaction,
+    /// but signs the registration message in the preceding ed25519 instruction
+    /// CHECK: The signature is checked in the program
+    pub authority: UncheckedAccount<'info>,
+    /// The DID to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// note - this may or may not be the same as the authority.
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the ed25519 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,34 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{
+    registration_inbox::RegistrationInbox, registration_invitation::RegistrationInvitation,
+};
+use anchor_lang::prelude::*;
+
+/// Reject an invitation to register a DID
+#[derive(Accounts)]
+pub struct RejectInvitation<'info> {
+    pub config: UnpausedConfig<'info>,
+    /// The key that was invited, which receives the deposit
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// The invitation, whose rent is refunded to the inviter
+    #[account(
+    mut,
+    close = inviter,
+    has_one = inviter,
+    seeds = [RegistrationInvitation::SEED_PREFIX, authority.key().as_ref(), invitation.did.as_ref()],
+    bump,
+    )]
+    pub invitation: Account<'info, RegistrationInvitation>,
+    /// The inbox of the key, which lists the invitation
+    #[account(
+    mut,
+    seeds = [RegistrationInbox::SEED_PREFIX, authority.key().as_ref()],
+    bump,
+    )]
+    pub inbox: Account<'info, RegistrationInbox>,
+    /// The account that paid for the invitation
+    /// CHECK: Checked against the invitation
+    #[account(mut)]
+    pub inviter: UncheckedAccount<'info>,
+}
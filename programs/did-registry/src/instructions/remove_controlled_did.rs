@@ -1,3 +1,4 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::controller_registry::ControllerRegistry;
 use crate::{SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
@@ -8,9 +9,10 @@ use anchor_lang::prelude::*;
 did_bump: u8,
 )]
 pub struct RemoveControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [ControllerRegistry::SEED_PREFIX, authority.key().as_ref()],
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
     bump,
     )]
     pub registry: Account<'info, ControllerRegistry>,
@@ -1,11 +1,13 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::key_registry::KeyRegistry;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct RemoveDid<'info> {
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref()],
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
     bump,
     has_one = authority
     // Leave out this constraint, to avoid having to iterate through the vec twice
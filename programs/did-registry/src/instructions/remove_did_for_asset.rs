@@ -0,0 +1,40 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::asset_registry::AssetRegistry;
+use crate::{ErrorCode, SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Remove a DID from an asset's registry. The holder must hold the asset, and be an authority on the DID.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RemoveDidForAsset<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [AssetRegistry::SEED_PREFIX, registry.mint.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, AssetRegistry>,
+    /// The current holder of the asset
+    pub holder: Signer<'info>,
+    /// The token account of the holder that holds the asset
+    #[account(
+    constraint = token_account.mint == registry.mint && token_account.owner == holder.key() && token_account.amount > 0 @ ErrorCode::NotAssetHolder,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// The DID to remove from the registry
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
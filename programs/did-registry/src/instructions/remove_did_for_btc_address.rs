@@ -0,0 +1,33 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::key_registry::KeyRegistry;
+use crate::state::message_nonce::MessageNonce;
+use crate::util::btc_signing::BtcAddressType;
+use anchor_lang::prelude::*;
+
+/// Remove a DID from a Bitcoin address's registry, authorised by a BIP-137 signature of the address
+#[derive(Accounts)]
+#[instruction(
+/// The type of the Bitcoin address
+address_type: BtcAddressType,
+/// The public key hash (hash160) encoded in the Bitcoin address
+pubkey_hash: [u8; 20],
+)]
+pub struct RemoveDidForBtcAddress<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::BTC_SEED_PREFIX, &address_type.seed(), &pubkey_hash],
+    bump,
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+    /// The nonce included in the signed message. It exists, since the DID was registered with a signed message
+    #[account(
+    mut,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+}
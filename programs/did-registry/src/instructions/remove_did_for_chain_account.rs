@@ -0,0 +1,18 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::chain_account_registry::ChainAccountRegistry;
+use anchor_lang::prelude::*;
+
+/// Remove a DID from a chain account's registry, authorised by a signature of the chain account
+#[derive(Accounts)]
+pub struct RemoveDidForChainAccount<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ChainAccountRegistry::SEED_PREFIX, &ChainAccountRegistry::account_id_seed(&registry.account_id())],
+    bump,
+    )]
+    pub registry: Account<'info, ChainAccountRegistry>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+}
@@ -0,0 +1,36 @@
+use crate::instructions::unpaused_config::*;
+use crate::{
+    state::external_controller_registry::ExternalControllerRegistry, SolDID, DID_ACCOUNT_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Remove a DID from the registry of a non-did:sol controller DID.
+/// The controller cannot sign Solana transactions, so this is authorised by an authority on the controlled DID.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the controlled did account
+controlled_did_bump: u8,
+)]
+pub struct RemoveDidForExternalController<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ExternalControllerRegistry::SEED_PREFIX, &ExternalControllerRegistry::controller_seed(&registry.controller)],
+    bump,
+    )]
+    pub registry: Account<'info, ExternalControllerRegistry>,
+    /// An authority on the controlled DID
+    pub authority: Signer<'info>,
+    /// The controlled DID to remove from the registry
+    /// CHECK: This can be any public key. But it should derive the controlled_did_account
+    pub controlled_did: UncheckedAccount<'info>,
+    /// The account containing the controlled DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since the DID may have been closed since it was registered, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, controlled_did.key().as_ref()],
+    bump = controlled_did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub controlled_did_account: UncheckedAccount<'info>,
+}
@@ -0,0 +1,12 @@
+use crate::instructions::unpaused_config::*;
+use anchor_lang::prelude::*;
+
+/// Remove a DID from the registries of many keys at once.
+/// The keys and their registries are passed in the remaining accounts, as (key, registry) pairs.
+#[derive(Accounts)]
+pub struct RemoveDidForKeys<'info> {
+    pub config: UnpausedConfig<'info>,
+    /// The DID to remove from the registries
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+}
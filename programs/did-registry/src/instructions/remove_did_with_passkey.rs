@@ -0,0 +1,37 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::passkey_registry::PasskeyRegistry;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Remove a DID from a passkey's registry, authorised by a WebAuthn assertion of the passkey
+#[derive(Accounts)]
+pub struct RemoveDidWithPasskey<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [PasskeyRegistry::SEED_PREFIX, &PasskeyRegistry::key_seed(&registry.key)],
+    bump,
+    )]
+    pub registry: Account<'info, PasskeyRegistry>,
+    /// Pays for the nonce account, if it does not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+    /// The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the secp256r1 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
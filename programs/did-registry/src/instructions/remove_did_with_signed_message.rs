@@ -0,0 +1,42 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::key_registry::KeyRegistry;
+use crate::state::message_nonce::MessageNonce;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Remove a DID from a key's registry, authorised by the key signing a message rather than the transaction.
+#[derive(Accounts)]
+pub struct RemoveDidWithSignedMessage<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// Pays for the nonce account, if it does not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The key that owns the registry. It does not sign the transaction,
+    /// but signs the removal message in the preceding ed25519 instruction
+    /// CHECK: The signature is checked in the program
+    pub authority: UncheckedAccount<'info>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the ed25519 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
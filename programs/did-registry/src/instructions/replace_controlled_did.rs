@@ -0,0 +1,58 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{controller_registry::ControllerRegistry, did_succession::DidSuccession};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Replace a superseded DID in a controller registry with its successor
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the registry did account
+did_bump: u8,
+/// The bump seed for the successor did account
+successor_bump: u8,
+)]
+pub struct ReplaceControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// An authority on the registry's DID
+    pub authority: Signer<'info>,
+    /// The record of the superseded DID and its successor
+    #[account(
+    seeds = [DidSuccession::SEED_PREFIX, succession.did.as_ref()],
+    bump,
+    )]
+    pub succession: Account<'info, DidSuccession>,
+    /// The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.
+    /// Otherwise it is ignored (see take_pending_removal).
+    /// CHECK: Checked by take_pending_removal
+    #[account(mut)]
+    pub pending_removal: UncheckedAccount<'info>,
+    /// The account that paid for the pending removal, which receives its rent back
+    /// CHECK: Checked by take_pending_removal
+    #[account(mut)]
+    pub pending_removal_payer: UncheckedAccount<'info>,
+    /// The account containing the successor DID document
+    /// This document must contain registry.did as a controller (checked by SolDid).
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, succession.successor.as_ref()],
+    bump = successor_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub successor_account: UncheckedAccount<'info>,
+    /// The account containing the DID document that this registry applies to.
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
@@ -0,0 +1,47 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{did_succession::DidSuccession, key_registry::KeyRegistry};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Replace a superseded DID in an authority's registry with its successor
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the successor did account
+successor_bump: u8,
+)]
+pub struct ReplaceDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The authority that owns the registry
+    pub authority: Signer<'info>,
+    /// The record of the superseded DID and its successor
+    #[account(
+    seeds = [DidSuccession::SEED_PREFIX, succession.did.as_ref()],
+    bump,
+    )]
+    pub succession: Account<'info, DidSuccession>,
+    /// The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.
+    /// Otherwise it is ignored (see take_pending_removal).
+    /// CHECK: Checked by take_pending_removal
+    #[account(mut)]
+    pub pending_removal: UncheckedAccount<'info>,
+    /// The account that paid for the pending removal, which receives its rent back
+    /// CHECK: Checked by take_pending_removal
+    #[account(mut)]
+    pub pending_removal_payer: UncheckedAccount<'info>,
+    /// The account containing the successor DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, succession.successor.as_ref()],
+    bump = successor_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub successor_account: UncheckedAccount<'info>,
+}
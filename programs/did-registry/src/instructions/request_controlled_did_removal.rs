@@ -0,0 +1,46 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{controller_registry::ControllerRegistry, pending_removal::PendingRemoval};
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the registry did account
+did_bump: u8,
+)]
+pub struct RequestControlledDidRemoval<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// The pending removal, which can be finalised once the registry's removal delay has elapsed
+    #[account(
+    init,
+    payer = payer,
+    space = PendingRemoval::SIZE,
+    seeds = [PendingRemoval::SEED_PREFIX, registry.key().as_ref(), did_to_remove.key().as_ref()],
+    bump,
+    )]
+    pub pending_removal: Account<'info, PendingRemoval>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// An authority on the registry DID
+    pub authority: Signer<'info>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did_to_remove: UncheckedAccount<'info>,
+    /// The account containing the DID document that this registry applies to.
+    /// This is required, in order to check that the authority is an authority on the DID
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,31 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::{key_registry::KeyRegistry, pending_removal::PendingRemoval};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RequestDidRemoval<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The pending removal, which can be finalised once the registry's removal delay has elapsed
+    #[account(
+    init,
+    payer = payer,
+    space = PendingRemoval::SIZE,
+    seeds = [PendingRemoval::SEED_PREFIX, registry.key().as_ref(), did.key().as_ref()],
+    bump,
+    )]
+    pub pending_removal: Account<'info, PendingRemoval>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The authority that owns the registry
+    pub authority: Signer<'info>,
+    /// The DID to remove from the registry
+    /// CHECK: No checks needed here - it is just a pubkey at this stage
+    pub did: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
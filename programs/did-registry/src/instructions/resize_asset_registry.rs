@@ -0,0 +1,24 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::asset_registry::AssetRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Grow an asset's registry. Anyone can do this, as the payer pays for the extra space.
+/// Shrinking is not allowed, as the rent was not necessarily paid by the payer.
+#[derive(Accounts)]
+#[instruction(did_count: u32)]
+pub struct ResizeAssetRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [AssetRegistry::SEED_PREFIX, registry.mint.as_ref()],
+    bump,
+    constraint = AssetRegistry::calculate_size(did_count) >= registry.to_account_info().data_len() @ ErrorCode::RegistryShrink,
+    )]
+    pub registry: Account<'info, AssetRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,24 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::chain_account_registry::ChainAccountRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Grow a chain account's registry. Anyone can do this, as the payer pays for the extra space.
+/// Shrinking is not allowed, as the rent was not necessarily paid by the payer.
+#[derive(Accounts)]
+#[instruction(did_count: u32)]
+pub struct ResizeChainAccountRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ChainAccountRegistry::SEED_PREFIX, &ChainAccountRegistry::account_id_seed(&registry.account_id())],
+    bump,
+    constraint = ChainAccountRegistry::calculate_size(registry.account_id().len(), did_count) >= registry.to_account_info().data_len() @ ErrorCode::RegistryShrink,
+    )]
+    pub registry: Account<'info, ChainAccountRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
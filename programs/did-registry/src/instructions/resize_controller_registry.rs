@@ -1,21 +1,39 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::controller_registry::ControllerRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use crate::{SolDID, DID_ACCOUNT_SEED};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-#[instruction(did_count: u32)]
+#[instruction(
+did_count: u32,
+/// The bump seed for the registry did account
+did_bump: u8,
+)]
 pub struct ResizeControllerRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [ControllerRegistry::SEED_PREFIX, authority.key().as_ref()],
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
     bump,
-    realloc = TryInto::<usize>::try_into(ControllerRegistry::calculate_size(did_count)).unwrap(),
-    realloc::payer = payer,
-    realloc::zero = false,
     )]
     pub registry: Account<'info, ControllerRegistry>,
     #[account(mut)]
     pub payer: Signer<'info>,
-    #[account(mut)]
-    pub authority: Signer<'info>, // ownership is proven in the program
+    /// An authority on the registry DID
+    pub authority: Signer<'info>,
+    /// The account containing the DID document that this registry applies to.
+    /// This is required, in order to check that the authority is an authority on the DID
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,24 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::external_controller_registry::ExternalControllerRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Grow the registry of a non-did:sol controller DID. Anyone can do this, as the payer pays for the extra space.
+/// Shrinking is not allowed, as the rent was not necessarily paid by the payer.
+#[derive(Accounts)]
+#[instruction(did_count: u32)]
+pub struct ResizeExternalControllerRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ExternalControllerRegistry::SEED_PREFIX, &ExternalControllerRegistry::controller_seed(&registry.controller)],
+    bump,
+    constraint = ExternalControllerRegistry::calculate_size(registry.controller.len(), did_count) >= registry.to_account_info().data_len() @ ErrorCode::RegistryShrink,
+    )]
+    pub registry: Account<'info, ExternalControllerRegistry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
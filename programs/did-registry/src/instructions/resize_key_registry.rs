@@ -1,16 +1,18 @@
+use crate::instructions::unpaused_config::*;
 use crate::state::key_registry::KeyRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(did_count: u32)]
 pub struct ResizeKeyRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
     #[account(
     mut,
-    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref()],
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
     bump,
-    realloc = TryInto::<usize>::try_into(KeyRegistry::calculate_size(did_count)).unwrap(),
-    realloc::payer = payer,
-    realloc::zero = false,
     has_one = authority
     )]
     pub registry: Account<'info, KeyRegistry>,
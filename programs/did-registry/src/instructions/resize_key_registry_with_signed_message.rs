@@ -0,0 +1,44 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::key_registry::KeyRegistry;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+/// Resize a key's registry, authorised by the key signing a message rather than the transaction.
+#[derive(Accounts)]
+#[instruction(did_count: u32)]
+pub struct ResizeKeyRegistryWithSignedMessage<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// Pays for any extra rent, or receives the rent freed that is not owed to the registry's rent payers.
+    /// It is included in the signed message.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The key that owns the registry. It does not sign the transaction,
+    /// but signs the resize message in the preceding ed25519 instruction
+    /// CHECK: The signature is checked in the program
+    pub authority: UncheckedAccount<'info>,
+    /// The nonce included in the signed message, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the ed25519 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,38 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::message_nonce::MessageNonce;
+use crate::state::passkey_registry::PasskeyRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+#[derive(Accounts)]
+#[instruction(did_count: u32)]
+pub struct ResizePasskeyRegistry<'info> {
+    #[account(constraint = ProgramConfig::load(&config)?.allows(did_count) @ ErrorCode::RegistryLimitExceeded)]
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [PasskeyRegistry::SEED_PREFIX, &PasskeyRegistry::key_seed(&registry.key)],
+    bump,
+    )]
+    pub registry: Account<'info, PasskeyRegistry>,
+    /// Pays for the extra rent, or is refunded any rent freed that is not owed to a rent payer.
+    /// It is included in the passkey's challenge.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it
+    #[account(
+    init_if_needed,
+    payer = payer,
+    space = MessageNonce::SIZE,
+    seeds = [MessageNonce::SEED_PREFIX, registry.key().as_ref()],
+    bump,
+    )]
+    pub message_nonce: Account<'info, MessageNonce>,
+    /// The instructions sysvar, used to find the secp256r1 signature instruction
+    /// CHECK: Checked against the sysvar address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
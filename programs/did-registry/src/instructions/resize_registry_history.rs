@@ -0,0 +1,22 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::registry_history::RegistryHistory;
+use anchor_lang::prelude::*;
+
+/// Anyone can pay to grow a registry history
+#[derive(Accounts)]
+#[instruction(capacity: u32)]
+pub struct ResizeRegistryHistory<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [RegistryHistory::SEED_PREFIX, history.registry.as_ref()],
+    bump,
+    realloc = RegistryHistory::calculate_size(capacity),
+    realloc::payer = payer,
+    realloc::zero = false,
+    )]
+    pub history: Account<'info, RegistryHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,42 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::did_succession::DidSuccession;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Remove the successor recorded for a DID, e.g. if it was set by mistake.
+/// The authority must be an authority on the superseded DID.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+)]
+pub struct RevokeDidSuccessor<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    close = payer,
+    seeds = [DidSuccession::SEED_PREFIX, did.key().as_ref()],
+    bump,
+    has_one = did,
+    has_one = payer,
+    )]
+    pub succession: Account<'info, DidSuccession>,
+    /// The account that paid for the succession, which receives its rent back
+    /// CHECK: Checked against the succession
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    /// An authority on the superseded DID
+    pub authority: Signer<'info>,
+    /// The superseded DID
+    /// CHECK: Checked against the succession
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the superseded DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
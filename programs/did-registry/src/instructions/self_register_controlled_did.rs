@@ -0,0 +1,50 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::controller_registry::ControllerRegistry;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Add a controlled DID to the registry of its controller DID, as an authority on the controlled DID
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the registry did account
+did_bump: u8,
+/// The bump seed for the controlled did account being added to the registry
+controlled_did_bump: u8,
+)]
+pub struct SelfRegisterControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// An authority on the controlled DID
+    pub authority: Signer<'info>,
+    /// The controlled did to add to the registry. This is the did "identifier", not the did account
+    /// i.e. did:sol:<identifier>
+    /// CHECK: This can be any public key. But it should derive the controlled_did_account
+    pub controlled_did: UncheckedAccount<'info>,
+    /// The account containing the controlled DID document
+    /// This document must contain registry.did as a controller (checked by SolDid).
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, controlled_did.key().as_ref()],
+    bump = controlled_did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub controlled_did_account: UncheckedAccount<'info>,
+    /// The account containing the DID document that this registry applies to.
+    /// This is required, in order to check that it controls the controlled DID
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The gateway token of the authority, if the program config requires one (see verify_gateway_token).
+    /// Otherwise, this can be any account, e.g. the program.
+    /// CHECK: Verified by verify_gateway_token
+    pub gateway_token: UncheckedAccount<'info>,
+}
@@ -0,0 +1,34 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::controller_registry::ControllerRegistry;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Remove a controlled DID from a controller registry, as an authority on the controlled DID
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the controlled did account being removed from the registry
+controlled_did_bump: u8,
+)]
+pub struct SelfRemoveControlledDid<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// An authority on the controlled DID
+    pub authority: Signer<'info>,
+    /// The controlled did to remove from the registry
+    /// CHECK: This can be any public key. But it should derive the controlled_did_account
+    pub controlled_did: UncheckedAccount<'info>,
+    /// The account containing the controlled DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, controlled_did.key().as_ref()],
+    bump = controlled_did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub controlled_did_account: UncheckedAccount<'info>,
+}
@@ -0,0 +1,34 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::controller_registry::ControllerRegistry;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(
+/// The removal delay in slots
+removal_delay: u64,
+/// The bump seed for the registry did account
+did_bump: u8,
+)]
+pub struct SetControllerRegistryRemovalDelay<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [ControllerRegistry::SEED_PREFIX, registry.did.as_ref(), registry.namespace.as_ref()],
+    bump,
+    )]
+    pub registry: Account<'info, ControllerRegistry>,
+    /// An authority on the registry DID
+    pub authority: Signer<'info>,
+    /// The account containing the DID document that this registry applies to.
+    /// This is required, in order to check that the authority is an authority on the DID
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Specifically, the did account is checked to see if it has the authority as a signer
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, registry.did.as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+}
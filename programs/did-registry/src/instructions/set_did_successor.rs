@@ -0,0 +1,53 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::did_succession::DidSuccession;
+use crate::{SolDID, DID_ACCOUNT_SEED};
+use anchor_lang::prelude::*;
+
+/// Record that a DID has been superseded by a successor DID.
+/// The authority must be an authority on both DIDs.
+#[derive(Accounts)]
+#[instruction(
+/// The bump seed for the did account
+did_bump: u8,
+/// The bump seed for the successor did account
+successor_bump: u8,
+)]
+pub struct SetDidSuccessor<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    init,
+    payer = payer,
+    space = DidSuccession::SIZE,
+    seeds = [DidSuccession::SEED_PREFIX, did.key().as_ref()],
+    bump,
+    )]
+    pub succession: Account<'info, DidSuccession>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// An authority on both the superseded DID and its successor
+    pub authority: Signer<'info>,
+    /// The superseded DID
+    /// CHECK: This can be any public key. But it should derive the did_account
+    pub did: UncheckedAccount<'info>,
+    /// The account containing the superseded DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    /// Since it can be a generative DID, we do not use Account<DidAccount> here
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, did.key().as_ref()],
+    bump = did_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub did_account: UncheckedAccount<'info>,
+    /// The DID that supersedes it
+    /// CHECK: This can be any public key. But it should derive the successor_account
+    pub successor: UncheckedAccount<'info>,
+    /// The account containing the successor DID document
+    /// CHECK: This is checked for correctness by the SolDid SDK
+    #[account(
+    seeds = [DID_ACCOUNT_SEED, successor.key().as_ref()],
+    bump = successor_bump,
+    seeds::program = SolDID::id()
+    )]
+    pub successor_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
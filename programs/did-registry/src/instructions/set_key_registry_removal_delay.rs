@@ -0,0 +1,17 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::key_registry::KeyRegistry;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetKeyRegistryRemovalDelay<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The authority that owns the registry
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,20 @@
+use crate::instructions::unpaused_config::*;
+use crate::state::key_registry::KeyRegistry;
+use anchor_lang::prelude::*;
+
+/// Remove all expired DIDs from a key's registry. Anyone can sweep a registry.
+#[derive(Accounts)]
+pub struct SweepExpiredDids<'info> {
+    pub config: UnpausedConfig<'info>,
+    #[account(
+    mut,
+    seeds = [KeyRegistry::SEED_PREFIX, authority.key().as_ref(), registry.namespace.as_ref()],
+    bump,
+    has_one = authority
+    )]
+    pub registry: Account<'info, KeyRegistry>,
+    /// The key that owns the registry, which receives any rent freed by compacting it that is not owed to a rent payer
+    /// CHECK: Checked against the registry
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+}
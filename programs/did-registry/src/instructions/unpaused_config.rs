@@ -0,0 +1,43 @@
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use std::ops::Deref;
+
+/// The program config, for instructions that are blocked while the program is paused (see ProgramConfig::load)
+#[derive(Accounts)]
+pub struct UnpausedConfig<'info> {
+    /// CHECK: Only read by ProgramConfig::load
+    #[account(
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    constraint = !ProgramConfig::load(&config)?.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: UncheckedAccount<'info>,
+}
+impl<'info> Deref for UnpausedConfig<'info> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.config
+    }
+}
+
+/// As UnpausedConfig, for instructions that may charge the registry fee, which the config receives
+#[derive(Accounts)]
+pub struct UnpausedFeeConfig<'info> {
+    /// CHECK: Only read by ProgramConfig::load
+    #[account(
+    mut,
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    constraint = !ProgramConfig::load(&config)?.paused @ ErrorCode::ProgramPaused,
+    )]
+    pub config: UncheckedAccount<'info>,
+}
+impl<'info> Deref for UnpausedFeeConfig<'info> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.config
+    }
+}
@@ -0,0 +1,14 @@
+use crate::state::program_config::ProgramConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+    mut,
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,18 @@
+use crate::state::program_config::ProgramConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+    mut,
+    seeds = [ProgramConfig::SEED_PREFIX],
+    bump,
+    has_one = admin
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+    /// The account that receives the fees
+    /// CHECK: Any account can receive the fees
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
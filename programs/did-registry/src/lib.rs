@@ -4,10 +4,20 @@ mod state;
 mod util;
 
 use crate::{
-    state::{controller_registry::ControllerRegistry, key_registry::KeyRegistry},
+    state::{
+        asset_registry::AssetRegistry, chain_account_registry::ChainAccountRegistry,
+        controller_registry::ControllerRegistry,
+        external_controller_registry::ExternalControllerRegistry, key_registry::KeyRegistry,
+        passkey_registry::PasskeyRegistry, program_config::ProgramConfig,
+        registration_invitation::RegistrationInvitation, registry_history::HistoryAction,
+    },
     util::{
-        did::{SolDID, DID_ACCOUNT_SEED},
+        btc_signing::BtcAddressType,
+        did::{verify_authority, verify_flags, SolDID, DID_ACCOUNT_SEED},
         eth_signing::Secp256k1RawSignature,
+        gateway::verify_gateway_token,
+        history::{record_history, split_history},
+        webauthn::WebAuthnAssertion,
     },
 };
 use anchor_lang::prelude::*;
@@ -18,72 +28,633 @@ declare_id!("regUajGv87Pti6QRLeeRuQWrarQ1LmEyDXcAozko6Ax");
 #[program]
 pub mod did_registry {
     use super::*;
-    use crate::util::eth_signing::{validate_eth_signature, Secp256k1RawSignature};
+    use crate::state::asset_registry::AssetRegistryEntry;
+    use crate::state::controller_registry::LegacyControllerRegistry;
+    use crate::state::key_registry::LegacyKeyRegistry;
+    use crate::state::passkey_registry::PASSKEY_SIZE;
+    use crate::state::registry_history::HistoryAction;
+    use crate::util::btc_signing::{
+        btc_register_did_message, btc_remove_did_message, find_btc_key_authority,
+        validate_btc_signature,
+    };
+    use crate::util::chain_account::{
+        self, chain_account_message, parse_account_id, REGISTER_CHAIN_ACCOUNT_DID_PREFIX,
+        REMOVE_CHAIN_ACCOUNT_DID_PREFIX,
+    };
+    use crate::util::config::charge_registry_fee;
+    use crate::util::did::{
+        did_account_pairs, has_authority, to_verification_method_flags, verify_authority,
+        verify_flags,
+    };
+    use crate::util::ed25519_signing::{
+        signed_message, verify_ed25519_instruction, CLOSE_MESSAGE_PREFIX,
+        REGISTER_DID_MESSAGE_PREFIX, REMOVE_DID_MESSAGE_PREFIX, RESIZE_MESSAGE_PREFIX,
+    };
+    use crate::util::eth_signing::{
+        eth_register_did_message, eth_registry_authority, find_eth_address_authority,
+        validate_eth_signature, verify_secp256k1_instruction, Secp256k1RawSignature,
+    };
+    use crate::util::gateway::{require_no_gatekeeper_network, verify_gateway_token};
+    use crate::util::history::{record_history, split_history, take_history};
+    use crate::util::key_registries::{
+        key_registry_pairs, load_or_create_key_registry, shrink_key_registry,
+    };
+    use crate::util::legacy::{load_legacy_registry, write_registry};
+    use crate::util::pending_removal::take_pending_removal;
+    use crate::util::rent::{
+        pay_for_new_registry, record_rent_payment, refund_rent, resize_registry, split_rent_payers,
+    };
+    use crate::util::webauthn::{
+        passkey_challenge, verify_webauthn_assertion, CLOSE_PREFIX, REGISTER_DID_PREFIX,
+        REMOVE_DID_PREFIX, RESIZE_PREFIX,
+    };
     use itertools::Itertools;
     use sol_did::integrations::is_authority;
     use sol_did::state::DidAccount;
 
     /// Create an empty DID registry for a given solana key
-    pub fn create_key_registry(ctx: Context<CreateKeyRegistry>, _bump: u8) -> Result<()> {
-        ctx.accounts.registry.authority = ctx.accounts.authority.key();
+    pub fn create_key_registry(
+        ctx: Context<CreateKeyRegistry>,
+        _bump: u8,
+        namespace: Vec<u8>,
+    ) -> Result<()> {
+        charge_registry_fee(
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        registry.authority = ctx.accounts.authority.key();
+        registry.namespace = namespace;
+        let rent = registry.to_account_info().lamports();
+        record_rent_payment(&mut registry.rent_payers, ctx.accounts.payer.key(), rent)
+    }
+
+    /// Start recording the DIDs added to and removed from an authority's registry,
+    /// keeping up to the given number of entries. An existing history is reused at its current capacity.
+    pub fn create_key_registry_history(
+        ctx: Context<CreateKeyRegistryHistory>,
+        capacity: u32,
+    ) -> Result<()> {
+        // a history outlives its registry, so a registry that was closed and created again
+        // continues the history that it had before
+        let history = &mut ctx.accounts.history;
+        if history.capacity == 0 {
+            require_gt!(capacity, 0, ErrorCode::InvalidHistoryCapacity);
+            history.registry = ctx.accounts.registry.key();
+            history.capacity = capacity;
+        }
+        ctx.accounts.registry.has_history = true;
+
         Ok(())
     }
 
     /// Add a DID to an authority's registry
     pub fn register_did(ctx: Context<RegisterDid>, _did_bump: u8) -> Result<()> {
-        // ensure the authority is an authority on the did account
-        // note, anchor has already verified the constraint that did_account
-        // is the account for the did.
-        is_authority(
-            &ctx.accounts.did_account.to_account_info(),
-            None,
-            &[], // the authority must be a direct authority on the DID
-            ctx.accounts.authority.key().as_ref(),
-            None,
-            None,
+        register_did_in_registry(ctx, None)
+    }
+
+    /// Add a DID to an authority's registry that expires at the given unix timestamp, e.g. for an event ticket.
+    /// Once expired, the DID is ignored, and can be removed by anyone with sweep_expired_dids.
+    pub fn register_did_with_expiry(
+        ctx: Context<RegisterDid>,
+        _did_bump: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        register_did_in_registry(ctx, Some(expires_at))
+    }
+
+    /// Remove a DID from an authority's registry
+    pub fn remove_did(ctx: Context<RemoveDid>) -> Result<()> {
+        // registries with a removal delay must use request_did_removal instead
+        require_eq!(
+            ctx.accounts.registry.removal_delay,
+            0,
+            ErrorCode::RemovalDelayed
+        );
+
+        let did_to_remove = ctx.accounts.did.key();
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // find the DID in the registry and remove it
+        // throw an error if not found
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .dids
+            .iter()
+            .position(|did| *did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.remove_did_at(index);
+
+        record_history(
+            &mut history,
+            &[(did_to_remove, HistoryAction::Remove)],
+            ctx.accounts.authority.key(),
         )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+    }
 
-        let did = &ctx.accounts.did;
-        // ensure the did is not already registered
+    /// Add a DID to the registries of many keys at once, creating any registries that do not exist yet.
+    /// The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories
+    /// of the registries that have one, in the same order. Each key must sign, and be a direct authority on the DID.
+    pub fn register_did_for_keys<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterDidForKeys<'info>>,
+        _did_bump: u8,
+        namespace: Vec<u8>,
+    ) -> Result<()> {
+        // the keys cannot each present a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
+        let did = ctx.accounts.did.key();
+        let did_account = ctx.accounts.did_account.to_account_info();
+        let (pairs, mut histories) = key_registry_pairs(ctx.remaining_accounts, &namespace)?;
+        for pair in pairs {
+            verify_authority(&did_account, &did, &[], pair.key.key.as_ref())?;
+
+            let mut registry = load_or_create_key_registry(
+                &pair,
+                &namespace,
+                &ctx.accounts.config,
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+
+            let (mut history, rest) =
+                take_history(&registry.key(), registry.has_history, histories)?;
+            histories = rest;
+
+            // ensure the key has the verification method flags required by its registry
+            verify_flags(
+                &did_account,
+                &did,
+                &[],
+                pair.key.key.as_ref(),
+                registry.required_flags,
+            )?;
+
+            // ensure the did is not already registered
+            require_eq!(
+                registry.is_registered(&did, Clock::get()?.unix_timestamp),
+                false,
+                ErrorCode::DIDRegistered
+            );
+
+            // ensure adding the did does not exceed the account size
+            require_gte!(
+                registry.to_account_info().data_len() as usize,
+                KeyRegistry::calculate_size((registry.dids.len() + 1).try_into().unwrap()),
+                ErrorCode::RegistryFull
+            );
+
+            registry.push_did(did);
+            // the registries are not in the instruction's accounts, so must be written explicitly
+            registry.exit(&crate::ID)?;
+
+            record_history(&mut history, &[(did, HistoryAction::Add)], *pair.key.key)?;
+        }
+
+        // every history passed must belong to one of the registries
+        require!(histories.is_empty(), ErrorCode::InvalidHistory);
+
+        Ok(())
+    }
+
+    /// Remove a DID from the registries of many keys at once.
+    /// The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories
+    /// of the registries that have one, in the same order. Each key must sign.
+    pub fn remove_did_for_keys(ctx: Context<RemoveDidForKeys>, namespace: Vec<u8>) -> Result<()> {
+        let did_to_remove = ctx.accounts.did.key();
+
+        let (pairs, mut histories) = key_registry_pairs(ctx.remaining_accounts, &namespace)?;
+        for pair in pairs {
+            let mut registry: Account<KeyRegistry> = Account::try_from(pair.registry)?;
+
+            let (mut history, rest) =
+                take_history(&registry.key(), registry.has_history, histories)?;
+            histories = rest;
+
+            // registries with a removal delay must use request_did_removal instead
+            require_eq!(registry.removal_delay, 0, ErrorCode::RemovalDelayed);
+
+            let index = registry
+                .dids
+                .iter()
+                .position(|did| *did == did_to_remove)
+                .ok_or(ErrorCode::DIDNotRegistered)?;
+            registry.remove_did_at(index);
+            registry.exit(&crate::ID)?;
+
+            record_history(
+                &mut history,
+                &[(did_to_remove, HistoryAction::Remove)],
+                *pair.key.key,
+            )?;
+        }
+
+        // every history passed must belong to one of the registries
+        require!(histories.is_empty(), ErrorCode::InvalidHistory);
+
+        Ok(())
+    }
+
+    /// Remove a DID from a key's registry, once the key is no longer an authority on it,
+    /// e.g. because the key was removed from the DID, or the DID account was closed.
+    /// Anyone can prune a registry. If shrink is set, the registry is shrunk by the space of one DID,
+    /// and the rent freed is refunded to the registry's rent payers, whose accounts are passed after the history, if any.
+    /// There is no bounty for pruning: the pruner receives only rent that is not owed to a rent payer,
+    /// which is none for a registry whose rent payers are all recorded.
+    ///
+    /// If the DID has controllers, the key may still be an authority on it through one of them,
+    /// so the (did, did account) pairs of all of its controllers are passed first, and the key must not be
+    /// an authority on any of them. DIDs whose controllers have controllers themselves cannot be pruned.
+    pub fn prune_did(ctx: Context<PruneDid>, _did_bump: u8, shrink: bool) -> Result<()> {
+        let did = ctx.accounts.did.key();
+        let did_account = ctx.accounts.did_account.to_account_info();
+        let key = ctx.accounts.registry.authority;
+        // the rent payers are only refunded, and passed, if the registry is shrunk
+        let (rest, rent_payer_accounts) = if shrink {
+            split_rent_payers(
+                &ctx.accounts.registry.rent_payers,
+                &ctx.accounts.pruner.key(),
+                ctx.remaining_accounts,
+            )?
+        } else {
+            (ctx.remaining_accounts, &[][..])
+        };
+        let (controller_accounts, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            rest,
+        )?;
+
+        require!(
+            !has_authority(&did_account, &did, &[], key.as_ref())?,
+            ErrorCode::StillAuthority
+        );
+
+        // every controller of the DID must be passed, once
+        let did_data = DidAccount::try_from_or_default(&(&did_account, did))?;
+        let controllers = if controller_accounts.is_empty() {
+            vec![]
+        } else {
+            did_account_pairs(controller_accounts)?
+        };
+        require!(
+            controllers.len() == did_data.native_controllers.len()
+                && controllers
+                    .iter()
+                    .map(|(_, controller)| controller)
+                    .all_unique(),
+            ErrorCode::DIDHasControllers
+        );
+        for (controller_account, controller) in controllers {
+            require!(
+                !has_authority(controller_account, &controller, &[], key.as_ref())?,
+                ErrorCode::StillAuthority
+            );
+
+            let controller_data =
+                DidAccount::try_from_or_default(&(controller_account, controller))?;
+            // the key may be an authority through a longer controller chain, which is not followed here
+            require!(
+                controller_data.native_controllers.is_empty(),
+                ErrorCode::DIDHasControllers
+            );
+            require!(
+                did_data.is_controlled_by(&[controller_data]),
+                ErrorCode::DIDHasControllers
+            );
+        }
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .dids
+            .iter()
+            .position(|registered_did| *registered_did == did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.remove_did_at(index);
+        record_history(
+            &mut history,
+            &[(did, HistoryAction::Remove)],
+            ctx.accounts.pruner.key(),
+        )?;
+
+        if shrink {
+            let size = registry
+                .to_account_info()
+                .data_len()
+                .saturating_sub(KeyRegistry::DID_SIZE);
+            shrink_key_registry(
+                registry,
+                size,
+                &ctx.accounts.pruner.to_account_info(),
+                rent_payer_accounts,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace a superseded DID in an authority's registry with its successor, in place.
+    /// The authority must be an authority on the successor, as when registering it.
+    /// If the registry has a removal delay, the superseded DID is removed by the replacement,
+    /// so its removal must first be requested (see request_did_removal) and the delay must have elapsed.
+    pub fn replace_did(ctx: Context<ReplaceDid>, _successor_bump: u8) -> Result<()> {
+        let succession = &ctx.accounts.succession;
+        let successor_account = ctx.accounts.successor_account.to_account_info();
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        verify_authority(
+            &successor_account,
+            &succession.successor,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+        verify_flags(
+            &successor_account,
+            &succession.successor,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        // replacing the DID removes it, so it must wait for the registry's removal delay
+        take_pending_removal(
+            ctx.accounts.registry.removal_delay,
+            &ctx.accounts.registry.key(),
+            &succession.did,
+            &ctx.accounts.pending_removal,
+            &ctx.accounts.pending_removal_payer,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
         require_eq!(
-            ctx.accounts.registry.dids.contains(&did.key()),
+            registry.dids.contains(&succession.successor),
             false,
             ErrorCode::DIDRegistered
         );
+        let index = registry
+            .dids
+            .iter()
+            .position(|did| *did == succession.did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.dids[index] = succession.successor;
 
-        // ensure adding the did does not exceed the account size
+        record_history(
+            &mut history,
+            &[
+                (succession.did, HistoryAction::Remove),
+                (succession.successor, HistoryAction::Add),
+            ],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Invite a key to register a DID, as an authority on the DID (e.g. an issuer proposing a DID to a wallet).
+    /// The invitation is listed in the key's inbox, and holds a deposit as protection against spam,
+    /// until the key accepts or rejects it, or the inviter cancels it.
+    pub fn invite_did(ctx: Context<InviteDid>, _did_bump: u8) -> Result<()> {
+        let did = ctx.accounts.did.key();
+
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // the deposit is held in the invitation, on top of its rent
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.inviter.to_account_info(),
+                    to: ctx.accounts.invitation.to_account_info(),
+                },
+            ),
+            RegistrationInvitation::DEPOSIT,
+        )?;
+
+        let invitation = &mut ctx.accounts.invitation;
+        invitation.key = ctx.accounts.key.key();
+        invitation.did = did;
+        invitation.inviter = ctx.accounts.inviter.key();
+
+        ctx.accounts.inbox.push_did(ctx.accounts.key.key(), did)
+    }
+
+    /// Accept an invitation to register a DID, adding it to the key's registry.
+    /// As when registering a DID directly, the key must be an authority on the DID.
+    /// The rent and deposit of the invitation are refunded to the inviter.
+    pub fn accept_invitation(ctx: Context<AcceptInvitation>, _did_bump: u8) -> Result<()> {
+        let did = ctx.accounts.invitation.did;
+        let did_account = ctx.accounts.did_account.to_account_info();
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority holds a gateway token, if the program config requires one
+        verify_gateway_token(
+            &ctx.accounts.config,
+            &ctx.accounts.gateway_token,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        verify_authority(
+            &did_account,
+            &did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+        verify_flags(
+            &did_account,
+            &did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        require_eq!(
+            registry.is_registered(&did, Clock::get()?.unix_timestamp),
+            false,
+            ErrorCode::DIDRegistered
+        );
         require_gte!(
-            ctx.accounts.registry.to_account_info().data_len() as usize,
-            KeyRegistry::calculate_size((ctx.accounts.registry.dids.len() + 1).try_into().unwrap()),
+            registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_size((registry.dids.len() + 1).try_into().unwrap()),
             ErrorCode::RegistryFull
         );
 
-        ctx.accounts.registry.dids.push(did.key());
+        registry.push_did(did);
+        ctx.accounts.inbox.remove_did(&did);
+        record_history(
+            &mut history,
+            &[(did, HistoryAction::Add)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Reject an invitation to register a DID. The deposit of the invitation is paid to the key,
+    /// and its rent is refunded to the inviter.
+    pub fn reject_invitation(ctx: Context<RejectInvitation>) -> Result<()> {
+        // the remaining lamports (the rent) are refunded to the inviter when the invitation is closed
+        let invitation = ctx.accounts.invitation.to_account_info();
+        let authority = ctx.accounts.authority.to_account_info();
+        **invitation.try_borrow_mut_lamports()? -= RegistrationInvitation::DEPOSIT;
+        **authority.try_borrow_mut_lamports()? += RegistrationInvitation::DEPOSIT;
+
+        let did = ctx.accounts.invitation.did;
+        ctx.accounts.inbox.remove_did(&did);
+
+        Ok(())
+    }
+
+    /// Cancel an invitation to register a DID that the key has not accepted or rejected yet.
+    /// Only the inviter can cancel it, and receives the rent and deposit of the invitation back.
+    pub fn cancel_invitation(ctx: Context<CancelInvitation>) -> Result<()> {
+        let did = ctx.accounts.invitation.did;
+        ctx.accounts.inbox.remove_did(&did);
+
+        Ok(())
+    }
+
+    /// Remove all expired DIDs from a key's registry, and compact it, refunding the rent freed to the registry's
+    /// rent payers, whose accounts are passed after the history, if any. Rent not owed to a rent payer is paid to the key.
+    /// Anyone can sweep a registry.
+    pub fn sweep_expired_dids(ctx: Context<SweepExpiredDids>) -> Result<()> {
+        // the history, if any, is passed before the rent payers
+        let (rest, rent_payer_accounts) = split_rent_payers(
+            &ctx.accounts.registry.rent_payers,
+            &ctx.accounts.authority.key(),
+            ctx.remaining_accounts,
+        )?;
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            rest,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let removed = registry.remove_expired_dids(Clock::get()?.unix_timestamp);
+        require!(!removed.is_empty(), ErrorCode::NoExpiredDids);
+
+        // expired DIDs are removed by whoever sweeps the registry, so no signer is recorded
+        let changes = removed
+            .into_iter()
+            .map(|did| (did, HistoryAction::Remove))
+            .collect::<Vec<_>>();
+        record_history(&mut history, &changes, Pubkey::default())?;
+
+        shrink_key_registry(
+            registry,
+            0,
+            &ctx.accounts.authority.to_account_info(),
+            rent_payer_accounts,
+        )
+    }
+
+    /// Set the number of slots that a removal from an authority's registry must wait before it can be finalised.
+    /// The delay can only be increased, so that a compromised key cannot use it to bypass the delay,
+    /// up to KeyRegistry::MAX_REMOVAL_DELAY.
+    pub fn set_key_registry_removal_delay(
+        ctx: Context<SetKeyRegistryRemovalDelay>,
+        removal_delay: u64,
+    ) -> Result<()> {
+        require_gte!(
+            removal_delay,
+            ctx.accounts.registry.removal_delay,
+            ErrorCode::RemovalDelayDecreased
+        );
+        require_gte!(
+            KeyRegistry::MAX_REMOVAL_DELAY,
+            removal_delay,
+            ErrorCode::RemovalDelayOverflow
+        );
+
+        ctx.accounts.registry.removal_delay = removal_delay;
 
         Ok(())
     }
 
-    /// Remove a DID from an authority's registry
-    pub fn remove_did(ctx: Context<RemoveDid>) -> Result<()> {
-        let did_to_remove = &ctx.accounts.did.key();
+    /// Set the verification method flags that a key must have on a DID in order to register it in an authority's registry
+    pub fn set_key_registry_required_flags(
+        ctx: Context<SetKeyRegistryRequiredFlags>,
+        required_flags: u16,
+    ) -> Result<()> {
+        to_verification_method_flags(required_flags)?;
 
-        // find the DID in the registry and remove it
-        // throw an error if not found
-        ctx.accounts
-            .registry
+        ctx.accounts.registry.required_flags = required_flags;
+
+        Ok(())
+    }
+
+    /// Queue the removal of a DID from an authority's registry, if the registry has a removal delay
+    pub fn request_did_removal(ctx: Context<RequestDidRemoval>) -> Result<()> {
+        let registry = &ctx.accounts.registry;
+        let did = ctx.accounts.did.key();
+
+        // registries without a removal delay must use remove_did instead
+        require_neq!(registry.removal_delay, 0, ErrorCode::RemovalNotDelayed);
+
+        require_eq!(
+            registry.dids.contains(&did),
+            true,
+            ErrorCode::DIDNotRegistered
+        );
+
+        let pending_removal = &mut ctx.accounts.pending_removal;
+        pending_removal.registry = registry.key();
+        pending_removal.did = did;
+        pending_removal.payer = ctx.accounts.payer.key();
+        pending_removal.unlock_slot = Clock::get()?
+            .slot
+            .checked_add(registry.removal_delay)
+            .ok_or(ErrorCode::RemovalDelayOverflow)?;
+
+        Ok(())
+    }
+
+    /// Remove a DID from an authority's registry, once its removal delay has elapsed
+    pub fn finalize_did_removal(ctx: Context<FinalizeDidRemoval>) -> Result<()> {
+        let pending_removal = &ctx.accounts.pending_removal;
+
+        require_gte!(
+            Clock::get()?.slot,
+            pending_removal.unlock_slot,
+            ErrorCode::RemovalLocked
+        );
+
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
             .dids
             .iter()
-            .position(|did| did == did_to_remove)
-            .map_or_else(
-                || Err(ErrorCode::DIDNotRegistered.into()),
-                |index| {
-                    ctx.accounts.registry.dids.swap_remove(index);
-                    Ok(())
-                },
-            )
+            .position(|did| did == &pending_removal.did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.remove_did_at(index);
+
+        // finalising is permissionless, so no signer is recorded
+        record_history(
+            &mut history,
+            &[(pending_removal.did, HistoryAction::Remove)],
+            Pubkey::default(),
+        )
     }
 
     /// Add a DID to an eth address's registry, if the solana signer is also an authority
@@ -91,32 +662,31 @@ pub mod did_registry {
         ctx: Context<RegisterDidForEthAddress>,
         eth_address: [u8; 20],
         _did_bump: u8,
+        namespace: Vec<u8>,
     ) -> Result<()> {
+        // an eth address cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
         // ensure the eth address is an authority on the DID
-        is_authority(
+        // either through a verification method containing the address, or one containing its secp256k1 public key
+        let (form, key_data) = find_eth_address_authority(&ctx.accounts.did_account, &eth_address)?;
+
+        // ensure the matching verification method has the flags required by the registry
+        verify_flags(
             &ctx.accounts.did_account.to_account_info(),
-            None,
-            &[], // the authority must be a direct authority on the DID
-            eth_address.as_ref(),
-            None,
-            None,
-        )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+            &ctx.accounts.did.key(),
+            &[],
+            &key_data,
+            ctx.accounts.registry.required_flags,
+        )?;
 
         // ensure the sol signer is also an authority on the DID account
-        is_authority(
+        verify_authority(
             &ctx.accounts.did_account.to_account_info(),
-            None, // the authority must be a direct authority on the DID
-            &[],
+            &ctx.accounts.did.key(),
+            ctx.remaining_accounts,
             ctx.accounts.authority.key().as_ref(),
-            None,
-            None,
-        )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+        )?;
 
         let did = &ctx.accounts.did;
         // ensure the did is not already registered
@@ -126,8 +696,30 @@ pub mod did_registry {
             ErrorCode::DIDRegistered
         );
 
+        // ensure adding the did does not exceed the account size
         // TODO handle resizing
-        ctx.accounts.registry.dids.push(did.key());
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_eth_size(
+                (ctx.accounts.registry.dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        // the registry may have just been created, in which case the authority paid its rent and the registry fee.
+        // It is derived from the eth address and namespace, so setting them is a no-op otherwise
+        if ctx.accounts.registry.authority == Pubkey::default() {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.authority = eth_registry_authority(&eth_address);
+        ctx.accounts.registry.namespace = namespace;
+        ctx.accounts.registry.push_eth_did(did.key(), form);
 
         Ok(())
     }
@@ -138,31 +730,36 @@ pub mod did_registry {
         eth_address: [u8; 20],
         eth_signature: Secp256k1RawSignature,
         _did_bump: u8,
+        namespace: Vec<u8>,
     ) -> Result<()> {
+        // an eth address cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
         // Check the eth signature is a signature of the DID identifier as a byte array
-        // and that it was signed by the eth address
-        // TODO: Change message to come directly from instruction OR only accept update via cryptid
-        // TODO: This allows replay attacks
+        // and that it was signed by the eth address.
+        // The message has no nonce, so the signature can be used again to register the DID after it is removed.
+        // It is kept for existing clients - register_did_signed_by_eth_address_via_precompile binds the message
+        // to the registry and a nonce.
         validate_eth_signature(
             ctx.accounts.did_account.authority_key().as_ref(),
             &eth_signature,
             eth_address.as_ref(),
         )?;
 
-        // ensure the authority is an authority on the did account
+        // ensure the eth address is an authority on the DID
         // note, anchor has already verified the constraint that did_account
         // is the account for the did.
-        is_authority(
+        // either through a verification method containing the address, or one containing its secp256k1 public key
+        let (form, key_data) = find_eth_address_authority(&ctx.accounts.did_account, &eth_address)?;
+
+        // ensure the matching verification method has the flags required by the registry
+        verify_flags(
             &ctx.accounts.did_account.to_account_info(),
-            None,
-            &[], // the authority must be a direct authority on the DID
-            eth_address.as_ref(),
-            None,
-            None,
-        )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+            &ctx.accounts.did.key(),
+            &[],
+            &key_data,
+            ctx.accounts.registry.required_flags,
+        )?;
 
         let did = &ctx.accounts.did;
         // ensure the did is not already registered
@@ -172,43 +769,792 @@ pub mod did_registry {
             ErrorCode::DIDRegistered
         );
 
+        // ensure adding the did does not exceed the account size
         // TODO handle resizing
-        ctx.accounts.registry.dids.push(did.key());
-
-        Ok(())
-    }
-
-    pub fn resize_key_registry(_ctx: Context<ResizeKeyRegistry>, did_count: u32) -> Result<()> {
-        msg!(
-            "Resizing key registry to fit {} dids - resulting in size {}",
-            did_count,
-            KeyRegistry::calculate_size(did_count)
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_eth_size(
+                (ctx.accounts.registry.dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
         );
-        Ok(())
-    }
 
-    pub fn close_key_registry(_ctx: Context<CloseKeyRegistry>) -> Result<()> {
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the eth address and namespace, so setting them is a no-op otherwise
+        if ctx.accounts.registry.authority == Pubkey::default() {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.authority = eth_registry_authority(&eth_address);
+        ctx.accounts.registry.namespace = namespace;
+        ctx.accounts.registry.push_eth_did(did.key(), form);
+
         Ok(())
     }
 
-    /// Create an empty controller registry for a given DID
-    pub fn create_controller_registry(
-        ctx: Context<CreateControllerRegistry>,
-        _bump: u8,     // the registry PDA bump
-        _did_bump: u8, // the DID account PDA bump
+    /// Add a DID to an eth address's registry, where the eth signature is verified by the secp256k1 program
+    /// in the preceding instruction, rather than being recovered in this program, which is expensive in compute units.
+    /// Unlike register_did_signed_by_eth_address, the signed message includes the registry and its nonce,
+    /// so the signature cannot be replayed.
+    pub fn register_did_signed_by_eth_address_via_precompile(
+        ctx: Context<RegisterDidSignedByEthAddressViaPrecompile>,
+        eth_address: [u8; 20],
+        _did_bump: u8,
+        namespace: Vec<u8>,
     ) -> Result<()> {
-        ctx.accounts.registry.did = ctx.accounts.did.key();
+        // an eth address cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
 
-        // ensure the authority is an authority on the did account that the registry is being created for
-        // note, anchor has already verified the constraint that did_account
-        // is the account for the did.
-        // Theoretically, creating a controller registry for a DID is a permissionless act and anyone could do it
-        // but to prevent any future exploits, we lock it down here to a DID authority.
-        is_authority(
-            &ctx.accounts.did_account.to_account_info(),
-            None, // the authority must be a direct authority on the DID
-            &[],
-            ctx.accounts.authority.key().as_ref(),
+        // Check the secp256k1 program verified a signature of the registration message,
+        // including the current nonce, by the eth address
+        verify_secp256k1_instruction(
+            &ctx.accounts.instructions.to_account_info(),
+            eth_address.as_ref(),
+            &eth_register_did_message(
+                &ctx.accounts.registry.key(),
+                &ctx.accounts.did.key(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // ensure the eth address is an authority on the DID
+        // either through a verification method containing the address, or one containing its secp256k1 public key
+        let (form, key_data) = find_eth_address_authority(&ctx.accounts.did_account, &eth_address)?;
+
+        // ensure the matching verification method has the flags required by the registry
+        verify_flags(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.did.key(),
+            &[],
+            &key_data,
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        let did = &ctx.accounts.did;
+        // ensure the did is not already registered
+        require_eq!(
+            ctx.accounts.registry.dids.contains(&did.key()),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        // TODO handle resizing
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_eth_size(
+                (ctx.accounts.registry.dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the eth address and namespace, so setting them is a no-op otherwise
+        if ctx.accounts.registry.authority == Pubkey::default() {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.authority = eth_registry_authority(&eth_address);
+        ctx.accounts.registry.namespace = namespace;
+        ctx.accounts.registry.push_eth_did(did.key(), form);
+        // the signature cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        Ok(())
+    }
+
+    /// Add a DID to a Bitcoin address's registry, authorised by a BIP-137 signature of the registration message
+    /// by the address. P2PKH and P2WPKH addresses are supported.
+    /// The address is identified by its type and public key hash. The DID must list the address's secp256k1
+    /// public key, as an EcdsaSecp256k1VerificationKey2019 verification method with the capabilityInvocation flag.
+    pub fn register_did_signed_by_btc_address(
+        ctx: Context<RegisterDidSignedByBtcAddress>,
+        address_type: BtcAddressType,
+        pubkey_hash: [u8; 20],
+        signature: [u8; 65],
+        _did_bump: u8,
+    ) -> Result<()> {
+        // a Bitcoin address cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
+        let did = ctx.accounts.did.key();
+
+        // Check the signature is a signature of the registration message, including the current nonce,
+        // by the Bitcoin address
+        let pubkey = validate_btc_signature(
+            btc_register_did_message(
+                &ctx.accounts.registry.key(),
+                &did,
+                ctx.accounts.message_nonce.nonce,
+            )
+            .as_bytes(),
+            &signature,
+            address_type,
+            &pubkey_hash,
+        )?;
+
+        // ensure the public key of the address is an authority on the did account
+        let key_data = find_btc_key_authority(&ctx.accounts.did_account, &pubkey)?;
+
+        // ensure the matching verification method has the flags required by the registry
+        verify_flags(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            &[],
+            &key_data,
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        // ensure the did is not already registered
+        require_eq!(
+            ctx.accounts.registry.dids.contains(&did),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_size((ctx.accounts.registry.dids.len() + 1).try_into().unwrap()),
+            ErrorCode::RegistryFull
+        );
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the address, so setting the authority is a no-op otherwise
+        if ctx.accounts.registry.authority == Pubkey::default() {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.authority = address_type.registry_authority(&pubkey_hash);
+        ctx.accounts.registry.push_did(did);
+        // the signature cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        Ok(())
+    }
+
+    /// Remove a DID from a Bitcoin address's registry, authorised by a BIP-137 signature of the removal message
+    /// by the address. The DID need not list the address any more.
+    pub fn remove_did_for_btc_address(
+        ctx: Context<RemoveDidForBtcAddress>,
+        address_type: BtcAddressType,
+        pubkey_hash: [u8; 20],
+        signature: [u8; 65],
+    ) -> Result<()> {
+        let did = ctx.accounts.did.key();
+
+        // Check the signature is a signature of the removal message, including the current nonce,
+        // by the Bitcoin address
+        validate_btc_signature(
+            btc_remove_did_message(
+                &ctx.accounts.registry.key(),
+                &did,
+                ctx.accounts.message_nonce.nonce,
+            )
+            .as_bytes(),
+            &signature,
+            address_type,
+            &pubkey_hash,
+        )?;
+
+        // find the DID in the registry and remove it
+        // throw an error if not found
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .dids
+            .iter()
+            .position(|registered| *registered == did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.remove_did_at(index);
+
+        // the signature cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        Ok(())
+    }
+
+    /// Add a DID to a key's registry, authorised by an ed25519 signature of the key over a registration message,
+    /// rather than by the key signing the transaction. This allows a relayer to submit and pay for the registration.
+    pub fn register_did_with_signed_message(
+        ctx: Context<RegisterDidWithSignedMessage>,
+        _did_bump: u8,
+        namespace: Vec<u8>,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let did = ctx.accounts.did.key();
+
+        // ensure the authority holds a gateway token, if the program config requires one
+        verify_gateway_token(
+            &ctx.accounts.config,
+            &ctx.accounts.gateway_token,
+            &authority,
+        )?;
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the authority and namespace, so setting them is a no-op otherwise
+        if ctx.accounts.registry.authority == Pubkey::default() {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.authority = authority;
+        ctx.accounts.registry.namespace = namespace;
+
+        // ensure the authority signed the registration message, including the current nonce
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions.to_account_info(),
+            &authority,
+            &signed_message(
+                REGISTER_DID_MESSAGE_PREFIX,
+                &ctx.accounts.registry.key(),
+                did.as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority is an authority on the did account
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            controller_chain,
+            authority.as_ref(),
+        )?;
+
+        // ensure the authority has the verification method flags required by the registry
+        verify_flags(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            controller_chain,
+            authority.as_ref(),
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        // ensure the did is not already registered
+        require_eq!(
+            ctx.accounts
+                .registry
+                .is_registered(&did, Clock::get()?.unix_timestamp),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            KeyRegistry::calculate_size((ctx.accounts.registry.dids.len() + 1).try_into().unwrap()),
+            ErrorCode::RegistryFull
+        );
+
+        ctx.accounts.registry.push_did(did);
+        // the signature cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        record_history(&mut history, &[(did, HistoryAction::Add)], authority)
+    }
+
+    /// Remove a DID from a key's registry, authorised by an ed25519 signature of the key over a removal message,
+    /// rather than by the key signing the transaction.
+    pub fn remove_did_with_signed_message(ctx: Context<RemoveDidWithSignedMessage>) -> Result<()> {
+        // registries with a removal delay must use request_did_removal instead
+        require_eq!(
+            ctx.accounts.registry.removal_delay,
+            0,
+            ErrorCode::RemovalDelayed
+        );
+
+        let did_to_remove = ctx.accounts.did.key();
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority signed the removal message, including the current nonce
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.authority.key(),
+            &signed_message(
+                REMOVE_DID_MESSAGE_PREFIX,
+                &ctx.accounts.registry.key(),
+                did_to_remove.as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .dids
+            .iter()
+            .position(|did| *did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.remove_did_at(index);
+        // the signature cannot be used again
+        ctx.accounts.message_nonce.consume(registry.key());
+
+        record_history(
+            &mut history,
+            &[(did_to_remove, HistoryAction::Remove)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Resize a key's registry, authorised by an ed25519 signature of the key over a resize message,
+    /// rather than by the key signing the transaction.
+    pub fn resize_key_registry_with_signed_message(
+        ctx: Context<ResizeKeyRegistryWithSignedMessage>,
+        did_count: u32,
+    ) -> Result<()> {
+        // the payer is included in the message, as it pays for, or is refunded, the change in rent
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.authority.key(),
+            &signed_message(
+                RESIZE_MESSAGE_PREFIX,
+                &ctx.accounts.registry.key(),
+                &[
+                    did_count.to_le_bytes().as_ref(),
+                    ctx.accounts.payer.key().as_ref(),
+                ]
+                .concat(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // the signature cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        msg!(
+            "Resizing key registry to fit {} dids - resulting in size {}",
+            did_count,
+            KeyRegistry::calculate_size(did_count)
+        );
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            KeyRegistry::calculate_size(did_count),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Close a key's registry, authorised by an ed25519 signature of the key over a close message,
+    /// rather than by the key signing the transaction.
+    pub fn close_key_registry_with_signed_message(
+        ctx: Context<CloseKeyRegistryWithSignedMessage>,
+    ) -> Result<()> {
+        // closing a registry removes all its DIDs, so it is not allowed while they are protected by a removal delay
+        require!(
+            ctx.accounts.registry.removal_delay == 0 || ctx.accounts.registry.dids.is_empty(),
+            ErrorCode::RegistryNotEmpty
+        );
+
+        // the payer is included in the message, as it receives any rent not owed to the registry's rent payers
+        verify_ed25519_instruction(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.authority.key(),
+            &signed_message(
+                CLOSE_MESSAGE_PREFIX,
+                &ctx.accounts.registry.key(),
+                ctx.accounts.payer.key().as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // the signature cannot be used again, even if the registry is created again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        // the history, if any, is passed before the rent payers
+        let (rest, rent_payer_accounts) = split_rent_payers(
+            &ctx.accounts.registry.rent_payers,
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            rest,
+        )?;
+
+        // the history outlives the registry, so it records the removal of the remaining DIDs
+        let changes = ctx
+            .accounts
+            .registry
+            .dids
+            .iter()
+            .map(|did| (*did, HistoryAction::Remove))
+            .collect::<Vec<_>>();
+        record_history(&mut history, &changes, ctx.accounts.authority.key())?;
+
+        let registry = &mut ctx.accounts.registry;
+        let registry_info = registry.to_account_info();
+        refund_rent(
+            &registry_info,
+            &mut registry.rent_payers,
+            registry_info.lamports(),
+            &ctx.accounts.payer.to_account_info(),
+            rent_payer_accounts,
+        )
+    }
+
+    /// Resize an authority's registry. The payer pays for any extra rent,
+    /// and the rent freed by shrinking it is refunded to the parties that paid for it,
+    /// whose accounts are passed in the remaining accounts.
+    pub fn resize_key_registry(ctx: Context<ResizeKeyRegistry>, did_count: u32) -> Result<()> {
+        msg!(
+            "Resizing key registry to fit {} dids - resulting in size {}",
+            did_count,
+            KeyRegistry::calculate_size(did_count)
+        );
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            KeyRegistry::calculate_size(did_count),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Close an authority's registry. Its rent is refunded to the parties that paid for it,
+    /// whose accounts are passed in the remaining accounts, and anything left over is paid to the payer.
+    /// A party that co-signs the transaction waives its refund in favour of the payer.
+    /// If the registry has a history, the removal of its remaining DIDs is recorded,
+    /// and the history is passed before the accounts of the parties that paid for it.
+    pub fn close_key_registry(ctx: Context<CloseKeyRegistry>) -> Result<()> {
+        // closing a registry removes all its DIDs, so it is not allowed while they are protected by a removal delay
+        require!(
+            ctx.accounts.registry.removal_delay == 0 || ctx.accounts.registry.dids.is_empty(),
+            ErrorCode::RegistryNotEmpty
+        );
+
+        // the history, if any, is passed before the rent payers
+        let (rest, rent_payer_accounts) = split_rent_payers(
+            &ctx.accounts.registry.rent_payers,
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            rest,
+        )?;
+
+        // the history outlives the registry, so it records the removal of the remaining DIDs
+        let changes = ctx
+            .accounts
+            .registry
+            .dids
+            .iter()
+            .map(|did| (*did, HistoryAction::Remove))
+            .collect::<Vec<_>>();
+        record_history(&mut history, &changes, ctx.accounts.authority.key())?;
+
+        let registry = &mut ctx.accounts.registry;
+        let registry_info = registry.to_account_info();
+        refund_rent(
+            &registry_info,
+            &mut registry.rent_payers,
+            registry_info.lamports(),
+            &ctx.accounts.payer.to_account_info(),
+            rent_payer_accounts,
+        )
+    }
+
+    /// Migrate a key (or eth address) registry created by the first release of the program that was filled,
+    /// leaving no room for the fields appended since, so that it cannot be loaded until it is migrated.
+    /// Registries in the original layout that were not filled load without migration.
+    /// The payer pays the rent for the appended fields. Anyone can migrate a registry.
+    pub fn migrate_key_registry(ctx: Context<MigrateKeyRegistry>) -> Result<()> {
+        let registry_info = ctx.accounts.registry.to_account_info();
+        let legacy = load_legacy_registry::<KeyRegistry, LegacyKeyRegistry>(&registry_info)?;
+
+        // the appended fields take their defaults, as they do for registries that were not filled
+        let mut registry = KeyRegistry {
+            version: legacy.version,
+            authority: legacy.authority,
+            expiries: vec![KeyRegistry::NO_EXPIRY; legacy.dids.len()],
+            dids: legacy.dids,
+            removal_delay: 0,
+            required_flags: 0,
+            eth_address_forms: vec![],
+            has_history: false,
+            namespace: vec![],
+            rent_payers: vec![],
+        };
+        resize_registry(
+            &registry_info,
+            &mut registry.rent_payers,
+            KeyRegistry::calculate_size(registry.dids.len() as u32),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+        )?;
+
+        write_registry(&registry_info, &registry)
+    }
+
+    /// Add a DID to a passkey's registry, authorised by a WebAuthn assertion of the passkey over the
+    /// registration challenge. The assertion signature is verified by the secp256r1 program in the preceding instruction.
+    /// The registry is created if it does not yet exist.
+    pub fn register_did_with_passkey(
+        ctx: Context<RegisterDidWithPasskey>,
+        key: [u8; 33],
+        assertion: WebAuthnAssertion,
+        _did_bump: u8,
+    ) -> Result<()> {
+        // a passkey cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
+        let did = ctx.accounts.did.key();
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the key, so setting it is a no-op otherwise
+        if ctx.accounts.registry.key == [0; PASSKEY_SIZE] {
+            pay_for_new_registry(
+                &ctx.accounts.registry.to_account_info(),
+                &mut ctx.accounts.registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        ctx.accounts.registry.key = key;
+
+        // ensure the passkey signed the registration challenge, including the current nonce
+        verify_webauthn_assertion(
+            &ctx.accounts.instructions.to_account_info(),
+            &key,
+            &assertion,
+            &passkey_challenge(
+                REGISTER_DID_PREFIX,
+                &ctx.accounts.registry.key(),
+                did.as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // ensure the passkey is an authority on the did account
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            ctx.remaining_accounts,
+            &key,
+        )?;
+
+        // ensure the did is not already registered
+        require_eq!(
+            ctx.accounts.registry.dids.contains(&did),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            PasskeyRegistry::calculate_size(
+                (ctx.accounts.registry.dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        ctx.accounts.registry.dids.push(did);
+        // the assertion cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        Ok(())
+    }
+
+    /// Remove a DID from a passkey's registry, authorised by a WebAuthn assertion of the passkey
+    pub fn remove_did_with_passkey(
+        ctx: Context<RemoveDidWithPasskey>,
+        assertion: WebAuthnAssertion,
+    ) -> Result<()> {
+        let did_to_remove = ctx.accounts.did.key();
+
+        verify_webauthn_assertion(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.registry.key,
+            &assertion,
+            &passkey_challenge(
+                REMOVE_DID_PREFIX,
+                &ctx.accounts.registry.key(),
+                did_to_remove.as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .dids
+            .iter()
+            .position(|did| *did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.dids.swap_remove(index);
+        // the assertion cannot be used again
+        ctx.accounts.message_nonce.consume(registry.key());
+
+        Ok(())
+    }
+
+    /// Resize a passkey's registry, authorised by a WebAuthn assertion of the passkey.
+    /// As with resize_key_registry, the payer pays for any extra rent, and the rent freed by shrinking it
+    /// is refunded to the parties that paid for it, whose accounts are passed in the remaining accounts.
+    pub fn resize_passkey_registry(
+        ctx: Context<ResizePasskeyRegistry>,
+        did_count: u32,
+        assertion: WebAuthnAssertion,
+    ) -> Result<()> {
+        verify_webauthn_assertion(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.registry.key,
+            &assertion,
+            &passkey_challenge(
+                RESIZE_PREFIX,
+                &ctx.accounts.registry.key(),
+                &[
+                    did_count.to_le_bytes().as_ref(),
+                    ctx.accounts.payer.key().as_ref(),
+                ]
+                .concat(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // the assertion cannot be used again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        msg!(
+            "Resizing passkey registry to fit {} dids - resulting in size {}",
+            did_count,
+            PasskeyRegistry::calculate_size(did_count)
+        );
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            PasskeyRegistry::calculate_size(did_count),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Close a passkey's registry, authorised by a WebAuthn assertion of the passkey.
+    /// Its rent is refunded to the parties that paid for it, as in close_key_registry.
+    pub fn close_passkey_registry(
+        ctx: Context<ClosePasskeyRegistry>,
+        assertion: WebAuthnAssertion,
+    ) -> Result<()> {
+        verify_webauthn_assertion(
+            &ctx.accounts.instructions.to_account_info(),
+            &ctx.accounts.registry.key,
+            &assertion,
+            &passkey_challenge(
+                CLOSE_PREFIX,
+                &ctx.accounts.registry.key(),
+                ctx.accounts.payer.key().as_ref(),
+                ctx.accounts.message_nonce.nonce,
+            ),
+        )?;
+
+        // the assertion cannot be used again, even if the registry is created again
+        let registry = ctx.accounts.registry.key();
+        ctx.accounts.message_nonce.consume(registry);
+
+        let registry = &mut ctx.accounts.registry;
+        let registry_info = registry.to_account_info();
+        refund_rent(
+            &registry_info,
+            &mut registry.rent_payers,
+            registry_info.lamports(),
+            &ctx.accounts.payer.to_account_info(),
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Add a DID to the registry of an account on another chain, identified by its CAIP-10 account id.
+    /// The chain account must be an authority on the DID, and sign the registration message
+    /// in the format of its namespace. The registry is created if it does not yet exist.
+    pub fn register_did_for_chain_account(
+        ctx: Context<RegisterDidForChainAccount>,
+        account_id: String,
+        signature: Vec<u8>,
+        _did_bump: u8,
+    ) -> Result<()> {
+        // an account on another chain cannot hold a gateway token, so this is blocked if the program config requires one
+        require_no_gatekeeper_network(&ctx.accounts.config)?;
+
+        let parsed_account_id = parse_account_id(&account_id)?;
+        let namespace = chain_account::namespace(parsed_account_id.namespace)?;
+        let key_data = namespace.key_data(parsed_account_id.address)?;
+        let did = ctx.accounts.did.key();
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the account id, so setting it is a no-op otherwise
+        let registry = &mut ctx.accounts.registry;
+        if registry.namespace.is_empty() {
+            pay_for_new_registry(
+                &registry.to_account_info(),
+                &mut registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        registry.namespace = parsed_account_id.namespace.to_string();
+        registry.reference = parsed_account_id.reference.to_string();
+        registry.address = parsed_account_id.address.to_string();
+
+        // ensure the chain account signed the registration message, including the current nonce
+        namespace.verify_signature(
+            &key_data,
+            &chain_account_message(REGISTER_CHAIN_ACCOUNT_DID_PREFIX, &registry.key(), &did),
+            registry.nonce,
+            &signature,
+        )?;
+
+        // ensure the chain account is an authority on the DID
+        is_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            None,
+            &[], // the chain account must be a direct authority on the DID
+            &key_data,
             None,
             None,
         )
@@ -216,169 +1562,1296 @@ pub mod did_registry {
         .then_some(())
         .ok_or(ErrorCode::NotAuthority)?;
 
+        let registry = &mut ctx.accounts.registry;
+        // ensure the did is not already registered
+        require_eq!(
+            registry.dids.contains(&did),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            registry.to_account_info().data_len() as usize,
+            ChainAccountRegistry::calculate_size(
+                account_id.len(),
+                (registry.dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        registry.dids.push(did);
+        // the signature cannot be used again
+        registry.nonce += 1;
+
+        Ok(())
+    }
+
+    /// Remove a DID from a chain account's registry, authorised by a signature of the removal message
+    /// by the chain account, in the format of its namespace
+    pub fn remove_did_for_chain_account(
+        ctx: Context<RemoveDidForChainAccount>,
+        signature: Vec<u8>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let did_to_remove = ctx.accounts.did.key();
+        let namespace = chain_account::namespace(&registry.namespace)?;
+        let key_data = namespace.key_data(&registry.address)?;
+
+        // ensure the chain account signed the removal message, including the current nonce
+        namespace.verify_signature(
+            &key_data,
+            &chain_account_message(
+                REMOVE_CHAIN_ACCOUNT_DID_PREFIX,
+                &registry.key(),
+                &did_to_remove,
+            ),
+            registry.nonce,
+            &signature,
+        )?;
+
+        let index = registry
+            .dids
+            .iter()
+            .position(|did| *did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.dids.swap_remove(index);
+        // the signature cannot be used again
+        registry.nonce += 1;
+
+        Ok(())
+    }
+
+    /// Grow a chain account's registry to fit the given number of DIDs
+    pub fn resize_chain_account_registry(
+        ctx: Context<ResizeChainAccountRegistry>,
+        did_count: u32,
+    ) -> Result<()> {
+        let size = ChainAccountRegistry::calculate_size(
+            ctx.accounts.registry.account_id().len(),
+            did_count,
+        );
+        msg!(
+            "Resizing chain account registry to fit {} dids - resulting in size {}",
+            did_count,
+            size
+        );
+        // the registry only grows (see ResizeChainAccountRegistry), so no rent payers are refunded
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            size,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+        )
+    }
+
+    /// Create an empty controller registry for a given DID
+    pub fn create_controller_registry(
+        ctx: Context<CreateControllerRegistry>,
+        _bump: u8,     // the registry PDA bump
+        _did_bump: u8, // the DID account PDA bump
+        namespace: Vec<u8>,
+    ) -> Result<()> {
+        charge_registry_fee(
+            &ctx.accounts.config.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        registry.did = ctx.accounts.did.key();
+        registry.namespace = namespace;
+        let rent = registry.to_account_info().lamports();
+        record_rent_payment(&mut registry.rent_payers, ctx.accounts.payer.key(), rent)?;
+
+        // ensure the authority is an authority on the did account that the registry is being created for
+        // note, anchor has already verified the constraint that did_account
+        // is the account for the did.
+        // Theoretically, creating a controller registry for a DID is a permissionless act and anyone could do it
+        // but to prevent any future exploits, we lock it down here to a DID authority.
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.did.key(),
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Start recording the DIDs added to and removed from a controller registry,
+    /// keeping up to the given number of entries. An existing history is reused at its current capacity.
+    pub fn create_controller_registry_history(
+        ctx: Context<CreateControllerRegistryHistory>,
+        capacity: u32,
+        _did_bump: u8,
+    ) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        let history = &mut ctx.accounts.history;
+        if history.capacity == 0 {
+            require_gt!(capacity, 0, ErrorCode::InvalidHistoryCapacity);
+            history.registry = ctx.accounts.registry.key();
+            history.capacity = capacity;
+        }
+        ctx.accounts.registry.has_history = true;
+
+        Ok(())
+    }
+
+    /// Add a controlled DID to an authority's controller registry
+    pub fn register_controlled_did(
+        ctx: Context<RegisterControlledDid>,
+        _did_bump: u8,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        msg!(
+            "Registering controlled did {} on controller registry for did {}",
+            ctx.accounts.controlled_did.key(),
+            ctx.accounts.registry.did.key()
+        );
+        msg!("Controller registry: {}", ctx.accounts.registry.key());
+
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority holds a gateway token, if the program config requires one
+        verify_gateway_token(
+            &ctx.accounts.config,
+            &ctx.accounts.gateway_token,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        // 1. Ensure ctx.accounts.authority is an authority on ctx.accounts.registry.did
+        // note, anchor has already verified the constraint that did_account
+        // is the account for the registry's did.
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // ensure the authority has the verification method flags required by the registry
+        verify_flags(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        // 2. Ensure ctx.accounts.controlled_did is controlled by ctx.accounts.registry.did
+        let controlled_did = &ctx.accounts.controlled_did;
+
+        let controller_account_info_and_pubkey = &(
+            &ctx.accounts.did_account.to_account_info(),
+            ctx.accounts.registry.did,
+        );
+        let controller_did_data =
+            DidAccount::try_from_or_default(controller_account_info_and_pubkey)?;
+
+        let controlled_account_info_and_pubkey = &(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            ctx.accounts.controlled_did.key(),
+        );
+        let controlled_did_data =
+            DidAccount::try_from_or_default(controlled_account_info_and_pubkey)?;
+
+        msg!("Controllers: {:?}", controlled_did_data.native_controllers);
+        msg!("authority key: {:?}", controller_did_data.authority_key());
+        msg!(
+            "contains: {:?}",
+            controlled_did_data
+                .native_controllers
+                .iter()
+                .contains(&controller_did_data.authority_key())
+        );
+        controlled_did_data
+            .is_controlled_by(&[controller_did_data])
+            .then_some(())
+            .ok_or(ErrorCode::NotController)?;
+
+        // 3. Ensure the did is not already registered
+        require_eq!(
+            ctx.accounts
+                .registry
+                .controlled_dids
+                .contains(&controlled_did.key()),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            ctx.accounts.registry.to_account_info().data_len() as usize,
+            ControllerRegistry::calculate_size(
+                (ctx.accounts.registry.controlled_dids.len() + 1)
+                    .try_into()
+                    .unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        ctx.accounts
+            .registry
+            .controlled_dids
+            .push(controlled_did.key());
+
+        record_history(
+            &mut history,
+            &[(controlled_did.key(), HistoryAction::Add)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Remove a controlled DID from a controller registry
+    pub fn remove_controlled_did(ctx: Context<RemoveControlledDid>, _did_bump: u8) -> Result<()> {
+        // registries with a removal delay must use request_controlled_did_removal instead
+        require_eq!(
+            ctx.accounts.registry.removal_delay,
+            0,
+            ErrorCode::RemovalDelayed
+        );
+
+        let did_to_remove = ctx.accounts.did_to_remove.key();
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority is an authority on the did account that the registry is being created for
+        // note, anchor has already verified the constraint that did_account
+        // is the account for the did.
+        // Theoretically, creating a controller registry for a DID is a permissionless act and anyone could do it
+        // but to prevent any future exploits, we lock it down here to a DID authority.
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // find the DID in the registry and remove it
+        // throw an error if not found
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .controlled_dids
+            .iter()
+            .position(|did| *did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.controlled_dids.swap_remove(index);
+
+        record_history(
+            &mut history,
+            &[(did_to_remove, HistoryAction::Remove)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Add a controlled DID to the registry of its controller DID, as an authority on the controlled DID.
+    /// This allows a controlled DID to list itself, without waiting for the controller to act.
+    pub fn self_register_controlled_did(
+        ctx: Context<SelfRegisterControlledDid>,
+        _did_bump: u8,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        let controlled_did = ctx.accounts.controlled_did.key();
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority holds a gateway token, if the program config requires one
+        verify_gateway_token(
+            &ctx.accounts.config,
+            &ctx.accounts.gateway_token,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        // 1. Ensure ctx.accounts.authority is an authority on the controlled DID
+        verify_authority(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            &controlled_did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // 2. Ensure the controlled DID is still controlled by the registry's DID
+        let controller_did_data = DidAccount::try_from_or_default(&(
+            &ctx.accounts.did_account.to_account_info(),
+            ctx.accounts.registry.did,
+        ))?;
+        let controlled_did_data = DidAccount::try_from_or_default(&(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            controlled_did,
+        ))?;
+        controlled_did_data
+            .is_controlled_by(&[controller_did_data])
+            .then_some(())
+            .ok_or(ErrorCode::NotController)?;
+
+        // 3. Ensure the did is not already registered
+        let registry = &mut ctx.accounts.registry;
+        require_eq!(
+            registry.controlled_dids.contains(&controlled_did),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            registry.to_account_info().data_len() as usize,
+            ControllerRegistry::calculate_size(
+                (registry.controlled_dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        registry.controlled_dids.push(controlled_did);
+
+        record_history(
+            &mut history,
+            &[(controlled_did, HistoryAction::Add)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Remove a controlled DID from a controller registry, as an authority on the controlled DID.
+    /// This is allowed whether or not the DID is still controlled by the registry's DID.
+    /// Registries with a removal delay must use self_request_controlled_did_removal instead,
+    /// so that the other authorities on the controlled DID can cancel the removal.
+    pub fn self_remove_controlled_did(
+        ctx: Context<SelfRemoveControlledDid>,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        let controlled_did = ctx.accounts.controlled_did.key();
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        // registries with a removal delay must use self_request_controlled_did_removal instead
+        require_eq!(
+            ctx.accounts.registry.removal_delay,
+            0,
+            ErrorCode::RemovalDelayed
+        );
+
+        verify_authority(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            &controlled_did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .controlled_dids
+            .iter()
+            .position(|did| *did == controlled_did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.controlled_dids.swap_remove(index);
+
+        record_history(
+            &mut history,
+            &[(controlled_did, HistoryAction::Remove)],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Remove DIDs from a controller registry, once they are no longer controlled by the registry's DID,
+    /// e.g. because the controller was removed from the controlled DID.
+    /// Anyone can prune a registry. The controlled DIDs are passed in the remaining accounts,
+    /// as (did, did account) pairs, so that many entries can be pruned at once.
+    pub fn prune_controlled_did<'info>(
+        ctx: Context<'_, '_, '_, 'info, PruneControlledDid<'info>>,
+        _did_bump: u8,
+    ) -> Result<()> {
+        let controller_did_data = [DidAccount::try_from_or_default(&(
+            &ctx.accounts.did_account.to_account_info(),
+            ctx.accounts.registry.did,
+        ))?];
+
+        // the history, if any, is passed after the (did, did account) pairs
+        let (pairs, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let mut changes = vec![];
+        for (controlled_did_account, controlled_did) in did_account_pairs(pairs)? {
+            let controlled_did_data =
+                DidAccount::try_from_or_default(&(controlled_did_account, controlled_did))?;
+            require!(
+                !controlled_did_data.is_controlled_by(&controller_did_data),
+                ErrorCode::StillControlled
+            );
+
+            let index = registry
+                .controlled_dids
+                .iter()
+                .position(|did| *did == controlled_did)
+                .ok_or(ErrorCode::DIDNotRegistered)?;
+            registry.controlled_dids.swap_remove(index);
+            changes.push((controlled_did, HistoryAction::Remove));
+        }
+
+        // anyone can prune a registry, so no signer is recorded
+        record_history(&mut history, &changes, Pubkey::default())
+    }
+
+    /// Replace a superseded DID in a controller registry with its successor, in place.
+    /// The successor must be controlled by the registry's DID, as when registering it.
+    /// If the registry has a removal delay, the superseded DID is removed by the replacement,
+    /// so its removal must first be requested (see request_controlled_did_removal) and the delay must have elapsed.
+    pub fn replace_controlled_did(
+        ctx: Context<ReplaceControlledDid>,
+        _did_bump: u8,
+        _successor_bump: u8,
+    ) -> Result<()> {
+        let did_account = ctx.accounts.did_account.to_account_info();
+        let registry_did = ctx.accounts.registry.did;
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        verify_authority(
+            &did_account,
+            &registry_did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+        verify_flags(
+            &did_account,
+            &registry_did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+            ctx.accounts.registry.required_flags,
+        )?;
+
+        let succession = &ctx.accounts.succession;
+        let controller_did_data = DidAccount::try_from_or_default(&(&did_account, registry_did))?;
+        let successor_did_data = DidAccount::try_from_or_default(&(
+            &ctx.accounts.successor_account.to_account_info(),
+            succession.successor,
+        ))?;
+        require!(
+            successor_did_data.is_controlled_by(&[controller_did_data]),
+            ErrorCode::NotController
+        );
+
+        // replacing the DID removes it, so it must wait for the registry's removal delay
+        take_pending_removal(
+            ctx.accounts.registry.removal_delay,
+            &ctx.accounts.registry.key(),
+            &succession.did,
+            &ctx.accounts.pending_removal,
+            &ctx.accounts.pending_removal_payer,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        require_eq!(
+            registry.controlled_dids.contains(&succession.successor),
+            false,
+            ErrorCode::DIDRegistered
+        );
+        let index = registry
+            .controlled_dids
+            .iter()
+            .position(|did| *did == succession.did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.controlled_dids[index] = succession.successor;
+
+        record_history(
+            &mut history,
+            &[
+                (succession.did, HistoryAction::Remove),
+                (succession.successor, HistoryAction::Add),
+            ],
+            ctx.accounts.authority.key(),
+        )
+    }
+
+    /// Set the number of slots that a removal from a controller registry must wait before it can be finalised.
+    /// The delay can only be increased, so that a compromised key cannot use it to bypass the delay,
+    /// up to KeyRegistry::MAX_REMOVAL_DELAY.
+    pub fn set_controller_registry_removal_delay(
+        ctx: Context<SetControllerRegistryRemovalDelay>,
+        removal_delay: u64,
+        _did_bump: u8,
+    ) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        require_gte!(
+            removal_delay,
+            ctx.accounts.registry.removal_delay,
+            ErrorCode::RemovalDelayDecreased
+        );
+        require_gte!(
+            KeyRegistry::MAX_REMOVAL_DELAY,
+            removal_delay,
+            ErrorCode::RemovalDelayOverflow
+        );
+
+        ctx.accounts.registry.removal_delay = removal_delay;
+
+        Ok(())
+    }
+
+    /// Set the verification method flags that an authority must have on the registry DID in order to register DIDs
+    pub fn set_controller_registry_required_flags(
+        ctx: Context<SetControllerRegistryRequiredFlags>,
+        required_flags: u16,
+        _did_bump: u8,
+    ) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        to_verification_method_flags(required_flags)?;
+
+        ctx.accounts.registry.required_flags = required_flags;
+
+        Ok(())
+    }
+
+    /// Queue the removal of a controlled DID from a controller registry, if the registry has a removal delay
+    pub fn request_controlled_did_removal(
+        ctx: Context<RequestControlledDidRemoval>,
+        _did_bump: u8,
+    ) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        let registry = &ctx.accounts.registry;
+        let did_to_remove = ctx.accounts.did_to_remove.key();
+
+        // registries without a removal delay must use remove_controlled_did instead
+        require_neq!(registry.removal_delay, 0, ErrorCode::RemovalNotDelayed);
+
+        require_eq!(
+            registry.controlled_dids.contains(&did_to_remove),
+            true,
+            ErrorCode::DIDNotRegistered
+        );
+
+        let pending_removal = &mut ctx.accounts.pending_removal;
+        pending_removal.registry = registry.key();
+        pending_removal.did = did_to_remove;
+        pending_removal.payer = ctx.accounts.payer.key();
+        pending_removal.unlock_slot = Clock::get()?
+            .slot
+            .checked_add(registry.removal_delay)
+            .ok_or(ErrorCode::RemovalDelayOverflow)?;
+
+        Ok(())
+    }
+
+    /// Queue the removal of a controlled DID from a controller registry, as an authority on the controlled DID,
+    /// if the registry has a removal delay (see self_remove_controlled_did).
+    pub fn self_request_controlled_did_removal(
+        ctx: Context<SelfRequestControlledDidRemoval>,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        let controlled_did = ctx.accounts.controlled_did.key();
+        verify_authority(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            &controlled_did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        let registry = &ctx.accounts.registry;
+
+        // registries without a removal delay must use self_remove_controlled_did instead
+        require_neq!(registry.removal_delay, 0, ErrorCode::RemovalNotDelayed);
+
+        require_eq!(
+            registry.controlled_dids.contains(&controlled_did),
+            true,
+            ErrorCode::DIDNotRegistered
+        );
+
+        let pending_removal = &mut ctx.accounts.pending_removal;
+        pending_removal.registry = registry.key();
+        pending_removal.did = controlled_did;
+        pending_removal.payer = ctx.accounts.payer.key();
+        pending_removal.unlock_slot = Clock::get()?
+            .slot
+            .checked_add(registry.removal_delay)
+            .ok_or(ErrorCode::RemovalDelayOverflow)?;
+
+        Ok(())
+    }
+
+    /// Remove a controlled DID from a controller registry, once its removal delay has elapsed
+    pub fn finalize_controlled_did_removal(
+        ctx: Context<FinalizeControlledDidRemoval>,
+    ) -> Result<()> {
+        let pending_removal = &ctx.accounts.pending_removal;
+
+        require_gte!(
+            Clock::get()?.slot,
+            pending_removal.unlock_slot,
+            ErrorCode::RemovalLocked
+        );
+
+        let (_, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            ctx.remaining_accounts,
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .controlled_dids
+            .iter()
+            .position(|did| did == &pending_removal.did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.controlled_dids.swap_remove(index);
+
+        // finalising is permissionless, so no signer is recorded
+        record_history(
+            &mut history,
+            &[(pending_removal.did, HistoryAction::Remove)],
+            Pubkey::default(),
+        )
+    }
+
+    /// Resize a controller registry, as an authority on its DID. As with key registries, the payer pays for
+    /// any extra rent, and the rent freed by shrinking it is refunded to the parties that paid for it,
+    /// whose accounts are passed after the controller chain.
+    pub fn resize_controller_registry(
+        ctx: Context<ResizeControllerRegistry>,
+        did_count: u32,
+        _did_bump: u8,
+    ) -> Result<()> {
+        let (controller_chain, rent_payer_accounts) = split_rent_payers(
+            &ctx.accounts.registry.rent_payers,
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+
+        // ensure the authority is an authority on the did account of the registry
+        // note, anchor has already verified the constraint that did_account
+        // is the account for the did.
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.registry.did,
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        msg!(
+            "Resizing controller registry to fit {} dids - resulting in size {}",
+            did_count,
+            ControllerRegistry::calculate_size(did_count)
+        );
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            ControllerRegistry::calculate_size(did_count),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            rent_payer_accounts,
+        )
+    }
+
+    /// Close a controller registry, refunding its rent and recording the removal of its remaining DIDs
+    /// as when closing a key registry. The history, if any, and the accounts of the parties that paid for it
+    /// are passed after the controller chain.
+    pub fn close_controller_registry(
+        ctx: Context<CloseControllerRegistry>,
+        _did_bump: u8,
+    ) -> Result<()> {
+        // the history, if any, is passed before the rent payers
+        let (rest, rent_payer_accounts) = split_rent_payers(
+            &ctx.accounts.registry.rent_payers,
+            &ctx.accounts.payer.key(),
+            ctx.remaining_accounts,
+        )?;
+        let (controller_chain, mut history) = split_history(
+            &ctx.accounts.registry.key(),
+            ctx.accounts.registry.has_history,
+            rest,
+        )?;
+
+        // ensure the authority is an authority on the did account whose registry is being closed
+        // note, anchor has already verified the constraint that did_account
+        // is the account for the did.
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.did.key(),
+            controller_chain,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // closing a registry removes all its DIDs, so it is not allowed while they are protected by a removal delay
+        require!(
+            ctx.accounts.registry.removal_delay == 0
+                || ctx.accounts.registry.controlled_dids.is_empty(),
+            ErrorCode::RegistryNotEmpty
+        );
+
+        // the history outlives the registry, so it records the removal of the remaining DIDs
+        let changes = ctx
+            .accounts
+            .registry
+            .controlled_dids
+            .iter()
+            .map(|did| (*did, HistoryAction::Remove))
+            .collect::<Vec<_>>();
+        record_history(&mut history, &changes, ctx.accounts.authority.key())?;
+
+        let registry = &mut ctx.accounts.registry;
+        let registry_info = registry.to_account_info();
+        refund_rent(
+            &registry_info,
+            &mut registry.rent_payers,
+            registry_info.lamports(),
+            &ctx.accounts.payer.to_account_info(),
+            rent_payer_accounts,
+        )
+    }
+
+    /// Migrate a controller registry created by the first release of the program that was filled,
+    /// as with migrate_key_registry. Anyone can migrate a registry.
+    pub fn migrate_controller_registry(ctx: Context<MigrateControllerRegistry>) -> Result<()> {
+        let registry_info = ctx.accounts.registry.to_account_info();
+        let legacy =
+            load_legacy_registry::<ControllerRegistry, LegacyControllerRegistry>(&registry_info)?;
+
+        let mut registry = ControllerRegistry {
+            version: legacy.version,
+            did: legacy.did,
+            controlled_dids: legacy.controlled_dids,
+            removal_delay: 0,
+            required_flags: 0,
+            has_history: false,
+            namespace: vec![],
+            rent_payers: vec![],
+        };
+        resize_registry(
+            &registry_info,
+            &mut registry.rent_payers,
+            ControllerRegistry::calculate_size(registry.controlled_dids.len() as u32),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+        )?;
+
+        write_registry(&registry_info, &registry)
+    }
+
+    /// Add a DID to the registry of a non-did:sol controller DID (e.g. did:web or did:ethr).
+    /// The DID must list the controller in its other_controllers, and the authority must be an authority on the DID,
+    /// either directly, or through the chain of controller DIDs passed in the remaining accounts.
+    pub fn register_did_for_external_controller(
+        ctx: Context<RegisterDidForExternalController>,
+        controller: String,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        let controlled_did = ctx.accounts.controlled_did.key();
+
+        // ensure the authority holds a gateway token, if the program config requires one
+        verify_gateway_token(
+            &ctx.accounts.config,
+            &ctx.accounts.gateway_token,
+            &ctx.accounts.authority.key(),
+        )?;
+
+        // the controller cannot sign, so the controlled DID must opt in via one of its authorities
+        verify_authority(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            &controlled_did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        // ensure the controlled DID lists the controller
+        require!(
+            ctx.accounts
+                .controlled_did_account
+                .other_controllers
+                .contains(&controller),
+            ErrorCode::NotController
+        );
+
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the controller, so setting it is a no-op otherwise
+        let registry = &mut ctx.accounts.registry;
+        if registry.controller.is_empty() {
+            pay_for_new_registry(
+                &registry.to_account_info(),
+                &mut registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        registry.controller = controller;
+
+        // ensure the did is not already registered
+        require_eq!(
+            registry.controlled_dids.contains(&controlled_did),
+            false,
+            ErrorCode::DIDRegistered
+        );
+
+        // ensure adding the did does not exceed the account size
+        require_gte!(
+            registry.to_account_info().data_len() as usize,
+            ExternalControllerRegistry::calculate_size(
+                registry.controller.len(),
+                (registry.controlled_dids.len() + 1).try_into().unwrap()
+            ),
+            ErrorCode::RegistryFull
+        );
+
+        registry.controlled_dids.push(controlled_did);
+
+        Ok(())
+    }
+
+    /// Remove a DID from the registry of a non-did:sol controller DID.
+    /// Any authority on the controlled DID can remove it, whether or not it still lists the controller.
+    pub fn remove_did_for_external_controller(
+        ctx: Context<RemoveDidForExternalController>,
+        _controlled_did_bump: u8,
+    ) -> Result<()> {
+        let controlled_did = ctx.accounts.controlled_did.key();
+
+        verify_authority(
+            &ctx.accounts.controlled_did_account.to_account_info(),
+            &controlled_did,
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .controlled_dids
+            .iter()
+            .position(|did| *did == controlled_did)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.controlled_dids.swap_remove(index);
+
         Ok(())
     }
 
-    /// Add a controlled DID to an authority's controller registry
-    pub fn register_controlled_did(
-        ctx: Context<RegisterControlledDid>,
-        _did_bump: u8,
-        _controlled_did_bump: u8,
+    /// Grow the registry of a non-did:sol controller DID to fit the given number of DIDs
+    pub fn resize_external_controller_registry(
+        ctx: Context<ResizeExternalControllerRegistry>,
+        did_count: u32,
     ) -> Result<()> {
+        let size = ExternalControllerRegistry::calculate_size(
+            ctx.accounts.registry.controller.len(),
+            did_count,
+        );
         msg!(
-            "Registering controlled did {} on controller registry for did {}",
-            ctx.accounts.controlled_did.key(),
-            ctx.accounts.registry.did.key()
+            "Resizing external controller registry to fit {} dids - resulting in size {}",
+            did_count,
+            size
         );
-        msg!("Controller registry: {}", ctx.accounts.registry.key());
-
-        // 1. Ensure ctx.accounts.authority is an authority on ctx.accounts.registry.did
-        // note, anchor has already verified the constraint that did_account
-        // is the account for the registry's did.
-        is_authority(
-            &ctx.accounts.did_account.to_account_info(),
-            None,
-            &[], // the authority must be a direct authority on the DID
-            ctx.accounts.authority.key().as_ref(),
-            None,
-            None,
+        // the registry only grows (see ResizeExternalControllerRegistry), so no rent payers are refunded
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            size,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
         )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+    }
 
-        // 2. Ensure ctx.accounts.controlled_did is controlled by ctx.accounts.registry.did
-        let controlled_did = &ctx.accounts.controlled_did;
+    /// Add a DID to an asset's registry. The holder must hold the asset, and be an authority on the DID,
+    /// either directly, or through the chain of controller DIDs passed in the remaining accounts.
+    pub fn register_did_for_asset(ctx: Context<RegisterDidForAsset>, _did_bump: u8) -> Result<()> {
+        let did = ctx.accounts.did.key();
+        let holder = ctx.accounts.holder.key();
 
-        let controller_account_info_and_pubkey = &(
-            &ctx.accounts.did_account.to_account_info(),
-            ctx.accounts.registry.did,
-        );
-        let controller_did_data =
-            DidAccount::try_from_or_default(controller_account_info_and_pubkey)?;
+        // ensure the holder has a gateway token, if the program config requires one
+        verify_gateway_token(&ctx.accounts.config, &ctx.accounts.gateway_token, &holder)?;
 
-        let controlled_account_info_and_pubkey = &(
-            &ctx.accounts.controlled_did_account.to_account_info(),
-            ctx.accounts.controlled_did.key(),
-        );
-        let controlled_did_data =
-            DidAccount::try_from_or_default(controlled_account_info_and_pubkey)?;
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            ctx.remaining_accounts,
+            holder.as_ref(),
+        )?;
 
-        msg!("Controllers: {:?}", controlled_did_data.native_controllers);
-        msg!("authority key: {:?}", controller_did_data.authority_key());
-        msg!(
-            "contains: {:?}",
-            controlled_did_data
-                .native_controllers
-                .iter()
-                .contains(&controller_did_data.authority_key())
-        );
-        controlled_did_data
-            .is_controlled_by(&[controller_did_data])
-            .then_some(())
-            .ok_or(ErrorCode::NotController)?;
+        // the registry may have just been created, in which case the payer paid its rent and the registry fee.
+        // It is derived from the mint, so setting it is a no-op otherwise
+        let registry = &mut ctx.accounts.registry;
+        if registry.mint == Pubkey::default() {
+            pay_for_new_registry(
+                &registry.to_account_info(),
+                &mut registry.rent_payers,
+                &ctx.accounts.config.to_account_info(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+        registry.mint = ctx.accounts.mint.key();
 
-        // 3. Ensure the did is not already registered
+        // ensure the did is not already registered
         require_eq!(
-            ctx.accounts
-                .registry
-                .controlled_dids
-                .contains(&controlled_did.key()),
+            registry.entries.iter().any(|entry| entry.did == did),
             false,
             ErrorCode::DIDRegistered
         );
 
         // ensure adding the did does not exceed the account size
         require_gte!(
-            ctx.accounts.registry.to_account_info().data_len() as usize,
-            ControllerRegistry::calculate_size(
-                (ctx.accounts.registry.controlled_dids.len() + 1)
-                    .try_into()
-                    .unwrap()
-            ),
+            registry.to_account_info().data_len() as usize,
+            AssetRegistry::calculate_size((registry.entries.len() + 1).try_into().unwrap()),
             ErrorCode::RegistryFull
         );
 
-        ctx.accounts
-            .registry
-            .controlled_dids
-            .push(controlled_did.key());
+        registry.entries.push(AssetRegistryEntry { did, holder });
 
         Ok(())
     }
 
-    /// Remove a controlled DID from a controller registry
-    pub fn remove_controlled_did(ctx: Context<RemoveControlledDid>, _did_bump: u8) -> Result<()> {
-        let did_to_remove = &ctx.accounts.did_to_remove.key();
+    /// Remove a DID from an asset's registry. The holder must hold the asset, and be an authority on the DID,
+    /// either directly, or through the chain of controller DIDs passed in the remaining accounts.
+    pub fn remove_did_for_asset(ctx: Context<RemoveDidForAsset>, _did_bump: u8) -> Result<()> {
+        let did_to_remove = ctx.accounts.did.key();
 
-        // ensure the authority is an authority on the did account that the registry is being created for
-        // note, anchor has already verified the constraint that did_account
-        // is the account for the did.
-        // Theoretically, creating a controller registry for a DID is a permissionless act and anyone could do it
-        // but to prevent any future exploits, we lock it down here to a DID authority.
-        is_authority(
+        verify_authority(
             &ctx.accounts.did_account.to_account_info(),
-            None, // the authority must be a direct authority on the DID
-            &[],
-            ctx.accounts.authority.key().as_ref(),
-            None,
-            None,
-        )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+            &did_to_remove,
+            ctx.remaining_accounts,
+            ctx.accounts.holder.key().as_ref(),
+        )?;
 
-        // find the DID in the registry and remove it
-        // throw an error if not found
-        ctx.accounts
-            .registry
-            .controlled_dids
+        let registry = &mut ctx.accounts.registry;
+        let index = registry
+            .entries
             .iter()
-            .position(|did| did == did_to_remove)
-            .map_or_else(
-                || Err(ErrorCode::DIDNotRegistered.into()),
-                |index| {
-                    ctx.accounts.registry.controlled_dids.swap_remove(index);
-                    Ok(())
-                },
-            )
+            .position(|entry| entry.did == did_to_remove)
+            .ok_or(ErrorCode::DIDNotRegistered)?;
+        registry.entries.swap_remove(index);
+
+        Ok(())
     }
 
-    pub fn resize_controller_registry(
-        _ctx: Context<ResizeControllerRegistry>,
-        did_count: u32,
+    /// Remove all DIDs registered by previous holders of an asset, e.g. after it has been transferred.
+    /// The current holder does not need to be an authority on the DIDs being removed.
+    pub fn clear_previous_asset_holder_dids(
+        ctx: Context<ClearPreviousAssetHolderDids>,
     ) -> Result<()> {
+        let holder = ctx.accounts.holder.key();
+        ctx.accounts
+            .registry
+            .entries
+            .retain(|entry| entry.holder == holder);
+
+        Ok(())
+    }
+
+    /// Grow an asset's registry to fit the given number of DIDs
+    pub fn resize_asset_registry(ctx: Context<ResizeAssetRegistry>, did_count: u32) -> Result<()> {
         msg!(
-            "Resizing controller registry to fit {} dids - resulting in size {}",
+            "Resizing asset registry {} to fit {} dids - resulting in size {}",
+            ctx.accounts.registry.key(),
             did_count,
-            ControllerRegistry::calculate_size(did_count)
+            AssetRegistry::calculate_size(did_count)
         );
+        // the registry only grows (see ResizeAssetRegistry), so no rent payers are refunded
+        let registry = &mut ctx.accounts.registry;
+        resize_registry(
+            &registry.to_account_info(),
+            &mut registry.rent_payers,
+            AssetRegistry::calculate_size(did_count),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+        )
+    }
+
+    /// Record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.
+    /// The authority must be a direct authority on both DIDs. The record can be updated or revoked
+    /// by an authority on the superseded DID (see update_did_successor and revoke_did_successor).
+    /// Registries containing the superseded DID can then replace it with its successor.
+    pub fn set_did_successor(
+        ctx: Context<SetDidSuccessor>,
+        _did_bump: u8,
+        _successor_bump: u8,
+    ) -> Result<()> {
+        let did = ctx.accounts.did.key();
+        let successor = ctx.accounts.successor.key();
+        require_keys_neq!(did, successor, ErrorCode::InvalidSuccessor);
+
+        let authority = ctx.accounts.authority.key();
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &did,
+            &[],
+            authority.as_ref(),
+        )?;
+        verify_authority(
+            &ctx.accounts.successor_account.to_account_info(),
+            &successor,
+            &[],
+            authority.as_ref(),
+        )?;
+
+        let succession = &mut ctx.accounts.succession;
+        succession.did = did;
+        succession.successor = successor;
+        succession.payer = ctx.accounts.payer.key();
+
         Ok(())
     }
 
-    pub fn close_controller_registry(
-        ctx: Context<CloseControllerRegistry>,
+    /// Change the successor recorded for a superseded DID.
+    /// The authority must be a direct authority on both the superseded DID and the new successor,
+    /// so the new successor consents to superseding the DID, as when the record was created.
+    pub fn update_did_successor(
+        ctx: Context<UpdateDidSuccessor>,
         _did_bump: u8,
+        _successor_bump: u8,
     ) -> Result<()> {
-        // ensure the authority is an authority on the did account whose registry is being closed
-        // note, anchor has already verified the constraint that did_account
-        // is the account for the did.
-        is_authority(
+        let did = ctx.accounts.did.key();
+        let successor = ctx.accounts.successor.key();
+        require_keys_neq!(did, successor, ErrorCode::InvalidSuccessor);
+
+        let authority = ctx.accounts.authority.key();
+        verify_authority(
             &ctx.accounts.did_account.to_account_info(),
-            None,
-            &[], // the authority must be a direct authority on the DID
+            &did,
+            &[],
+            authority.as_ref(),
+        )?;
+        verify_authority(
+            &ctx.accounts.successor_account.to_account_info(),
+            &successor,
+            &[],
+            authority.as_ref(),
+        )?;
+
+        ctx.accounts.succession.successor = successor;
+
+        Ok(())
+    }
+
+    /// Remove the successor recorded for a DID, refunding the rent of the record to its payer.
+    /// The authority must be a direct authority on the superseded DID.
+    /// Registries that have already replaced the DID with its successor are not changed.
+    pub fn revoke_did_successor(ctx: Context<RevokeDidSuccessor>, _did_bump: u8) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.did.key(),
+            &[],
             ctx.accounts.authority.key().as_ref(),
-            None,
-            None,
         )
-        .map_err(|_| ErrorCode::DIDError)?
-        .then_some(())
-        .ok_or(ErrorCode::NotAuthority)?;
+    }
+
+    /// Increase the number of entries kept in a registry's history. Anyone can pay to grow a history.
+    pub fn resize_registry_history(
+        ctx: Context<ResizeRegistryHistory>,
+        capacity: u32,
+    ) -> Result<()> {
+        require_gt!(
+            capacity,
+            ctx.accounts.history.capacity,
+            ErrorCode::InvalidHistoryCapacity
+        );
+
+        ctx.accounts.history.grow(capacity);
+
+        Ok(())
+    }
+
+    /// Cancel a pending removal from a key or controller registry.
+    /// Any authority on the DID being removed can cancel it.
+    pub fn cancel_removal(ctx: Context<CancelRemoval>, _did_bump: u8) -> Result<()> {
+        verify_authority(
+            &ctx.accounts.did_account.to_account_info(),
+            &ctx.accounts.did.key(),
+            ctx.remaining_accounts,
+            ctx.accounts.authority.key().as_ref(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Create the program config, with the upgrade authority of the program as its admin
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        initial_capacity: u32,
+        max_entries: u32,
+        registry_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.set_limits(initial_capacity, max_entries, registry_fee)
+    }
+
+    /// Pause or unpause the program. While paused, all instructions that change registries are blocked,
+    /// except for closing them.
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        ctx.accounts.config.paused = paused;
+        Ok(())
+    }
+
+    /// Set the capacity of new registries, the maximum number of DIDs in a registry (0 = no maximum),
+    /// and the fee charged for creating a registry (0 = no fee).
+    /// Existing registries larger than the maximum are not affected, but cannot grow.
+    pub fn set_config_limits(
+        ctx: Context<UpdateConfig>,
+        initial_capacity: u32,
+        max_entries: u32,
+        registry_fee: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .config
+            .set_limits(initial_capacity, max_entries, registry_fee)
+    }
+
+    /// Propose a new admin for the program config, which becomes admin once it accepts.
+    /// Proposing the default pubkey cancels a proposal.
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.pending_admin = new_admin;
+        Ok(())
+    }
+
+    /// Accept a proposal to become the admin of the program config
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = config.pending_admin;
+        config.pending_admin = Pubkey::default();
+        Ok(())
+    }
+
+    /// Withdraw the registry fees held in the program config
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>) -> Result<()> {
+        let config = ctx.accounts.config.to_account_info();
+        let recipient = ctx.accounts.recipient.to_account_info();
+
+        // the config keeps enough lamports to remain rent-exempt
+        let fees = config
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(config.data_len()));
+        **config.try_borrow_mut_lamports()? -= fees;
+        **recipient.try_borrow_mut_lamports()? += fees;
+
+        Ok(())
+    }
 
+    /// Require a gateway token (Civic Pass) in the gatekeeper network to register DIDs
+    /// via register_did and register_controlled_did. Setting the default pubkey removes the requirement.
+    pub fn set_gatekeeper_network(
+        ctx: Context<UpdateConfig>,
+        gatekeeper_network: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.config.gatekeeper_network = gatekeeper_network;
         Ok(())
     }
 }
 
+/// Add a DID to an authority's registry, optionally expiring at the given unix timestamp
+fn register_did_in_registry(ctx: Context<RegisterDid>, expires_at: Option<i64>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expires_at) = expires_at {
+        require_gt!(expires_at, now, ErrorCode::InvalidExpiry);
+    }
+
+    let (controller_chain, mut history) = split_history(
+        &ctx.accounts.registry.key(),
+        ctx.accounts.registry.has_history,
+        ctx.remaining_accounts,
+    )?;
+
+    // ensure the authority holds a gateway token, if the program config requires one
+    verify_gateway_token(
+        &ctx.accounts.config,
+        &ctx.accounts.gateway_token,
+        &ctx.accounts.authority.key(),
+    )?;
+
+    // ensure the authority is an authority on the did account
+    // note, anchor has already verified the constraint that did_account
+    // is the account for the did.
+    verify_authority(
+        &ctx.accounts.did_account.to_account_info(),
+        &ctx.accounts.did.key(),
+        controller_chain,
+        ctx.accounts.authority.key().as_ref(),
+    )?;
+
+    // ensure the authority has the verification method flags required by the registry
+    verify_flags(
+        &ctx.accounts.did_account.to_account_info(),
+        &ctx.accounts.did.key(),
+        controller_chain,
+        ctx.accounts.authority.key().as_ref(),
+        ctx.accounts.registry.required_flags,
+    )?;
+
+    let did = &ctx.accounts.did;
+    // ensure the did is not already registered
+    require_eq!(
+        ctx.accounts.registry.is_registered(&did.key(), now),
+        false,
+        ErrorCode::DIDRegistered
+    );
+
+    // ensure adding the did does not exceed the account size
+    require_gte!(
+        ctx.accounts.registry.to_account_info().data_len() as usize,
+        KeyRegistry::calculate_size((ctx.accounts.registry.dids.len() + 1).try_into().unwrap()),
+        ErrorCode::RegistryFull
+    );
+
+    match expires_at {
+        Some(expires_at) => ctx
+            .accounts
+            .registry
+            .push_expiring_did(did.key(), expires_at),
+        None => ctx.accounts.registry.push_did(did.key()),
+    }
+    record_history(
+        &mut history,
+        &[(did.key(), HistoryAction::Add)],
+        ctx.accounts.authority.key(),
+    )
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("An error occurred evaluating the DID")]
@@ -406,4 +2879,143 @@ pub enum ErrorCode {
         "The registry has exceeded its maximum size - use the resize instruction to get more space"
     )]
     RegistryFull,
+
+    #[msg(
+        "The registry has a removal delay - request the removal and finalise it once the delay has elapsed"
+    )]
+    RemovalDelayed,
+
+    #[msg("The registry has no removal delay - remove the DID directly")]
+    RemovalNotDelayed,
+
+    #[msg("The removal delay has not yet elapsed")]
+    RemovalLocked,
+
+    #[msg("The removal delay cannot be decreased")]
+    RemovalDelayDecreased,
+
+    #[msg("The removal delay is too large")]
+    RemovalDelayOverflow,
+
+    #[msg("The registry cannot be closed while it contains DIDs protected by a removal delay")]
+    RegistryNotEmpty,
+
+    #[msg("The controller chain exceeds the maximum length")]
+    ControllerChainTooLong,
+
+    #[msg("Unknown verification method flags")]
+    InvalidFlags,
+
+    #[msg("The key does not have the verification method flags required by the registry")]
+    MissingRequiredFlags,
+
+    #[msg(
+        "The transaction must include a signature verification instruction immediately before this instruction"
+    )]
+    MissingSignatureInstruction,
+
+    #[msg(
+        "The signature verification instruction must contain a single signature, with its data in the same instruction"
+    )]
+    InvalidSignatureInstruction,
+
+    #[msg("The message was signed by the wrong key")]
+    WrongSigner,
+
+    #[msg("The signed message does not match the expected message")]
+    WrongMessage,
+
+    #[msg("The WebAuthn assertion is not a valid assertion by a present user")]
+    InvalidWebAuthnAssertion,
+
+    #[msg("The account id is not a valid CAIP-10 account id")]
+    InvalidAccountId,
+
+    #[msg("Signatures by accounts of this CAIP-2 namespace are not supported")]
+    UnsupportedNamespace,
+
+    #[msg("The registry can only grow")]
+    RegistryShrink,
+
+    #[msg("The Bitcoin signature is not a valid BIP-137 signature for the address type")]
+    InvalidBtcSignature,
+
+    #[msg("The Bitcoin signature was signed by the wrong address")]
+    WrongBtcSigner,
+
+    #[msg("The asset must be an NFT, with a supply of 1 and no decimals")]
+    InvalidAsset,
+
+    #[msg("The signer does not hold the asset")]
+    NotAssetHolder,
+
+    #[msg("Keys and their registries must be passed as (key, registry) pairs")]
+    InvalidKeyRegistryPairs,
+
+    #[msg("The key is still an authority on the DID")]
+    StillAuthority,
+
+    #[msg(
+        "The DID has controllers - pass all of them, which must not have controllers themselves"
+    )]
+    DIDHasControllers,
+
+    #[msg("DIDs and their accounts must be passed as (did, did account) pairs")]
+    InvalidDidAccountPairs,
+
+    #[msg("The DID is still controlled by the registry's DID")]
+    StillControlled,
+
+    #[msg("A DID cannot be its own successor")]
+    InvalidSuccessor,
+
+    #[msg("The expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("The registry contains no expired DIDs")]
+    NoExpiredDids,
+
+    #[msg("The registry records its history, so the history must be passed after the other remaining accounts")]
+    HistoryRequired,
+
+    #[msg("The history is not the history of the registry")]
+    InvalidHistory,
+
+    #[msg("The history capacity must be greater than zero, and can only be increased")]
+    InvalidHistoryCapacity,
+
+    #[msg("The program is paused")]
+    ProgramPaused,
+
+    #[msg("The registry would exceed the maximum number of DIDs")]
+    RegistryLimitExceeded,
+
+    #[msg("The program config is invalid")]
+    InvalidConfig,
+
+    #[msg("The signer is not the proposed admin")]
+    NotPendingAdmin,
+
+    #[msg("The authority does not have a valid gateway token in the required gatekeeper network")]
+    InvalidGatewayToken,
+
+    #[msg("The registry has recorded the maximum number of rent payers")]
+    TooManyRentPayers,
+
+    #[msg("The accounts of the registry's rent payers must be passed after the other remaining accounts")]
+    RentPayerMissing,
+
+    #[msg(
+        "The program config requires a gateway token, which cannot be verified for this authority"
+    )]
+    GatewayTokenNotSupported,
+
+    #[msg("The namespace is longer than the maximum length of a namespace")]
+    NamespaceTooLong,
+
+    #[msg("The registry can be loaded in its current layout, so it does not need to be migrated")]
+    RegistryNotLegacy,
+
+    #[msg("The key has too many pending invitations - it must accept or reject some first")]
+    InboxFull,
 }
@@ -0,0 +1,33 @@
+use crate::state::rent_payer::RentPayer;
+use anchor_lang::prelude::*;
+
+/// A DID registered against an asset, along with the holder of the asset that registered it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AssetRegistryEntry {
+    pub did: Pubkey,
+    /// The holder of the asset at the time the DID was registered
+    pub holder: Pubkey,
+}
+
+/// A registry of DIDs linked to an asset (an NFT), by the holders of the asset
+#[account]
+pub struct AssetRegistry {
+    pub version: u8,
+    /// The mint of the asset
+    pub mint: Pubkey,
+    pub entries: Vec<AssetRegistryEntry>,
+    /// The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)
+    pub rent_payers: Vec<RentPayer>,
+}
+impl AssetRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"asset_registry";
+    pub const INITIAL_SIZE: usize = Self::calculate_size(4); // allow 4 DIDs to be registered initially
+
+    pub const fn calculate_size(did_count: u32) -> usize {
+        (8 // discriminator
+            + 1 // version
+            + 32 // mint
+            + 4 + (did_count * 64) // Each entry is a did and a holder, 32 bytes each
+            + RentPayer::SIZE as u32) as usize // rent payers
+    }
+}
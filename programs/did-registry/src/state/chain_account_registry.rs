@@ -0,0 +1,45 @@
+use crate::state::rent_payer::RentPayer;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// A registry of DIDs for an account on another chain, identified by its CAIP-10 account id
+/// (see https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md)
+#[account]
+pub struct ChainAccountRegistry {
+    pub version: u8,
+    /// The CAIP-2 namespace of the chain, e.g. "eip155"
+    pub namespace: String,
+    /// The CAIP-2 reference of the chain within its namespace, e.g. "1" for Ethereum mainnet
+    pub reference: String,
+    /// The address of the account on the chain
+    pub address: String,
+    /// Incremented on each update, so that each signature can only be used once
+    pub nonce: u64,
+    pub dids: Vec<Pubkey>,
+    /// The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)
+    pub rent_payers: Vec<RentPayer>,
+}
+impl ChainAccountRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"chain_account_registry";
+    pub const INITIAL_DID_COUNT: u32 = 4; // allow 4 DIDs to be registered initially
+
+    /// The size of a registry for an account id of the given length (including separators)
+    pub const fn calculate_size(account_id_len: usize, did_count: u32) -> usize {
+        8 // discriminator
+            + 1 // version
+            + 4 * 3 + account_id_len // namespace, reference and address, each with a length field
+            + 8 // nonce
+            + 4 + (did_count as usize * 32) // Each registered did is 32 bytes
+            + RentPayer::SIZE // rent payers
+    }
+
+    /// The registry is seeded by the hash of the account id, as the id may be longer than a seed allows
+    pub fn account_id_seed(account_id: &str) -> [u8; 32] {
+        hash(account_id.as_bytes()).to_bytes()
+    }
+
+    /// The CAIP-10 account id of the registry
+    pub fn account_id(&self) -> String {
+        format!("{}:{}:{}", self.namespace, self.reference, self.address)
+    }
+}
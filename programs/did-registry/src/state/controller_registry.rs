@@ -1,3 +1,5 @@
+use crate::state::key_registry::KeyRegistry;
+use crate::state::rent_payer::RentPayer;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -5,6 +7,18 @@ pub struct ControllerRegistry {
     pub version: u8,
     pub did: Pubkey,
     pub controlled_dids: Vec<Pubkey>,
+    // as in KeyRegistry, fields added since the first release are appended after controlled_dids
+    /// The number of slots a removal must wait before it can be finalised (0 = no delay)
+    pub removal_delay: u64,
+    /// The verification method flags that a key must have on a DID in order to register it (0 = any)
+    pub required_flags: u16,
+    /// Whether adds and removes are recorded in a RegistryHistory
+    pub has_history: bool,
+    /// The application namespace of the registry, a seed of its address (empty = the default registry).
+    /// A DID has a separate controller registry in each namespace.
+    pub namespace: Vec<u8>,
+    /// The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)
+    pub rent_payers: Vec<RentPayer>,
 }
 impl ControllerRegistry {
     pub const SEED_PREFIX: &'static [u8] = b"controller_registry";
@@ -14,6 +28,24 @@ impl ControllerRegistry {
         (8 // discriminator
             + 1 // version
             + 32 // key
-            + 4 + (did_count * 32)) as usize // Each registered did is 32 bytes
+            + 4 + (did_count * 32) // Each registered did is 32 bytes
+            + 8 // removal delay
+            + 2 // required flags
+            + 1 // has history
+            + 4 + KeyRegistry::MAX_NAMESPACE_LEN as u32 // namespace
+            + RentPayer::SIZE as u32) as usize // rent payers
     }
+
+    /// The address and bump seed of the registry of the DID in the namespace
+    pub fn find_address(did: &Pubkey, namespace: &[u8]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, did.as_ref(), namespace], &crate::ID)
+    }
+}
+
+/// The layout of a ControllerRegistry in the first release of the program (see LegacyKeyRegistry)
+#[derive(AnchorDeserialize)]
+pub struct LegacyControllerRegistry {
+    pub version: u8,
+    pub did: Pubkey,
+    pub controlled_dids: Vec<Pubkey>,
 }
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// A record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.
+/// Created by an authority on both DIDs. An authority on the superseded DID can update or revoke it.
+#[account]
+pub struct DidSuccession {
+    pub version: u8,
+    /// The superseded DID
+    pub did: Pubkey,
+    /// The DID that supersedes it
+    pub successor: Pubkey,
+    /// The account that paid the rent for this record, and is refunded when it is revoked
+    pub payer: Pubkey,
+}
+impl DidSuccession {
+    pub const SEED_PREFIX: &'static [u8] = b"did_succession";
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // did
+        + 32 // successor
+        + 32; // payer
+}
@@ -0,0 +1,33 @@
+use crate::state::rent_payer::RentPayer;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// A registry of did:sol DIDs controlled by a non-did:sol DID (e.g. did:web or did:ethr),
+/// which the controlled DIDs list in their other_controllers
+#[account]
+pub struct ExternalControllerRegistry {
+    pub version: u8,
+    /// The controller DID, e.g. "did:web:example.com"
+    pub controller: String,
+    pub controlled_dids: Vec<Pubkey>,
+    /// The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)
+    pub rent_payers: Vec<RentPayer>,
+}
+impl ExternalControllerRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"external_controller_registry";
+    pub const INITIAL_DID_COUNT: u32 = 4; // allow 4 DIDs to be registered initially
+
+    /// The size of a registry for a controller DID of the given length
+    pub const fn calculate_size(controller_len: usize, did_count: u32) -> usize {
+        8 // discriminator
+            + 1 // version
+            + 4 + controller_len // controller, with a length field
+            + 4 + (did_count as usize * 32) // Each registered did is 32 bytes
+            + RentPayer::SIZE // rent payers
+    }
+
+    /// The registry is seeded by the hash of the controller DID, as it may be longer than a seed allows
+    pub fn controller_seed(controller: &str) -> [u8; 32] {
+        hash(controller.as_bytes()).to_bytes()
+    }
+}
@@ -1,3 +1,6 @@
+use crate::state::rent_payer::RentPayer;
+use crate::util::eth_signing::EthAddressForm;
+use crate::ErrorCode;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -5,16 +8,169 @@ pub struct KeyRegistry {
     pub version: u8,
     pub authority: Pubkey,
     pub dids: Vec<Pubkey>,
+    // fields added since the first release are appended after dids, so the original fields keep their layout
+    /// The number of slots a removal must wait before it can be finalised (0 = no delay)
+    pub removal_delay: u64,
+    /// The verification method flags that a key must have on a DID in order to register it (0 = any)
+    pub required_flags: u16,
+    /// For eth address registries, the form of the verification method that matched the address
+    /// for each registered DID, in the same order as dids. Empty for other registries.
+    pub eth_address_forms: Vec<EthAddressForm>,
+    /// The unix timestamp at which each registered DID expires (NO_EXPIRY = never), in the same order as dids
+    pub expiries: Vec<i64>,
+    /// Whether adds and removes are recorded in a RegistryHistory
+    pub has_history: bool,
+    /// The application namespace of the registry, a seed of its address (empty = the default registry).
+    /// A key (or eth address) has a separate registry in each namespace.
+    pub namespace: Vec<u8>,
+    /// The parties that paid rent for the registry, and the lamports each is refunded when it is shrunk or closed
+    pub rent_payers: Vec<RentPayer>,
 }
 impl KeyRegistry {
     pub const SEED_PREFIX: &'static [u8] = b"key_registry";
     pub const ETH_SEED_PREFIX: &'static [u8] = b"eth_key_registry";
+    pub const BTC_SEED_PREFIX: &'static [u8] = b"btc_key_registry";
+    pub const NO_EXPIRY: i64 = 0;
+    /// The maximum removal delay, in slots (about 30 days), so that the delay, which can only be increased,
+    /// cannot lock DIDs into a registry indefinitely
+    pub const MAX_REMOVAL_DELAY: u64 = 6_480_000;
+    /// The maximum length of a namespace, which is limited by the length of a PDA seed
+    pub const MAX_NAMESPACE_LEN: usize = 32;
+    /// The space taken by each registered DID, along with its expiry
+    pub const DID_SIZE: usize = 32 + 8;
     pub const INITIAL_SIZE: usize = Self::calculate_size(4); // allow 4 DIDs to be registered initially
 
     pub const fn calculate_size(did_count: u32) -> usize {
         (8 // discriminator
             + 1 // version
             + 32 // key
-            + 4 + (did_count * 32)) as usize // Each registered did is 32 bytes
+            + 4 + (did_count * 32) // Each registered did is 32 bytes
+            + 8 // removal delay
+            + 2 // required flags
+            + 4 // eth address forms length field
+            + 4 + (did_count * 8) // Each expiry is 8 bytes
+            + 1 // has history
+            + 4 + Self::MAX_NAMESPACE_LEN as u32 // namespace
+            + RentPayer::SIZE as u32) as usize // rent payers
     }
+
+    /// The namespace of an instruction, as a seed of a registry address.
+    /// This fails cleanly on a namespace that is too long, which would otherwise abort the address derivation.
+    pub fn namespace_seed(namespace: &[u8]) -> Result<&[u8]> {
+        require!(
+            namespace.len() <= Self::MAX_NAMESPACE_LEN,
+            ErrorCode::NamespaceTooLong
+        );
+        Ok(namespace)
+    }
+
+    /// The address and bump seed of the registry of the key in the namespace
+    pub fn find_address(key: &Pubkey, namespace: &[u8]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, key.as_ref(), namespace], &crate::ID)
+    }
+
+    /// The address and bump seed of the registry of the eth address in the namespace
+    pub fn find_eth_address(eth_address: &[u8; 20], namespace: &[u8]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::ETH_SEED_PREFIX, eth_address, namespace], &crate::ID)
+    }
+
+    /// The size of an eth address registry, which records the form of the matching verification method for each DID
+    pub const fn calculate_eth_size(did_count: u32) -> usize {
+        Self::calculate_size(did_count) + did_count as usize
+    }
+
+    /// Add a DID to an eth address registry, along with the form of the verification method that matched the address
+    /// If the DID is already registered (see push_expiring_did), its form is replaced.
+    pub fn push_eth_did(&mut self, did: Pubkey, form: EthAddressForm) {
+        self.push_did(did);
+        self.align_eth_forms();
+        if let Some(index) = self.dids.iter().position(|registered| *registered == did) {
+            self.eth_address_forms[index] = form;
+        }
+    }
+
+    /// Add a DID that never expires
+    pub fn push_did(&mut self, did: Pubkey) {
+        self.push_expiring_did(did, Self::NO_EXPIRY);
+    }
+
+    /// Add a DID that expires at the given unix timestamp.
+    /// If the DID is already registered (i.e. it has expired but has not been swept yet), its expiry is replaced.
+    pub fn push_expiring_did(&mut self, did: Pubkey, expires_at: i64) {
+        self.align_expiries();
+        match self.dids.iter().position(|registered| *registered == did) {
+            Some(index) => self.expiries[index] = expires_at,
+            None => {
+                self.dids.push(did);
+                self.expiries.push(expires_at);
+            }
+        }
+    }
+
+    /// Remove the DID at the given index, along with its eth address form and expiry
+    pub fn remove_did_at(&mut self, index: usize) {
+        self.align_expiries();
+        // eth address forms are only recorded for eth address registries
+        let has_eth_forms = !self.eth_address_forms.is_empty();
+        if has_eth_forms {
+            self.align_eth_forms();
+        }
+        self.dids.swap_remove(index);
+        self.expiries.swap_remove(index);
+        if has_eth_forms {
+            self.eth_address_forms.swap_remove(index);
+        }
+    }
+
+    /// Whether the DID is registered and has not expired
+    pub fn is_registered(&self, did: &Pubkey, now: i64) -> bool {
+        self.dids
+            .iter()
+            .position(|registered| registered == did)
+            .map_or(false, |index| !self.is_expired(index, now))
+    }
+
+    /// Whether the DID at the given index has expired
+    pub fn is_expired(&self, index: usize, now: i64) -> bool {
+        self.expiries.get(index).map_or(false, |expires_at| {
+            *expires_at != Self::NO_EXPIRY && *expires_at <= now
+        })
+    }
+
+    /// Remove all expired DIDs, returning the DIDs removed
+    pub fn remove_expired_dids(&mut self, now: i64) -> Vec<Pubkey> {
+        let mut removed = vec![];
+        let mut index = 0;
+        while index < self.dids.len() {
+            if self.is_expired(index, now) {
+                removed.push(self.dids[index]);
+                // the last DID is moved into this index, so check it next
+                self.remove_did_at(index);
+            } else {
+                index += 1;
+            }
+        }
+        removed
+    }
+
+    // keep an expiry for each DID, e.g. for DIDs registered before expiries were recorded
+    fn align_expiries(&mut self) {
+        self.expiries.resize(self.dids.len(), Self::NO_EXPIRY);
+    }
+
+    // keep a form for each DID in an eth address registry, e.g. for DIDs registered before forms were recorded,
+    // which could only match the address itself
+    fn align_eth_forms(&mut self) {
+        self.eth_address_forms
+            .resize(self.dids.len(), EthAddressForm::Address);
+    }
+}
+
+/// The layout of a KeyRegistry in the first release of the program, before any fields were appended after dids.
+/// A registry in this layout that was filled cannot be loaded as a KeyRegistry until it is migrated.
+#[derive(AnchorDeserialize)]
+pub struct LegacyKeyRegistry {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub dids: Vec<Pubkey>,
 }
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// The nonce included in the signed messages (or WebAuthn assertions) that authorise updates to a registry.
+/// It is kept in its own account, which is never closed, so that it is not reset when the registry
+/// is closed and created again, and each signature can only ever be used once.
+#[account]
+pub struct MessageNonce {
+    pub version: u8,
+    /// The registry whose updates are authorised by messages including the nonce
+    pub registry: Pubkey,
+    /// Incremented on each update authorised by a signed message
+    pub nonce: u64,
+}
+impl MessageNonce {
+    pub const SEED_PREFIX: &'static [u8] = b"message_nonce";
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // registry
+        + 8; // nonce
+
+    /// Use the current nonce, so that the message including it cannot be used again
+    pub fn consume(&mut self, registry: Pubkey) {
+        // the account may have just been created
+        self.registry = registry;
+        self.nonce += 1;
+    }
+}
@@ -1,2 +1,14 @@
+pub(crate) mod asset_registry;
+pub(crate) mod chain_account_registry;
 pub(crate) mod controller_registry;
+pub(crate) mod did_succession;
+pub(crate) mod external_controller_registry;
 pub(crate) mod key_registry;
+pub(crate) mod message_nonce;
+pub(crate) mod passkey_registry;
+pub(crate) mod pending_removal;
+pub(crate) mod program_config;
+pub(crate) mod registration_inbox;
+pub(crate) mod registration_invitation;
+pub(crate) mod registry_history;
+pub(crate) mod rent_payer;
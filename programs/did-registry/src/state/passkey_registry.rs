@@ -0,0 +1,33 @@
+use crate::state::rent_payer::RentPayer;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// The size of a compressed secp256r1 (P-256) public key
+pub const PASSKEY_SIZE: usize = 33;
+
+#[account]
+pub struct PasskeyRegistry {
+    pub version: u8,
+    /// The compressed secp256r1 (P-256) public key of the passkey that owns the registry
+    pub key: [u8; 33], // PASSKEY_SIZE - a literal is required by the IDL generator
+    pub dids: Vec<Pubkey>,
+    /// The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)
+    pub rent_payers: Vec<RentPayer>,
+}
+impl PasskeyRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"passkey_registry";
+    pub const INITIAL_SIZE: usize = Self::calculate_size(4); // allow 4 DIDs to be registered initially
+
+    pub const fn calculate_size(did_count: u32) -> usize {
+        (8 // discriminator
+            + 1 // version
+            + PASSKEY_SIZE as u32 // key
+            + 4 + (did_count * 32) // Each registered did is 32 bytes
+            + RentPayer::SIZE as u32) as usize // rent payers
+    }
+
+    /// The registry is seeded by the hash of the passkey, as the key itself is longer than a seed allows
+    pub fn key_seed(key: &[u8; PASSKEY_SIZE]) -> [u8; 32] {
+        hash(key).to_bytes()
+    }
+}
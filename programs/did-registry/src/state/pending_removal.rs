@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// A removal of a DID from a key or controller registry that is waiting for the
+/// registry's removal delay to elapse.
+#[account]
+pub struct PendingRemoval {
+    pub version: u8,
+    /// The registry (key or controller) that the DID is being removed from
+    pub registry: Pubkey,
+    /// The DID being removed
+    pub did: Pubkey,
+    /// The account that paid the rent for this pending removal, and is refunded when it is closed
+    pub payer: Pubkey,
+    /// The slot from which the removal can be finalised
+    pub unlock_slot: u64,
+}
+impl PendingRemoval {
+    pub const SEED_PREFIX: &'static [u8] = b"pending_removal";
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // registry
+        + 32 // did
+        + 32 // payer
+        + 8; // unlock slot
+}
@@ -0,0 +1,87 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// The program-wide configuration, controlled by an admin key.
+/// Until it is initialised, the defaults apply: nothing is paused, and there are no limits or fees.
+#[account]
+pub struct ProgramConfig {
+    pub version: u8,
+    pub admin: Pubkey,
+    /// The key proposed as the next admin, which must accept before it becomes admin (default = none)
+    pub pending_admin: Pubkey,
+    /// While set, all instructions that change registries are blocked, except for closing them
+    pub paused: bool,
+    /// The number of DIDs that a new key or controller registry has space for
+    pub initial_capacity: u32,
+    /// The maximum number of DIDs that a registry can be resized to hold (0 = no maximum)
+    pub max_entries: u32,
+    /// The fee in lamports charged for creating a registry (0 = no fee).
+    /// Fees are held in this account until the admin withdraws them.
+    pub registry_fee: u64,
+    /// If set, registering a DID requires a valid gateway token (Civic Pass) in this gatekeeper network
+    /// for the authority (default = none)
+    pub gatekeeper_network: Pubkey,
+}
+impl ProgramConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+    pub const DEFAULT_INITIAL_CAPACITY: u32 = 4;
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // admin
+        + 32 // pending admin
+        + 1 // paused
+        + 4 // initial capacity
+        + 4 // max entries
+        + 8 // registry fee
+        + 32; // gatekeeper network
+
+    /// Load the config, falling back to the defaults if it has not been initialised.
+    /// Since it may not have been initialised, instructions take the config as an unchecked account,
+    /// through UnpausedConfig (or UnpausedFeeConfig), which checks its address and that the program is not paused,
+    /// and only read it using this function.
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        if info.data_is_empty() {
+            return Ok(Self::default());
+        }
+
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidConfig);
+        let data = info.try_borrow_data()?;
+        Self::try_deserialize(&mut data.as_ref())
+    }
+
+    /// Set the capacity of new registries, the maximum number of DIDs in a registry, and the registry fee
+    pub fn set_limits(
+        &mut self,
+        initial_capacity: u32,
+        max_entries: u32,
+        registry_fee: u64,
+    ) -> Result<()> {
+        require_gt!(initial_capacity, 0, ErrorCode::InvalidConfig);
+        self.initial_capacity = initial_capacity;
+        self.max_entries = max_entries;
+        self.registry_fee = registry_fee;
+
+        // new registries must not exceed the maximum
+        require!(self.allows(initial_capacity), ErrorCode::InvalidConfig);
+        Ok(())
+    }
+
+    /// Whether a registry may be resized to hold the given number of DIDs
+    pub fn allows(&self, did_count: u32) -> bool {
+        self.max_entries == 0 || did_count <= self.max_entries
+    }
+}
+impl Default for ProgramConfig {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            admin: Pubkey::default(),
+            pending_admin: Pubkey::default(),
+            paused: false,
+            initial_capacity: Self::DEFAULT_INITIAL_CAPACITY,
+            max_entries: 0,
+            registry_fee: 0,
+            gatekeeper_network: Pubkey::default(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// The DIDs that a key has been invited to register (see RegistrationInvitation), so that the key can find
+/// its pending invitations from a single account. It is created by the first invitation, and never closed.
+#[account]
+pub struct RegistrationInbox {
+    pub version: u8,
+    /// The key whose invitations are listed
+    pub key: Pubkey,
+    /// The DIDs of the pending invitations
+    pub dids: Vec<Pubkey>,
+}
+impl RegistrationInbox {
+    pub const SEED_PREFIX: &'static [u8] = b"registration_inbox";
+    /// The maximum number of pending invitations for a key. Each is backed by a deposit,
+    /// which the key receives by rejecting it, so filling the inbox is not free.
+    pub const MAX_INVITATIONS: usize = 16;
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // key
+        + 4 + (Self::MAX_INVITATIONS * 32); // dids
+
+    /// Add the DID of a new invitation
+    pub fn push_did(&mut self, key: Pubkey, did: Pubkey) -> Result<()> {
+        // the account may have just been created
+        self.key = key;
+        require_gt!(Self::MAX_INVITATIONS, self.dids.len(), ErrorCode::InboxFull);
+        self.dids.push(did);
+        Ok(())
+    }
+
+    /// Remove the DID of an invitation that was accepted, rejected or cancelled
+    pub fn remove_did(&mut self, did: &Pubkey) {
+        self.dids.retain(|pending| pending != did);
+    }
+}
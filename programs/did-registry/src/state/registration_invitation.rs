@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// An invitation by an authority on a DID for a key to register the DID, listed in the key's inbox
+/// (see RegistrationInbox) to be accepted or rejected by the key, or cancelled by the inviter.
+#[account]
+pub struct RegistrationInvitation {
+    pub version: u8,
+    /// The key invited to register the DID
+    pub key: Pubkey,
+    /// The DID to register
+    pub did: Pubkey,
+    /// The account that paid the rent and deposit for this invitation
+    pub inviter: Pubkey,
+}
+impl RegistrationInvitation {
+    pub const SEED_PREFIX: &'static [u8] = b"registration_invitation";
+    /// The deposit held by an invitation on top of its rent, as protection against spam.
+    /// It is refunded to the inviter when the invitation is accepted or cancelled, and paid to the key when it is rejected.
+    pub const DEPOSIT: u64 = 1_000_000;
+    pub const SIZE: usize = 8 // discriminator
+        + 1 // version
+        + 32 // key
+        + 32 // did
+        + 32; // inviter
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+/// A change to the DIDs in a registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    Add,
+    Remove,
+}
+
+/// A record of a DID being added to or removed from a registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub did: Pubkey,
+    pub action: HistoryAction,
+    pub slot: u64,
+    /// The key that authorised the change, or the default pubkey if anyone could make it (e.g. a sweep)
+    pub signer: Pubkey,
+}
+
+/// The history of the DIDs added to and removed from a key or controller registry.
+/// Once the capacity is reached, the oldest entries are overwritten.
+#[account]
+pub struct RegistryHistory {
+    pub version: u8,
+    /// The registry (key or controller) that this history records
+    pub registry: Pubkey,
+    /// The maximum number of entries kept
+    pub capacity: u32,
+    /// Once the history is full, the index of the oldest entry, which is overwritten next
+    pub next: u32,
+    pub entries: Vec<HistoryEntry>,
+}
+impl RegistryHistory {
+    pub const SEED_PREFIX: &'static [u8] = b"registry_history";
+
+    pub const fn calculate_size(capacity: u32) -> usize {
+        (8 // discriminator
+            + 1 // version
+            + 32 // registry
+            + 4 // capacity
+            + 4 // next
+            + 4 + (capacity * (32 + 1 + 8 + 32))) as usize // Each entry is a did, action, slot and signer
+    }
+
+    /// Record a change, overwriting the oldest entry if the history is full
+    pub fn record(&mut self, did: Pubkey, action: HistoryAction, signer: Pubkey) -> Result<()> {
+        let entry = HistoryEntry {
+            did,
+            action,
+            slot: Clock::get()?.slot,
+            signer,
+        };
+
+        if self.entries.len() < self.capacity as usize {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next as usize] = entry;
+            self.next = (self.next + 1) % self.capacity;
+        }
+
+        Ok(())
+    }
+
+    /// Increase the capacity of the history, keeping its entries
+    pub fn grow(&mut self, capacity: u32) {
+        // put the entries in order, oldest first, so that new entries are appended after them
+        self.entries.rotate_left(self.next as usize);
+        self.next = 0;
+        self.capacity = capacity;
+    }
+}
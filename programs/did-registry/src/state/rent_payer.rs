@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// A party that paid rent for a registry, and the lamports it is owed back when the registry is shrunk or closed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RentPayer {
+    pub payer: Pubkey,
+    pub lamports: u64,
+}
+impl RentPayer {
+    /// The maximum number of rent payers recorded for a registry
+    pub const MAX_RENT_PAYERS: usize = 4;
+    /// The space taken by the rent payers of a registry
+    pub const SIZE: usize = 4 + Self::MAX_RENT_PAYERS * (32 + 8);
+}
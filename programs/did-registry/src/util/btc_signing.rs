@@ -0,0 +1,128 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::secp256k1_recover::{secp256k1_recover, Secp256k1Pubkey};
+use ripemd::{Digest, Ripemd160};
+use sol_did::state::{DidAccount, VerificationMethodFlags, VerificationMethodType};
+
+/// The prefix added to a message by Bitcoin wallets before hashing (including the length of the prefix)
+const BITCOIN_MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+// BIP-137 signature headers, which encode the recovery id and the type of address
+// see https://github.com/bitcoin/bips/blob/master/bip-0137.mediawiki
+const P2PKH_UNCOMPRESSED_HEADER: u8 = 27;
+const P2PKH_COMPRESSED_HEADER: u8 = 31;
+const P2WPKH_HEADER: u8 = 39;
+
+/// The types of Bitcoin address that can be registered
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BtcAddressType {
+    /// Legacy addresses (starting with 1)
+    P2pkh,
+    /// Native segwit addresses (starting with bc1q)
+    P2wpkh,
+}
+impl BtcAddressType {
+    /// Registries for the same public key hash are distinct for each address type
+    pub fn seed(&self) -> [u8; 1] {
+        [*self as u8]
+    }
+
+    /// The authority recorded on the registry of an address: the address type followed by the public key hash.
+    /// This identifies the address, as the authority of a key registry identifies its key,
+    /// but is not a key that anyone can sign with, so the registry can only be updated through BIP-137 signatures.
+    pub fn registry_authority(&self, pubkey_hash: &[u8; 20]) -> Pubkey {
+        let mut authority = [0u8; 32];
+        authority[0] = *self as u8;
+        authority[1..21].copy_from_slice(pubkey_hash);
+        Pubkey::new_from_array(authority)
+    }
+}
+
+/// The (text) message that a Bitcoin address signs to register a DID in its registry.
+/// The registry's nonce (see MessageNonce) is included so that each signature can only be used once.
+pub fn btc_register_did_message(registry: &Pubkey, did: &Pubkey, nonce: u64) -> String {
+    format!("did-registry:register_did:{}:{}:{}", registry, did, nonce)
+}
+
+/// The (text) message that a Bitcoin address signs to remove a DID from its registry (see btc_register_did_message)
+pub fn btc_remove_did_message(registry: &Pubkey, did: &Pubkey, nonce: u64) -> String {
+    format!("did-registry:remove_did:{}:{}:{}", registry, did, nonce)
+}
+
+/// Check that the message was signed, using a BIP-137 signature, by the Bitcoin address
+/// of the given type and public key hash. Returns the public key of the address.
+pub fn validate_btc_signature(
+    message: &[u8],
+    signature: &[u8; 65],
+    address_type: BtcAddressType,
+    expected_pubkey_hash: &[u8; 20],
+) -> Result<Secp256k1Pubkey> {
+    let header = signature[0];
+    let (recovery_id, compressed) = match (address_type, header) {
+        (BtcAddressType::P2pkh, 27..=30) => (header - P2PKH_UNCOMPRESSED_HEADER, false),
+        (BtcAddressType::P2pkh, 31..=34) => (header - P2PKH_COMPRESSED_HEADER, true),
+        (BtcAddressType::P2wpkh, 39..=42) => (header - P2WPKH_HEADER, true),
+        _ => return Err(ErrorCode::InvalidBtcSignature.into()),
+    };
+
+    let message_hash = hash(
+        hashv(&[
+            BITCOIN_MESSAGE_PREFIX,
+            &message_length_prefix(message.len())?,
+            message,
+        ])
+        .as_ref(),
+    );
+
+    let pubkey = secp256k1_recover(message_hash.as_ref(), recovery_id, &signature[1..])
+        .map_err(|_| ErrorCode::InvalidBtcSignature)?;
+    let pubkey_bytes = pubkey.to_bytes();
+
+    let serialized_pubkey = if compressed {
+        // the prefix of a compressed key encodes whether y is even or odd
+        [&[0x02 + (pubkey_bytes[63] & 1)][..], &pubkey_bytes[..32]].concat()
+    } else {
+        [&[0x04][..], pubkey_bytes.as_ref()].concat()
+    };
+
+    // Solana has no RIPEMD-160 syscall, so the hash160 that binds the address to the key is computed here
+    let pubkey_hash = Ripemd160::digest(hash(&serialized_pubkey).as_ref());
+
+    require!(
+        pubkey_hash.as_slice() == expected_pubkey_hash,
+        ErrorCode::WrongBtcSigner
+    );
+    Ok(pubkey)
+}
+
+/// Find the verification method through which the public key of a Bitcoin address is an authority on the DID.
+/// As for eth addresses (see find_eth_address_authority), the DID lists the uncompressed secp256k1 public key
+/// in an EcdsaSecp256k1VerificationKey2019 verification method. Returns the key data of the verification method.
+pub fn find_btc_key_authority(
+    did_account: &Account<DidAccount>,
+    pubkey: &Secp256k1Pubkey,
+) -> Result<Vec<u8>> {
+    let key_data = pubkey.to_bytes().to_vec();
+
+    let is_key_authority = !did_account
+        .verification_methods(
+            Some(&[VerificationMethodType::EcdsaSecp256k1VerificationKey2019]),
+            Some(VerificationMethodFlags::CAPABILITY_INVOCATION),
+            Some(&key_data),
+            None,
+        )
+        .is_empty();
+    require!(is_key_authority, ErrorCode::NotAuthority);
+
+    Ok(key_data)
+}
+
+/// The Bitcoin variable length integer encoding of the message length
+fn message_length_prefix(length: usize) -> Result<Vec<u8>> {
+    match length {
+        0..=0xfc => Ok(vec![length as u8]),
+        0xfd..=0xffff => Ok([&[0xfd][..], (length as u16).to_le_bytes().as_ref()].concat()),
+        _ => Err(ErrorCode::InvalidBtcSignature.into()),
+    }
+}
@@ -0,0 +1,58 @@
+use crate::util::chain_account::Namespace;
+use crate::util::eth_signing::{validate_eth_signature_with_nonce, Secp256k1RawSignature};
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// EVM chains (see https://github.com/ChainAgnostic/namespaces/tree/main/eip155)
+pub const NAMESPACE: &str = "eip155";
+
+const ADDRESS_SIZE: usize = 20;
+const SIGNATURE_SIZE: usize = 65;
+
+/// EVM accounts are listed on a DID by their 20 byte address, and sign messages with personal_sign
+pub struct Eip155;
+
+impl Namespace for Eip155 {
+    fn key_data(&self, address: &str) -> Result<Vec<u8>> {
+        // the address must be lowercase (not EIP-55 checksummed), so that each account has a single account id,
+        // and therefore a single registry
+        let hex = address
+            .strip_prefix("0x")
+            .filter(|hex| hex.len() == ADDRESS_SIZE * 2)
+            .filter(|hex| hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')))
+            .ok_or(ErrorCode::InvalidAccountId)?;
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| ErrorCode::InvalidAccountId.into())
+    }
+
+    fn verify_signature(
+        &self,
+        key_data: &[u8],
+        message: &[u8],
+        nonce: u64,
+        signature: &[u8],
+    ) -> Result<()> {
+        require_eq!(
+            signature.len(),
+            SIGNATURE_SIZE,
+            ErrorCode::InvalidEthSignature
+        );
+
+        // eth signatures are r || s || v, where v is either the recovery id or the recovery id + 27
+        let recovery_id = signature[64];
+        let eth_signature = Secp256k1RawSignature {
+            signature: signature[..64].try_into().unwrap(),
+            recovery_id: if recovery_id >= 27 {
+                recovery_id - 27
+            } else {
+                recovery_id
+            },
+        };
+
+        validate_eth_signature_with_nonce(message, nonce, &eth_signature, key_data)
+    }
+}
@@ -0,0 +1,73 @@
+mod eip155;
+
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use eip155::Eip155;
+
+/// The prefixes of the messages that a chain account signs to authorise updates to its registry
+pub const REGISTER_CHAIN_ACCOUNT_DID_PREFIX: &[u8] = b"did-registry:chain_account:register_did";
+pub const REMOVE_CHAIN_ACCOUNT_DID_PREFIX: &[u8] = b"did-registry:chain_account:remove_did";
+
+// see https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-2.md
+const MAX_NAMESPACE_LENGTH: usize = 8;
+const MAX_REFERENCE_LENGTH: usize = 32;
+// see https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md
+const MAX_ADDRESS_LENGTH: usize = 128;
+
+/// A CAIP-10 account id, e.g. eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb
+pub struct AccountId<'a> {
+    pub namespace: &'a str,
+    pub reference: &'a str,
+    pub address: &'a str,
+}
+
+pub fn parse_account_id(account_id: &str) -> Result<AccountId> {
+    let mut parts = account_id.split(':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(namespace), Some(reference), Some(address), None)
+            if !namespace.is_empty()
+                && namespace.len() <= MAX_NAMESPACE_LENGTH
+                && !reference.is_empty()
+                && reference.len() <= MAX_REFERENCE_LENGTH
+                && !address.is_empty()
+                && address.len() <= MAX_ADDRESS_LENGTH =>
+        {
+            Ok(AccountId {
+                namespace,
+                reference,
+                address,
+            })
+        }
+        _ => Err(ErrorCode::InvalidAccountId.into()),
+    }
+}
+
+/// How the accounts of a CAIP-2 namespace are listed on a DID, and how they sign messages.
+/// To support a new namespace, implement this trait and add it to `namespace`.
+pub trait Namespace {
+    /// The key data of the verification method on a DID for the given account address
+    fn key_data(&self, address: &str) -> Result<Vec<u8>>;
+
+    /// Check that the message, with the nonce, was signed by the account with the given key data
+    fn verify_signature(
+        &self,
+        key_data: &[u8],
+        message: &[u8],
+        nonce: u64,
+        signature: &[u8],
+    ) -> Result<()>;
+}
+
+/// Find the signature verification module for a CAIP-2 namespace
+pub fn namespace(namespace: &str) -> Result<&'static dyn Namespace> {
+    match namespace {
+        eip155::NAMESPACE => Ok(&Eip155),
+        _ => Err(ErrorCode::UnsupportedNamespace.into()),
+    }
+}
+
+/// The message that a chain account signs to authorise an update to its registry.
+/// The registry nonce is added by the namespace when verifying the signature.
+pub fn chain_account_message(prefix: &[u8], registry: &Pubkey, did: &Pubkey) -> Vec<u8> {
+    [prefix, registry.as_ref(), did.as_ref()].concat()
+}
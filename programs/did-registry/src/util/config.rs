@@ -0,0 +1,27 @@
+use crate::state::program_config::ProgramConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// Charge the payer the registry fee set in the program config, if there is one.
+/// The fee is held in the config account until the admin withdraws it.
+pub fn charge_registry_fee<'info>(
+    config: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let fee = ProgramConfig::load(config)?.registry_fee;
+    if fee == 0 {
+        return Ok(());
+    }
+
+    transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: config.clone(),
+            },
+        ),
+        fee,
+    )
+}
@@ -1,4 +1,7 @@
+use crate::ErrorCode;
 use anchor_lang::prelude::*;
+use sol_did::integrations::is_authority;
+use sol_did::state::{DidAccount, VerificationMethodFlags};
 use std::str::FromStr;
 
 // TODO move to sol_did
@@ -11,3 +14,140 @@ impl Id for SolDID {
         Pubkey::from_str("didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc").unwrap()
     }
 }
+
+// TODO move to cryptid
+pub const CRYPTID_ACCOUNT_SEED: &[u8] = b"cryptid_account";
+#[derive(Debug, Clone)]
+pub struct Cryptid;
+
+impl Id for Cryptid {
+    fn id() -> Pubkey {
+        Pubkey::from_str("cryptJTh61jY5kbUmBEXyc86tBUyueBDrLuNSZWmUcs").unwrap()
+    }
+}
+
+/// The default Cryptid account for a DID (the account at index 0)
+pub fn default_cryptid_account(did: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CRYPTID_ACCOUNT_SEED,
+            SolDID::id().as_ref(),
+            did.as_ref(),
+            &0u32.to_le_bytes(),
+        ],
+        &Cryptid::id(),
+    )
+    .0
+}
+
+/// The maximum number of controller DID accounts that can be traversed to find an authority
+pub const MAX_CONTROLLER_CHAIN_LENGTH: usize = 4;
+
+/// Check that the key is an authority on the DID, either directly, or through a chain of
+/// controller DIDs. The controller chain is passed in as the DID accounts of the controllers,
+/// ordered from the controller of the DID to the DID on which the key is a direct authority.
+///
+/// The default Cryptid account of the DID is also accepted as an authority. Note, the key must be a
+/// transaction signer for this to be safe - a Cryptid account can only sign via a CPI from the
+/// Cryptid program, which has already checked that the transaction was signed by an authority on the DID.
+pub fn verify_authority(
+    did_account: &AccountInfo,
+    did: &Pubkey,
+    controller_chain: &[AccountInfo],
+    key: &[u8],
+) -> Result<()> {
+    require_gte!(
+        MAX_CONTROLLER_CHAIN_LENGTH,
+        controller_chain.len(),
+        ErrorCode::ControllerChainTooLong
+    );
+
+    require!(
+        has_authority(did_account, did, controller_chain, key)?,
+        ErrorCode::NotAuthority
+    );
+
+    Ok(())
+}
+
+/// Whether the key is an authority on the DID, as checked by verify_authority
+pub fn has_authority(
+    did_account: &AccountInfo,
+    did: &Pubkey,
+    controller_chain: &[AccountInfo],
+    key: &[u8],
+) -> Result<bool> {
+    let is_direct_authority = is_authority(did_account, None, controller_chain, key, None, None)
+        .map_err(|_| ErrorCode::DIDError)?;
+
+    // only derive the cryptid account if needed, as it is relatively expensive
+    Ok(is_direct_authority
+        || (controller_chain.is_empty() && key == default_cryptid_account(did).as_ref()))
+}
+
+/// Check that the key is a verification method on the DID with all of the required flags.
+/// If a controller chain is used, the key is expected on the last DID in the chain,
+/// which must therefore be a non-generative DID.
+pub fn verify_flags(
+    did_account: &AccountInfo,
+    did: &Pubkey,
+    controller_chain: &[AccountInfo],
+    key: &[u8],
+    required_flags: u16,
+) -> Result<()> {
+    if required_flags == 0 {
+        return Ok(());
+    }
+
+    let did_data = match controller_chain.last() {
+        Some(controller_did_account) => {
+            Account::<DidAccount>::try_from(controller_did_account)?.into_inner()
+        }
+        None => DidAccount::try_from_or_default(&(did_account, *did))?,
+    };
+
+    let flags = to_verification_method_flags(required_flags)?;
+
+    require!(
+        !did_data
+            .verification_methods(None, Some(flags), Some(key), None)
+            .is_empty(),
+        ErrorCode::MissingRequiredFlags
+    );
+
+    Ok(())
+}
+
+/// Parse verification method flags, rejecting any unknown bits
+pub fn to_verification_method_flags(flags: u16) -> Result<VerificationMethodFlags> {
+    VerificationMethodFlags::from_bits(flags).ok_or_else(|| ErrorCode::InvalidFlags.into())
+}
+
+/// Parse the accounts of an instruction acting on many DIDs at once.
+/// The accounts are passed as (did, did account) pairs, and each did account must be the account of
+/// the DID preceding it. Returns the did accounts along with their DIDs.
+pub fn did_account_pairs<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<Vec<(&'a AccountInfo<'info>, Pubkey)>> {
+    require!(
+        !accounts.is_empty() && accounts.len() % 2 == 0,
+        ErrorCode::InvalidDidAccountPairs
+    );
+
+    accounts
+        .chunks(2)
+        .map(|pair| {
+            let (did, did_account) = (&pair[0], &pair[1]);
+
+            let (expected_did_account, _) =
+                Pubkey::find_program_address(&[DID_ACCOUNT_SEED, did.key.as_ref()], &SolDID::id());
+            require_keys_eq!(
+                *did_account.key,
+                expected_did_account,
+                ErrorCode::InvalidDidAccountPairs
+            );
+
+            Ok((did_account, *did.key))
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+use crate::util::instructions_sysvar::load_preceding_instruction;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+
+/// The prefixes of the messages that a key signs to authorise updates to its registry.
+/// They separate the domains of the messages, so that a signature for one update cannot be used for another.
+pub const REGISTER_DID_MESSAGE_PREFIX: &[u8] = b"did-registry:register_did";
+pub const REMOVE_DID_MESSAGE_PREFIX: &[u8] = b"did-registry:remove_did";
+pub const RESIZE_MESSAGE_PREFIX: &[u8] = b"did-registry:resize";
+pub const CLOSE_MESSAGE_PREFIX: &[u8] = b"did-registry:close";
+
+// The layout of the ed25519 program instruction data, which is shared by the secp256r1 program
+// see https://docs.solana.com/developing/runtime-facilities/programs#ed25519-program
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+// an instruction index of u16::MAX means "this instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// The message that a key signs to authorise an update to its registry.
+/// The registry's nonce (see MessageNonce) is included so that each signature can only be used once.
+pub fn signed_message(prefix: &[u8], registry: &Pubkey, data: &[u8], nonce: u64) -> Vec<u8> {
+    [prefix, registry.as_ref(), data, &nonce.to_le_bytes()].concat()
+}
+
+/// Check that the instruction immediately preceding the current one is an ed25519 program instruction
+/// verifying a single signature of the expected message by the expected signer.
+/// The ed25519 program has already verified the signature itself, otherwise the transaction would have failed.
+pub fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    verify_signature_instruction(
+        instructions_sysvar,
+        &ed25519_program::ID,
+        expected_signer.as_ref(),
+        expected_message,
+    )
+}
+
+/// Check that the instruction immediately preceding the current one belongs to the given signature verification
+/// program, which must use the ed25519 program instruction layout, and verifies a single signature
+/// of the expected message by the expected signer.
+pub fn verify_signature_instruction(
+    instructions_sysvar: &AccountInfo,
+    program_id: &Pubkey,
+    expected_signer: &[u8],
+    expected_message: &[u8],
+) -> Result<()> {
+    let instruction = load_preceding_instruction(instructions_sysvar, program_id)?;
+
+    let data = &instruction.data;
+    let offsets = data
+        .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    let read_offset =
+        |position: usize| u16::from_le_bytes([offsets[position], offsets[position + 1]]);
+
+    // only a single signature is supported, and all its data must be contained in the instruction itself
+    require_eq!(data[0], 1, ErrorCode::InvalidSignatureInstruction);
+    require!(
+        read_offset(2) == CURRENT_INSTRUCTION
+            && read_offset(6) == CURRENT_INSTRUCTION
+            && read_offset(12) == CURRENT_INSTRUCTION,
+        ErrorCode::InvalidSignatureInstruction
+    );
+
+    let pubkey_offset = read_offset(4) as usize;
+    let message_offset = read_offset(8) as usize;
+    let message_size = read_offset(10) as usize;
+
+    let signer = data
+        .get(pubkey_offset..pubkey_offset + expected_signer.len())
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+    require!(signer == expected_signer, ErrorCode::WrongSigner);
+    require!(message == expected_message, ErrorCode::WrongMessage);
+
+    Ok(())
+}
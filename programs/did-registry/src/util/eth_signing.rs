@@ -1,10 +1,50 @@
+use crate::util::instructions_sysvar::load_preceding_instruction;
 use crate::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    secp256k1_program, secp256k1_recover::Secp256k1Pubkey,
+    sysvar::instructions::load_current_index_checked,
+};
 use sol_did::{
-    state::Secp256k1RawSignature as SolDidSecp256k1RawSignature,
+    integrations::is_authority,
+    state::{
+        DidAccount, Secp256k1RawSignature as SolDidSecp256k1RawSignature, VerificationMethodFlags,
+        VerificationMethodType,
+    },
     utils::{convert_secp256k1pub_key_to_address, eth_verify_message},
 };
 
+/// The prefix added to a message by eth personal_sign before hashing
+const ETH_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+// The layout of the secp256k1 program instruction data
+// see https://docs.solana.com/developing/runtime-facilities/programs#secp256k1-program
+const SIGNATURE_OFFSETS_START: usize = 1;
+const SIGNATURE_OFFSETS_SIZE: usize = 11;
+const ETH_ADDRESS_SIZE: usize = 20;
+
+/// The length of an (uncompressed, unprefixed) secp256k1 public key in a verification method
+const SECP256K1_PUBLIC_KEY_SIZE: usize = 64;
+
+/// The form of the verification method through which an eth address is an authority on a DID
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EthAddressForm {
+    /// The verification method contains the eth address (EcdsaSecp256k1RecoveryMethod2020)
+    Address,
+    /// The verification method contains the secp256k1 public key that the address is derived from
+    /// (EcdsaSecp256k1VerificationKey2019)
+    PublicKey,
+}
+
+/// The authority recorded on the registry of an eth address: the address, padded with zeros.
+/// As with Bitcoin addresses (see BtcAddressType::registry_authority), this identifies the address,
+/// but is not a key that anyone can sign with.
+pub fn eth_registry_authority(eth_address: &[u8; 20]) -> Pubkey {
+    let mut authority = [0u8; 32];
+    authority[..ETH_ADDRESS_SIZE].copy_from_slice(eth_address);
+    Pubkey::new_from_array(authority)
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct Secp256k1RawSignature {
     pub signature: [u8; 64],
@@ -24,9 +64,24 @@ pub fn validate_eth_signature(
     eth_signature: &Secp256k1RawSignature,
     expected_address: &[u8],
 ) -> Result<()> {
-    let secp256k1_pubkey = eth_verify_message(
+    validate_eth_signature_with_nonce(
         message,
         0u64, // We do not use a nonce here - there is no risk of replay when adding a DID to a registry
+        eth_signature,
+        expected_address,
+    )
+}
+
+/// Check that the message, followed by the nonce, was signed (using personal_sign) by the expected eth address
+pub fn validate_eth_signature_with_nonce(
+    message: &[u8],
+    nonce: u64,
+    eth_signature: &Secp256k1RawSignature,
+    expected_address: &[u8],
+) -> Result<()> {
+    let secp256k1_pubkey = eth_verify_message(
+        message,
+        nonce,
         eth_signature.signature,
         eth_signature.recovery_id,
     )
@@ -41,3 +96,101 @@ pub fn validate_eth_signature(
         false => Err(ErrorCode::WrongEthSigner.into()),
     }
 }
+
+/// The message, as passed to the secp256k1 program, that an eth address signs (using personal_sign)
+/// to register a DID in its registry. The registry and its nonce (see MessageNonce) are included,
+/// so that each signature can only be used once, and only for that registry.
+pub fn eth_register_did_message(registry: &Pubkey, did: &Pubkey, nonce: u64) -> Vec<u8> {
+    let message = format!("did-registry:register_did:{}:{}:{}", registry, did, nonce);
+    [
+        ETH_MESSAGE_PREFIX,
+        message.len().to_string().as_bytes(),
+        message.as_bytes(),
+    ]
+    .concat()
+}
+
+/// Check that the instruction immediately preceding the current one is a secp256k1 program instruction
+/// verifying a single signature of the expected message by the expected eth address.
+/// The secp256k1 program has already verified the signature itself, otherwise the transaction would have failed.
+pub fn verify_secp256k1_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_address: &[u8],
+    expected_message: &[u8],
+) -> Result<()> {
+    let instruction = load_preceding_instruction(instructions_sysvar, &secp256k1_program::ID)?;
+
+    let data = &instruction.data;
+    let offsets = data
+        .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    let read_offset =
+        |position: usize| u16::from_le_bytes([offsets[position], offsets[position + 1]]) as usize;
+
+    // only a single signature is supported, and all its data must be contained in the secp256k1 instruction itself.
+    // Unlike the ed25519 program, the secp256k1 program uses the absolute index of the instruction, and a u8.
+    let signature_index = load_current_index_checked(instructions_sysvar)?
+        .checked_sub(1)
+        .ok_or(ErrorCode::MissingSignatureInstruction)?;
+    require_eq!(data[0], 1, ErrorCode::InvalidSignatureInstruction);
+    require!(
+        [offsets[2], offsets[5], offsets[10]]
+            .iter()
+            .all(|index| u16::from(*index) == signature_index),
+        ErrorCode::InvalidSignatureInstruction
+    );
+
+    let address_offset = read_offset(3);
+    let message_offset = read_offset(6);
+    let message_size = read_offset(8);
+
+    let address = data
+        .get(address_offset..address_offset + ETH_ADDRESS_SIZE)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+    require!(address == expected_address, ErrorCode::WrongEthSigner);
+    require!(message == expected_message, ErrorCode::WrongMessage);
+
+    Ok(())
+}
+
+/// Find how the eth address is an authority on the DID - either through a verification method containing the address,
+/// or through one containing a secp256k1 public key that derives the address.
+/// Returns the form, along with the key data of the matching verification method.
+pub fn find_eth_address_authority(
+    did_account: &Account<DidAccount>,
+    eth_address: &[u8; 20],
+) -> Result<(EthAddressForm, Vec<u8>)> {
+    let is_address_authority = is_authority(
+        &did_account.to_account_info(),
+        None,
+        &[], // the eth address must be a direct authority on the DID
+        eth_address.as_ref(),
+        None,
+        None,
+    )
+    .map_err(|_| ErrorCode::DIDError)?;
+
+    if is_address_authority {
+        return Ok((EthAddressForm::Address, eth_address.to_vec()));
+    }
+
+    did_account
+        .verification_methods(
+            Some(&[VerificationMethodType::EcdsaSecp256k1VerificationKey2019]),
+            Some(VerificationMethodFlags::CAPABILITY_INVOCATION),
+            None,
+            None,
+        )
+        .into_iter()
+        .find(|vm| {
+            vm.key_data.len() == SECP256K1_PUBLIC_KEY_SIZE
+                && convert_secp256k1pub_key_to_address(&Secp256k1Pubkey::new(&vm.key_data))
+                    == *eth_address
+        })
+        .map(|vm| (EthAddressForm::PublicKey, vm.key_data.clone()))
+        .ok_or_else(|| ErrorCode::NotAuthority.into())
+}
@@ -0,0 +1,33 @@
+use crate::state::program_config::ProgramConfig;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use solana_gateway::Gateway;
+
+/// If the program config names a gatekeeper network, ensure that the gateway token (Civic Pass)
+/// was issued to the owner in that network, and is active and unexpired.
+pub fn verify_gateway_token(
+    config: &AccountInfo,
+    gateway_token: &AccountInfo,
+    owner: &Pubkey,
+) -> Result<()> {
+    let gatekeeper_network = ProgramConfig::load(config)?.gatekeeper_network;
+    if gatekeeper_network == Pubkey::default() {
+        return Ok(());
+    }
+
+    Gateway::verify_gateway_token_account_info(gateway_token, owner, &gatekeeper_network, None)
+        .map_err(|error| {
+            msg!("Invalid gateway token: {:?}", error);
+            error!(ErrorCode::InvalidGatewayToken)
+        })
+}
+
+/// Block instructions whose authority cannot hold a gateway token (e.g. an Ethereum address, or many keys at once)
+/// while the program config names a gatekeeper network.
+pub fn require_no_gatekeeper_network(config: &AccountInfo) -> Result<()> {
+    require!(
+        ProgramConfig::load(config)?.gatekeeper_network == Pubkey::default(),
+        ErrorCode::GatewayTokenNotSupported
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::state::registry_history::{HistoryAction, RegistryHistory};
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Split the history of a registry off the end of the remaining accounts, if the registry has one.
+/// Returns the accounts before it (e.g. a controller chain), along with the history.
+pub fn split_history<'a, 'info>(
+    registry: &Pubkey,
+    has_history: bool,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(
+    &'a [AccountInfo<'info>],
+    Option<Account<'info, RegistryHistory>>,
+)> {
+    if !has_history {
+        return Ok((accounts, None));
+    }
+
+    let (history_info, rest) = accounts.split_last().ok_or(ErrorCode::HistoryRequired)?;
+    let history: Account<RegistryHistory> = Account::try_from(history_info)?;
+    require_keys_eq!(history.registry, *registry, ErrorCode::InvalidHistory);
+
+    Ok((rest, Some(history)))
+}
+
+/// Take the history of a registry off the start of the remaining accounts, if the registry has one,
+/// for instructions that act on many registries, whose histories are passed in the order of the registries.
+/// Returns the history, along with the accounts after it.
+pub fn take_history<'a, 'info>(
+    registry: &Pubkey,
+    has_history: bool,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(
+    Option<Account<'info, RegistryHistory>>,
+    &'a [AccountInfo<'info>],
+)> {
+    if !has_history {
+        return Ok((None, accounts));
+    }
+
+    let (history_info, rest) = accounts.split_first().ok_or(ErrorCode::HistoryRequired)?;
+    let history: Account<RegistryHistory> = Account::try_from(history_info)?;
+    require_keys_eq!(history.registry, *registry, ErrorCode::InvalidHistory);
+
+    Ok((Some(history), rest))
+}
+
+/// Record changes to a registry in its history, if it has one
+pub fn record_history(
+    history: &mut Option<Account<RegistryHistory>>,
+    changes: &[(Pubkey, HistoryAction)],
+    signer: Pubkey,
+) -> Result<()> {
+    if let Some(history) = history {
+        for (did, action) in changes {
+            history.record(*did, *action, signer)?;
+        }
+        // the history is not in the instruction's accounts, so must be written explicitly
+        history.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
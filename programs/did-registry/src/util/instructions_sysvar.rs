@@ -0,0 +1,27 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+/// Load the instruction immediately preceding the current one, ensuring it belongs to the expected program.
+/// Used to find the signature verification (precompile) instruction for the current instruction.
+pub fn load_preceding_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_program_id: &Pubkey,
+) -> Result<Instruction> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let preceding_index = current_index
+        .checked_sub(1)
+        .ok_or(ErrorCode::MissingSignatureInstruction)?;
+    let instruction = load_instruction_at_checked(preceding_index.into(), instructions_sysvar)?;
+
+    require_keys_eq!(
+        instruction.program_id,
+        *expected_program_id,
+        ErrorCode::MissingSignatureInstruction
+    );
+
+    Ok(instruction)
+}
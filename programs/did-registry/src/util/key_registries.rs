@@ -0,0 +1,131 @@
+use crate::state::key_registry::KeyRegistry;
+use crate::state::program_config::ProgramConfig;
+use crate::util::config::charge_registry_fee;
+use crate::util::rent::{record_rent_payment, refund_rent};
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+/// A key, passed as a signer, along with the account of its registry (in the namespace of the instruction)
+/// and the registry's bump seed
+pub struct KeyAndRegistry<'a, 'info> {
+    pub key: &'a AccountInfo<'info>,
+    pub registry: &'a AccountInfo<'info>,
+    pub bump: u8,
+}
+
+/// Parse the accounts of an instruction acting on the registries of many keys at once.
+/// The accounts are passed as (key, registry) pairs, followed by the histories of the registries that have one,
+/// in the same order (see take_history). Each key must be a signer,
+/// and each registry must be the registry of the key preceding it in the namespace.
+/// Returns the pairs, along with the accounts after them.
+pub fn key_registry_pairs<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    namespace: &[u8],
+) -> Result<(Vec<KeyAndRegistry<'a, 'info>>, &'a [AccountInfo<'info>])> {
+    let namespace = KeyRegistry::namespace_seed(namespace)?;
+
+    // histories are never signers, so the pairs end at the first non-signer in the place of a key
+    let pair_count = accounts
+        .chunks_exact(2)
+        .take_while(|pair| pair[0].is_signer)
+        .count();
+    require!(pair_count > 0, ErrorCode::InvalidKeyRegistryPairs);
+    let (pair_accounts, rest) = accounts.split_at(pair_count * 2);
+
+    let pairs = pair_accounts
+        .chunks(2)
+        .map(|pair| {
+            let (key, registry) = (&pair[0], &pair[1]);
+
+            let (expected_registry, bump) = KeyRegistry::find_address(key.key, namespace);
+            require_keys_eq!(
+                *registry.key,
+                expected_registry,
+                ErrorCode::InvalidKeyRegistryPairs
+            );
+
+            Ok(KeyAndRegistry {
+                key,
+                registry,
+                bump,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((pairs, rest))
+}
+
+/// Load the registry of a key, creating it if it does not exist yet, with the capacity set in the program config.
+/// The payer pays for a new registry, along with the registry fee.
+pub fn load_or_create_key_registry<'info>(
+    pair: &KeyAndRegistry<'_, 'info>,
+    namespace: &[u8],
+    config: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<Account<'info, KeyRegistry>> {
+    if !pair.registry.data_is_empty() {
+        return Account::try_from(pair.registry);
+    }
+
+    charge_registry_fee(config, payer, system_program)?;
+
+    let space = KeyRegistry::calculate_size(ProgramConfig::load(config)?.initial_capacity);
+    let rent = Rent::get()?.minimum_balance(space);
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount {
+                from: payer.clone(),
+                to: pair.registry.clone(),
+            },
+            &[&[
+                KeyRegistry::SEED_PREFIX,
+                pair.key.key.as_ref(),
+                namespace,
+                &[pair.bump],
+            ]],
+        ),
+        rent,
+        space as u64,
+        &crate::ID,
+    )?;
+
+    // the account data is all zeros, which deserializes to an empty registry
+    let mut registry: Account<KeyRegistry> = Account::try_from_unchecked(pair.registry)?;
+    registry.authority = *pair.key.key;
+    registry.namespace = namespace.to_vec();
+    record_rent_payment(&mut registry.rent_payers, *payer.key, rent)?;
+    Ok(registry)
+}
+
+/// Shrink a key registry to the given size, refunding the rent freed to its rent payers (see refund_rent).
+/// Any rent freed that is not owed to a rent payer is paid to the recipient.
+/// The registry is never shrunk below the space needed for its DIDs, or below its initial size.
+pub fn shrink_key_registry(
+    registry: &mut Account<KeyRegistry>,
+    size: usize,
+    recipient: &AccountInfo,
+    rent_payer_accounts: &[AccountInfo],
+) -> Result<()> {
+    let registry_info = registry.to_account_info();
+    let size = size
+        .max(KeyRegistry::calculate_size(
+            registry.dids.len().try_into().unwrap(),
+        ))
+        .max(KeyRegistry::INITIAL_SIZE)
+        .min(registry_info.data_len());
+    registry_info.realloc(size, false)?;
+
+    let rent_freed = registry_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(size));
+    refund_rent(
+        &registry_info,
+        &mut registry.rent_payers,
+        rent_freed,
+        recipient,
+        rent_payer_accounts,
+    )
+}
@@ -0,0 +1,31 @@
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// Load a registry in the layout of the first release of the program (L), which cannot be loaded
+/// in its current layout (T), because it was filled, leaving no room for the fields appended since.
+pub fn load_legacy_registry<T, L>(registry: &AccountInfo) -> Result<L>
+where
+    T: AccountDeserialize + Discriminator,
+    L: AnchorDeserialize,
+{
+    let data = registry.try_borrow_data()?;
+    require!(
+        data.starts_with(&T::discriminator()),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(
+        T::try_deserialize(&mut &data[..]).is_err(),
+        ErrorCode::RegistryNotLegacy
+    );
+
+    L::deserialize(&mut &data[8..])
+        .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Write a registry in its current layout, once it has been resized to fit
+pub fn write_registry<T: AccountSerialize>(registry: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = registry.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)
+}
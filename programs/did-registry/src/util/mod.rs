@@ -1,2 +1,14 @@
+pub(crate) mod btc_signing;
+pub(crate) mod chain_account;
+pub(crate) mod config;
 pub(crate) mod did;
+pub(crate) mod ed25519_signing;
 pub(crate) mod eth_signing;
+pub(crate) mod gateway;
+pub(crate) mod history;
+pub(crate) mod instructions_sysvar;
+pub(crate) mod key_registries;
+pub(crate) mod legacy;
+pub(crate) mod pending_removal;
+pub(crate) mod rent;
+pub(crate) mod webauthn;
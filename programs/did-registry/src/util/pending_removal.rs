@@ -0,0 +1,36 @@
+use crate::state::pending_removal::PendingRemoval;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+
+/// If the registry has a removal delay, ensure that the removal of the DID was requested,
+/// and the delay has elapsed, and close the pending removal, refunding its payer.
+/// Otherwise, the pending removal and payer accounts are ignored.
+pub fn take_pending_removal<'info>(
+    removal_delay: u64,
+    registry: &Pubkey,
+    did: &Pubkey,
+    pending_removal: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+) -> Result<()> {
+    if removal_delay == 0 {
+        return Ok(());
+    }
+
+    let pending_removal = Account::<PendingRemoval>::try_from(pending_removal)
+        .map_err(|_| error!(ErrorCode::RemovalDelayed))?;
+    require_keys_eq!(
+        pending_removal.registry,
+        *registry,
+        ErrorCode::RemovalDelayed
+    );
+    require_keys_eq!(pending_removal.did, *did, ErrorCode::RemovalDelayed);
+    require_keys_eq!(pending_removal.payer, payer.key());
+    require_gte!(
+        Clock::get()?.slot,
+        pending_removal.unlock_slot,
+        ErrorCode::RemovalLocked
+    );
+
+    pending_removal.close(payer.clone())
+}
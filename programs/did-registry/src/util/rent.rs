@@ -0,0 +1,162 @@
+use crate::state::rent_payer::RentPayer;
+use crate::util::config::charge_registry_fee;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// Record that the payer paid the given rent for a registry, adding to its earlier payments, if it has any
+pub fn record_rent_payment(
+    rent_payers: &mut Vec<RentPayer>,
+    payer: Pubkey,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+
+    match rent_payers
+        .iter_mut()
+        .find(|rent_payer| rent_payer.payer == payer)
+    {
+        Some(rent_payer) => rent_payer.lamports += lamports,
+        None => {
+            require_gt!(
+                RentPayer::MAX_RENT_PAYERS,
+                rent_payers.len(),
+                ErrorCode::TooManyRentPayers
+            );
+            rent_payers.push(RentPayer { payer, lamports });
+        }
+    }
+
+    Ok(())
+}
+
+/// Charge the payer of a registry that was just created (e.g. through init_if_needed) the registry fee,
+/// and record the rent it paid for the registry
+pub fn pay_for_new_registry<'info>(
+    registry: &AccountInfo<'info>,
+    rent_payers: &mut Vec<RentPayer>,
+    config: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    record_rent_payment(rent_payers, *payer.key, registry.lamports())?;
+    charge_registry_fee(config, payer, system_program)
+}
+
+/// Split the accounts of the rent payers of a registry off the end of the remaining accounts.
+/// An account must be passed for each rent payer other than the payer of the instruction,
+/// in the order in which they were recorded. Returns the accounts before them (e.g. a controller chain),
+/// along with the rent payer accounts.
+pub fn split_rent_payers<'a, 'info>(
+    rent_payers: &[RentPayer],
+    payer: &Pubkey,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let expected = rent_payers
+        .iter()
+        .map(|rent_payer| rent_payer.payer)
+        .filter(|rent_payer| rent_payer != payer)
+        .collect::<Vec<_>>();
+    require_gte!(accounts.len(), expected.len(), ErrorCode::RentPayerMissing);
+
+    let (rest, rent_payer_accounts) = accounts.split_at(accounts.len() - expected.len());
+    require!(
+        rent_payer_accounts
+            .iter()
+            .map(|account| *account.key)
+            .eq(expected),
+        ErrorCode::RentPayerMissing
+    );
+
+    Ok((rest, rent_payer_accounts))
+}
+
+/// Refund lamports from a registry to the rent payers that paid for it, the most recent first,
+/// paying anything left over (i.e. rent that was not recorded) to the payer of the instruction.
+/// The account of each rent payer that is refunded, other than the payer, must be passed in rent_payer_accounts.
+/// A rent payer that co-signs the transaction waives its refund in favour of the payer.
+pub fn refund_rent(
+    registry: &AccountInfo,
+    rent_payers: &mut Vec<RentPayer>,
+    mut lamports: u64,
+    payer: &AccountInfo,
+    rent_payer_accounts: &[AccountInfo],
+) -> Result<()> {
+    while lamports > 0 {
+        let rent_payer = match rent_payers.last_mut() {
+            Some(rent_payer) => rent_payer,
+            None => break,
+        };
+        let refund = rent_payer.lamports.min(lamports);
+
+        if rent_payer.payer == *payer.key {
+            move_lamports(registry, payer, refund)?;
+        } else {
+            let account = rent_payer_accounts
+                .iter()
+                .find(|account| *account.key == rent_payer.payer)
+                .ok_or(ErrorCode::RentPayerMissing)?;
+            if account.is_signer {
+                move_lamports(registry, payer, refund)?;
+            } else {
+                move_lamports(registry, account, refund)?;
+            }
+        }
+
+        rent_payer.lamports -= refund;
+        if rent_payer.lamports == 0 {
+            rent_payers.pop();
+        }
+        lamports -= refund;
+    }
+
+    move_lamports(registry, payer, lamports)
+}
+
+/// Resize a registry. If it grows, the payer pays the extra rent, which is recorded against it.
+/// If it shrinks, the rent freed is refunded to its rent payers (see refund_rent).
+pub fn resize_registry<'info>(
+    registry: &AccountInfo<'info>,
+    rent_payers: &mut Vec<RentPayer>,
+    size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    rent_payer_accounts: &[AccountInfo],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(size);
+    let lamports = registry.lamports();
+
+    if rent > lamports {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: registry.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+        record_rent_payment(rent_payers, *payer.key, rent - lamports)?;
+    } else if size < registry.data_len() {
+        refund_rent(
+            registry,
+            rent_payers,
+            lamports - rent,
+            payer,
+            rent_payer_accounts,
+        )?;
+    }
+
+    registry.realloc(size, false)?;
+    Ok(())
+}
+
+// the registry is owned by the program, so its lamports can be moved without the system program
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= lamports;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::state::passkey_registry::PASSKEY_SIZE;
+use crate::util::ed25519_signing::verify_signature_instruction;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Secp256r1Program;
+
+impl Id for Secp256r1Program {
+    fn id() -> Pubkey {
+        Pubkey::from_str("Secp256r1SigVerify1111111111111111111111111").unwrap()
+    }
+}
+
+/// The prefixes of the messages that a passkey signs (as the WebAuthn challenge) to authorise updates to its registry
+pub const REGISTER_DID_PREFIX: &[u8] = b"did-registry:passkey:register_did";
+pub const REMOVE_DID_PREFIX: &[u8] = b"did-registry:passkey:remove_did";
+pub const RESIZE_PREFIX: &[u8] = b"did-registry:passkey:resize";
+pub const CLOSE_PREFIX: &[u8] = b"did-registry:passkey:close";
+
+// see https://www.w3.org/TR/webauthn-2/#sctn-authenticator-data
+const AUTHENTICATOR_DATA_FLAGS_INDEX: usize = 32;
+const USER_PRESENT_FLAG: u8 = 0x01;
+const ASSERTION_TYPE: &[u8] = br#""type":"webauthn.get""#;
+
+/// The parts of a WebAuthn assertion that the passkey signed, alongside the signature itself
+/// (which is verified by the secp256r1 program)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WebAuthnAssertion {
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+}
+
+/// The WebAuthn challenge that a passkey signs to authorise an update to its registry.
+/// The registry's nonce (see MessageNonce) is included so that each assertion can only be used once.
+pub fn passkey_challenge(prefix: &[u8], registry: &Pubkey, data: &[u8], nonce: u64) -> [u8; 32] {
+    hash(&[prefix, registry.as_ref(), data, &nonce.to_le_bytes()].concat()).to_bytes()
+}
+
+/// Check that the assertion is a WebAuthn assertion over the expected challenge, and that the instruction
+/// immediately preceding the current one is a secp256r1 program instruction verifying the passkey's signature of it.
+/// The secp256r1 program has already verified the signature itself, otherwise the transaction would have failed.
+pub fn verify_webauthn_assertion(
+    instructions_sysvar: &AccountInfo,
+    key: &[u8; PASSKEY_SIZE],
+    assertion: &WebAuthnAssertion,
+    expected_challenge: &[u8],
+) -> Result<()> {
+    // the user must have been present when the assertion was made
+    let flags = assertion
+        .authenticator_data
+        .get(AUTHENTICATOR_DATA_FLAGS_INDEX)
+        .ok_or(ErrorCode::InvalidWebAuthnAssertion)?;
+    require!(
+        flags & USER_PRESENT_FLAG != 0,
+        ErrorCode::InvalidWebAuthnAssertion
+    );
+
+    // the client data is serialised by the browser, so it is enough to check that it contains
+    // the expected type and challenge, rather than parsing it
+    require!(
+        contains(&assertion.client_data_json, ASSERTION_TYPE),
+        ErrorCode::InvalidWebAuthnAssertion
+    );
+    let challenge = format!(
+        r#""challenge":"{}""#,
+        base64::encode_config(expected_challenge, base64::URL_SAFE_NO_PAD)
+    );
+    require!(
+        contains(&assertion.client_data_json, challenge.as_bytes()),
+        ErrorCode::WrongMessage
+    );
+
+    // a WebAuthn signature is over the authenticator data and the hash of the client data
+    let signed_message = [
+        assertion.authenticator_data.as_slice(),
+        hash(&assertion.client_data_json).as_ref(),
+    ]
+    .concat();
+
+    verify_signature_instruction(
+        instructions_sysvar,
+        &Secp256r1Program::id(),
+        key,
+        &signed_message,
+    )
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
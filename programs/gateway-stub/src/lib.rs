@@ -0,0 +1,85 @@
+//! A stand-in for the Civic gateway program, used in tests only.
+//! It is loaded at the gateway program address, and issues gateway tokens with the same address and layout,
+//! but, unlike the gateway program, it lets anyone issue a token in any gatekeeper network.
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use solana_gateway::state::GatewayToken;
+
+declare_id!("gatem74V238djXdzWnJf94Wo1DcnuGkfijbf3AuBhfs");
+
+pub const GATEWAY_TOKEN_ADDRESS_SEED: &[u8] = b"gateway";
+/// The additional seed of the default gateway token of a wallet
+pub const DEFAULT_ADDRESS_SEED: [u8; 8] = [0; 8];
+
+#[program]
+pub mod gateway_stub {
+    use super::*;
+
+    /// Issue the default gateway token of the owner in the gatekeeper network, with an optional expiry time.
+    /// The payer is recorded as the issuing gatekeeper.
+    pub fn issue(ctx: Context<Issue>, expire_time: Option<i64>) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let gatekeeper_network = ctx.accounts.gatekeeper_network.key();
+        let (gateway_token, bump) = Pubkey::find_program_address(
+            &[
+                owner.as_ref(),
+                GATEWAY_TOKEN_ADDRESS_SEED,
+                &DEFAULT_ADDRESS_SEED,
+                gatekeeper_network.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(ctx.accounts.gateway_token.key(), gateway_token);
+
+        let data = GatewayToken::new_vanilla(
+            &owner,
+            &gatekeeper_network,
+            &ctx.accounts.payer.key(),
+            &expire_time,
+        )
+        .try_to_vec()?;
+
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.gateway_token.to_account_info(),
+                },
+                &[&[
+                    owner.as_ref(),
+                    GATEWAY_TOKEN_ADDRESS_SEED,
+                    &DEFAULT_ADDRESS_SEED,
+                    gatekeeper_network.as_ref(),
+                    &[bump],
+                ]],
+            ),
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            &crate::ID,
+        )?;
+        ctx.accounts
+            .gateway_token
+            .try_borrow_mut_data()?
+            .copy_from_slice(&data);
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Issue<'info> {
+    /// The gateway token to create
+    /// CHECK: Checked against the derived address
+    #[account(mut)]
+    pub gateway_token: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The wallet that the token is issued to
+    /// CHECK: This can be any public key
+    pub owner: UncheckedAccount<'info>,
+    /// The gatekeeper network that the token is issued in
+    /// CHECK: This can be any public key
+    pub gatekeeper_network: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
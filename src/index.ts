@@ -1,6 +1,17 @@
 export * from "./service/keyRegistry/Registry";
 export * from "./service/keyRegistry/EthRegistry";
+export * from "./service/keyRegistry/BtcRegistry";
 export * from "./service/keyRegistry/ReadOnlyRegistry";
+export * from "./service/keyRegistry/KeyRegistries";
 
 export * from "./service/controllerRegistry/ControllerRegistry";
 export * from "./service/controllerRegistry/ReadOnlyControllerRegistry";
+
+export * from "./service/passkeyRegistry/PasskeyRegistry";
+export * from "./service/chainAccountRegistry/ChainAccountRegistry";
+export * from "./service/externalControllerRegistry/ExternalControllerRegistry";
+export * from "./service/assetRegistry/AssetRegistry";
+
+export * from "./service/succession/DidSuccession";
+
+export * from "./service/config/ProgramConfig";
@@ -4,10 +4,21 @@ import {
   DidSolIdentifier,
   ExtendedCluster,
 } from "@identity.com/sol-did-client";
-import { PublicKey } from "@solana/web3.js";
-import { DidAccount } from "../types";
-import { DID_REGISTRY_PROGRAM_ID } from "./constants";
-import { confirm } from "./util";
+import { AccountMeta, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  DidAccount,
+  Namespace,
+  PendingRemoval,
+  RegistryHistoryEntry,
+  RentPayer,
+} from "../types";
+import {
+  DID_REGISTRY_PROGRAM_ID,
+  PENDING_REMOVAL_SEED_PREFIX,
+  REGISTRY_HISTORY_SEED_PREFIX,
+} from "./constants";
+import { confirm, getSuccessionAddress, namespaceSeed } from "./util";
 
 /**
  * A common base class for all registries (key and controller)
@@ -17,17 +28,21 @@ export abstract class CommonRegistry {
   protected cluster: ExtendedCluster;
   protected address: Uint8Array;
   protected seedPrefix: string;
+  // The application namespace of the registry (empty = the default registry)
+  protected namespace: Buffer;
   protected registryAddress: PublicKey;
   protected registryBump: number;
 
   protected constructor(
     address: Uint8Array,
     seedPrefix: string,
-    cluster: ExtendedCluster
+    cluster: ExtendedCluster,
+    namespace: Namespace = ""
   ) {
     this.address = address;
     this.seedPrefix = seedPrefix;
     this.cluster = cluster;
+    this.namespace = namespaceSeed(namespace);
     [this.registryAddress, this.registryBump] =
       this.getRegistryAddressAndBump();
   }
@@ -46,6 +61,39 @@ export abstract class CommonRegistry {
     sizeBytes: number;
   }>;
 
+  abstract listDIDs(): Promise<string[]>;
+
+  // convert the rent payers of a registry account, as decoded by anchor
+  protected toRentPayers(
+    rentPayers: { payer: PublicKey; lamports: BN }[]
+  ): RentPayer[] {
+    return rentPayers.map(({ payer, lamports }) => ({
+      payer,
+      lamports: lamports.toNumber(),
+    }));
+  }
+
+  /**
+   * The accounts of the rent payers to pass to instructions that shrink or close the registry,
+   * after any other remaining accounts. The payer of the instruction is refunded directly, so is not included.
+   * Rent payers in cosigners waive their refunds in favour of the payer, and must sign the transaction.
+   */
+  protected rentPayerAccounts(
+    rentPayers: RentPayer[],
+    payer: PublicKey,
+    cosigners: PublicKey[]
+  ): AccountMeta[] {
+    return rentPayers
+      .filter((rentPayer) => !rentPayer.payer.equals(payer))
+      .map((rentPayer) => ({
+        pubkey: rentPayer.payer,
+        isSigner: cosigners.some((cosigner) =>
+          cosigner.equals(rentPayer.payer)
+        ),
+        isWritable: true,
+      }));
+  }
+
   protected didToAccount(did: string): DidAccount {
     const didSolIdentifier = DidSolIdentifier.parse(did);
     const [didAccount, didBump] = didSolIdentifier.dataAccount();
@@ -56,10 +104,178 @@ export abstract class CommonRegistry {
     };
   }
 
+  /**
+   * Convert a chain of controller DIDs into the accounts expected by the program.
+   * The chain is ordered from the controller of the DID to the DID on which the signer is a direct authority.
+   */
+  protected controllerChainToAccounts(
+    controllerChain: string[]
+  ): AccountMeta[] {
+    return controllerChain.map((did) => ({
+      pubkey: this.didToAccount(did).account,
+      isSigner: false,
+      isWritable: false,
+    }));
+  }
+
   public getRegistryAddressAndBump(): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-      [Buffer.from(this.seedPrefix), this.address],
+      [Buffer.from(this.seedPrefix), this.address, this.namespace],
       DID_REGISTRY_PROGRAM_ID
     );
   }
+
+  public getPendingRemovalAddress(did: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(PENDING_REMOVAL_SEED_PREFIX),
+        this.registryAddress.toBuffer(),
+        did.toBuffer(),
+      ],
+      DID_REGISTRY_PROGRAM_ID
+    )[0];
+  }
+
+  public getHistoryAddress(): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REGISTRY_HISTORY_SEED_PREFIX),
+        this.registryAddress.toBuffer(),
+      ],
+      DID_REGISTRY_PROGRAM_ID
+    )[0];
+  }
+
+  /**
+   * The accounts to pass to instructions that add or remove DIDs.
+   * If the registry has a history, it must be passed after any other remaining accounts.
+   */
+  protected historyAccounts(hasHistory: boolean): AccountMeta[] {
+    return hasHistory
+      ? [
+          {
+            pubkey: this.getHistoryAddress(),
+            isSigner: false,
+            isWritable: true,
+          },
+        ]
+      : [];
+  }
+
+  /**
+   * The capacity to create the history with. An existing history is reused,
+   * and the program requires its current capacity to be passed.
+   */
+  protected async historyCapacity(capacity: number): Promise<number> {
+    const history = await this.program.account.registryHistory.fetchNullable(
+      this.getHistoryAddress()
+    );
+    return history ? history.capacity : capacity;
+  }
+
+  /**
+   * List the DIDs added to and removed from this registry, oldest first,
+   * if the registry has a history. Only the most recent entries are kept, up to the history's capacity.
+   */
+  async listHistory(): Promise<RegistryHistoryEntry[]> {
+    const history = await this.program.account.registryHistory.fetchNullable(
+      this.getHistoryAddress()
+    );
+
+    if (!history) return [];
+
+    // once the history is full, the oldest entry is at index "next"
+    const entries = [
+      ...history.entries.slice(history.next),
+      ...history.entries.slice(0, history.next),
+    ];
+
+    // anchor decodes enums as an object with a single key, e.g. { add: {} }
+    return entries.map((entry) => ({
+      did: DidSolIdentifier.create(entry.did, this.cluster).toString(),
+      action: Object.keys(entry.action)[0] as RegistryHistoryEntry["action"],
+      slot: entry.slot.toNumber(),
+      signer: entry.signer,
+    }));
+  }
+
+  protected async pendingRemovalPayer(
+    pendingRemoval: PublicKey
+  ): Promise<PublicKey> {
+    const pendingRemovalAccount =
+      await this.program.account.pendingRemoval.fetch(pendingRemoval);
+    return pendingRemovalAccount.payer;
+  }
+
+  /**
+   * The pending removal of a DID, and its payer, to pass when replacing the DID.
+   * They are only checked if the registry has a removal delay, so if the removal was not requested,
+   * the given default payer is passed instead.
+   */
+  protected async pendingRemovalAccounts(
+    did: PublicKey,
+    defaultPayer: PublicKey
+  ): Promise<{ pendingRemoval: PublicKey; pendingRemovalPayer: PublicKey }> {
+    const pendingRemoval = this.getPendingRemovalAddress(did);
+    const pendingRemovalAccount =
+      await this.program.account.pendingRemoval.fetchNullable(pendingRemoval);
+    return {
+      pendingRemoval,
+      pendingRemovalPayer: pendingRemovalAccount
+        ? pendingRemovalAccount.payer
+        : defaultPayer,
+    };
+  }
+
+  /**
+   * Follow the succession records of a DID, returning its latest successor,
+   * or the DID itself if it has not been superseded.
+   */
+  async resolveSuccessor(did: string): Promise<string> {
+    const seen = new Set<string>();
+    let current = did;
+
+    // stop if the successions form a cycle
+    while (!seen.has(current)) {
+      seen.add(current);
+      const succession = await this.program.account.didSuccession.fetchNullable(
+        getSuccessionAddress(this.didToAccount(current).authority)
+      );
+      if (!succession) break;
+
+      current = DidSolIdentifier.create(
+        succession.successor,
+        this.cluster
+      ).toString();
+    }
+
+    return current;
+  }
+
+  /**
+   * List the registered DIDs, resolving any that have been superseded to their latest successors
+   */
+  async listResolvedDIDs(): Promise<string[]> {
+    const dids = await this.listDIDs();
+    return Promise.all(dids.map((did) => this.resolveSuccessor(did)));
+  }
+
+  /**
+   * List all removals from this registry that are waiting for the registry's removal delay to elapse
+   */
+  async listPendingRemovals(): Promise<PendingRemoval[]> {
+    const pendingRemovals = await this.program.account.pendingRemoval.all([
+      {
+        memcmp: {
+          offset: 8 + 1, // discriminator + version
+          bytes: this.registryAddress.toBase58(),
+        },
+      },
+    ]);
+
+    return pendingRemovals.map(({ account }) => ({
+      did: DidSolIdentifier.create(account.did, this.cluster).toString(),
+      unlockSlot: account.unlockSlot.toNumber(),
+    }));
+  }
 }
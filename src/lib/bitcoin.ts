@@ -0,0 +1,123 @@
+import { utils } from "@project-serum/anchor";
+import { sha256 } from "@noble/hashes/sha256";
+
+/**
+ * The types of Bitcoin address that can be registered.
+ *
+ * NOTE: The order must stay in sync with BtcAddressType in the program, as it is used in the registry seed.
+ */
+export enum BtcAddressType {
+  P2pkh = 0,
+  P2wpkh = 1,
+}
+
+export type BtcAddress = {
+  type: BtcAddressType;
+  // hash160 of the public key
+  pubkeyHash: Uint8Array;
+};
+
+const P2PKH_VERSION = 0x00;
+export const BECH32_CHARSET = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR = [
+  0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3,
+];
+
+export const bech32Polymod = (values: number[]): number =>
+  values.reduce((chk, value) => {
+    const top = chk >> 25;
+    let next = ((chk & 0x1ffffff) << 5) ^ value;
+    BECH32_GENERATOR.forEach((generator, i) => {
+      if ((top >> i) & 1) next ^= generator;
+    });
+    return next;
+  }, 1);
+
+export const bech32HrpExpand = (hrp: string): number[] => [
+  ...Array.from(hrp).map((c) => c.charCodeAt(0) >> 5),
+  0,
+  ...Array.from(hrp).map((c) => c.charCodeAt(0) & 31),
+];
+
+// convert the 5-bit groups of a bech32 payload to bytes
+const fromWords = (words: number[]): Uint8Array => {
+  let acc = 0;
+  let bits = 0;
+  const bytes: number[] = [];
+  words.forEach((word) => {
+    acc = (acc << 5) | word;
+    bits += 5;
+    if (bits >= 8) {
+      bits -= 8;
+      bytes.push((acc >> bits) & 0xff);
+    }
+  });
+  return Uint8Array.from(bytes);
+};
+
+const decodeP2wpkh = (address: string): Uint8Array => {
+  const lowerCaseAddress = address.toLowerCase();
+  const separator = lowerCaseAddress.lastIndexOf("1");
+  const hrp = lowerCaseAddress.slice(0, separator);
+  const data = Array.from(lowerCaseAddress.slice(separator + 1)).map((c) =>
+    BECH32_CHARSET.indexOf(c)
+  );
+
+  if (
+    data.includes(-1) ||
+    bech32Polymod([...bech32HrpExpand(hrp), ...data]) !== 1
+  ) {
+    throw new Error(`Invalid bech32 address ${address}`);
+  }
+
+  // witness version 0, followed by the program, followed by the 6 word checksum
+  const [witnessVersion, ...program] = data.slice(0, -6);
+  const pubkeyHash = fromWords(program);
+  if (witnessVersion !== 0 || pubkeyHash.length !== 20) {
+    throw new Error(`Address ${address} is not a P2WPKH address`);
+  }
+  return pubkeyHash;
+};
+
+const decodeP2pkh = (address: string): Uint8Array => {
+  const bytes = utils.bytes.bs58.decode(address);
+  const payload = bytes.slice(0, -4);
+  const checksum = sha256(sha256(payload)).slice(0, 4);
+  if (Buffer.compare(Buffer.from(checksum), Buffer.from(bytes.slice(-4)))) {
+    throw new Error(`Invalid base58check address ${address}`);
+  }
+  if (payload.length !== 21 || payload[0] !== P2PKH_VERSION) {
+    throw new Error(`Address ${address} is not a P2PKH address`);
+  }
+  return Uint8Array.from(payload.slice(1));
+};
+
+/**
+ * Decode a mainnet P2PKH (1...) or P2WPKH (bc1q...) address into its type and public key hash.
+ */
+export const decodeBtcAddress = (address: string): BtcAddress =>
+  address.toLowerCase().startsWith("bc1")
+    ? { type: BtcAddressType.P2wpkh, pubkeyHash: decodeP2wpkh(address) }
+    : { type: BtcAddressType.P2pkh, pubkeyHash: decodeP2pkh(address) };
+
+/**
+ * The (text) message that a Bitcoin address signs to register a DID in its registry.
+ *
+ * NOTE: This must stay in sync with btc_register_did_message in the program.
+ */
+export const btcRegisterDidMessage = (
+  registry: string,
+  did: string,
+  nonce: string
+): string => `did-registry:register_did:${registry}:${did}:${nonce}`;
+
+/**
+ * The (text) message that a Bitcoin address signs to remove a DID from its registry.
+ *
+ * NOTE: This must stay in sync with btc_remove_did_message in the program.
+ */
+export const btcRemoveDidMessage = (
+  registry: string,
+  did: string,
+  nonce: string
+): string => `did-registry:remove_did:${registry}:${did}:${nonce}`;
@@ -4,6 +4,51 @@ export const DID_REGISTRY_PROGRAM_ID = new PublicKey(
   "regUajGv87Pti6QRLeeRuQWrarQ1LmEyDXcAozko6Ax"
 );
 
+// The Civic gateway program, which issues gateway tokens (Civic Passes)
+export const GATEWAY_PROGRAM_ID = new PublicKey(
+  "gatem74V238djXdzWnJf94Wo1DcnuGkfijbf3AuBhfs"
+);
+
+export const PENDING_REMOVAL_SEED_PREFIX = "pending_removal";
+export const DID_SUCCESSION_SEED_PREFIX = "did_succession";
+export const REGISTRATION_INVITATION_SEED_PREFIX = "registration_invitation";
+export const REGISTRATION_INBOX_SEED_PREFIX = "registration_inbox";
+export const REGISTRY_HISTORY_SEED_PREFIX = "registry_history";
+export const PROGRAM_CONFIG_SEED_PREFIX = "config";
+export const MESSAGE_NONCE_SEED_PREFIX = "message_nonce";
+
+// NOTE: These must stay in sync with the message prefixes in the program.
+export const REGISTER_DID_MESSAGE_PREFIX = "did-registry:register_did";
+export const REMOVE_DID_MESSAGE_PREFIX = "did-registry:remove_did";
+export const RESIZE_MESSAGE_PREFIX = "did-registry:resize";
+export const CLOSE_MESSAGE_PREFIX = "did-registry:close";
+
+// NOTE: These must stay in sync with the passkey prefixes in the program.
+export const PASSKEY_REGISTER_DID_PREFIX = "did-registry:passkey:register_did";
+export const PASSKEY_REMOVE_DID_PREFIX = "did-registry:passkey:remove_did";
+export const PASSKEY_RESIZE_PREFIX = "did-registry:passkey:resize";
+export const PASSKEY_CLOSE_PREFIX = "did-registry:passkey:close";
+
+// NOTE: These must stay in sync with the chain account prefixes in the program.
+export const CHAIN_ACCOUNT_REGISTER_DID_PREFIX =
+  "did-registry:chain_account:register_did";
+export const CHAIN_ACCOUNT_REMOVE_DID_PREFIX =
+  "did-registry:chain_account:remove_did";
+
+// NOTE: This must stay in sync with KeyRegistry::MAX_NAMESPACE_LEN in the program.
+export const MAX_NAMESPACE_LENGTH = 32;
+
+// The space taken by each DID in a key registry: 32 bytes, plus 8 bytes for its expiry.
+// NOTE: This must stay in sync with KeyRegistry::DID_SIZE in the program.
+export const KEY_REGISTRY_DID_SIZE = 40;
+
+// The maximum removal delay of a registry, in slots (about 30 days).
+// NOTE: This must stay in sync with KeyRegistry::MAX_REMOVAL_DELAY in the program.
+export const MAX_REMOVAL_DELAY = 6_480_000;
+
+// NOTE: This must stay in sync with RentPayer::MAX_RENT_PAYERS in the program.
+export const MAX_RENT_PAYERS = 4;
+
 export const SPACE_BUFFER = 1; // increase registry size by this whenever resizing
 
 // Use this to pass a non-signing wallet into read-only registries
@@ -0,0 +1,101 @@
+import { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import BN from "bn.js";
+import { sha256 } from "@noble/hashes/sha256";
+
+export const SECP256R1_PROGRAM_ID = new PublicKey(
+  "Secp256r1SigVerify1111111111111111111111111"
+);
+
+// The order of the P-256 curve
+const P256_ORDER = new BN(
+  "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+  16
+);
+
+// The layout of the secp256r1 program instruction data (shared with the ed25519 program)
+const SIGNATURE_OFFSETS_START = 2;
+const SIGNATURE_OFFSETS_SIZE = 14;
+const DATA_START = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+const PUBLIC_KEY_SIZE = 33;
+const SIGNATURE_SIZE = 64;
+// an instruction index of u16::MAX means "this instruction"
+const CURRENT_INSTRUCTION = 0xffff;
+
+/**
+ * The WebAuthn challenge that a passkey signs to authorise an update to its registry.
+ *
+ * NOTE: This must stay in sync with passkey_challenge in the program.
+ */
+export const passkeyChallenge = (
+  prefix: string,
+  registry: PublicKey,
+  data: Uint8Array,
+  nonce: BN
+): Buffer =>
+  Buffer.from(
+    sha256(
+      Buffer.concat([
+        Buffer.from(prefix),
+        registry.toBuffer(),
+        Buffer.from(data),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ])
+    )
+  );
+
+export const toBase64Url = (data: Uint8Array): string =>
+  Buffer.from(data)
+    .toString("base64")
+    .replace(/\+/g, "-")
+    .replace(/\//g, "_")
+    .replace(/=+$/, "");
+
+/**
+ * Convert a DER-encoded P-256 signature, as returned by WebAuthn, into the 64-byte form
+ * expected by the secp256r1 program. The program only accepts signatures with a low S value.
+ */
+export const p256SignatureFromDer = (der: Uint8Array): Buffer => {
+  // SEQUENCE { INTEGER r, INTEGER s }
+  const rLength = der[3];
+  const r = new BN(der.slice(4, 4 + rLength));
+  const sLength = der[5 + rLength];
+  const s = new BN(der.slice(6 + rLength, 6 + rLength + sLength));
+  const lowS = s.gt(P256_ORDER.shrn(1)) ? P256_ORDER.sub(s) : s;
+
+  return Buffer.concat([
+    r.toArrayLike(Buffer, "be", 32),
+    lowS.toArrayLike(Buffer, "be", 32),
+  ]);
+};
+
+/**
+ * Create a secp256r1 program instruction verifying a signature of the message by the (compressed) public key
+ */
+export const createSecp256r1Instruction = (
+  publicKey: Uint8Array,
+  message: Uint8Array,
+  signature: Uint8Array
+): TransactionInstruction => {
+  const publicKeyOffset = DATA_START;
+  const signatureOffset = publicKeyOffset + PUBLIC_KEY_SIZE;
+  const messageOffset = signatureOffset + SIGNATURE_SIZE;
+
+  const data = Buffer.alloc(messageOffset + message.length);
+  data.writeUInt8(1, 0); // number of signatures
+  data.writeUInt16LE(signatureOffset, SIGNATURE_OFFSETS_START);
+  data.writeUInt16LE(CURRENT_INSTRUCTION, SIGNATURE_OFFSETS_START + 2);
+  data.writeUInt16LE(publicKeyOffset, SIGNATURE_OFFSETS_START + 4);
+  data.writeUInt16LE(CURRENT_INSTRUCTION, SIGNATURE_OFFSETS_START + 6);
+  data.writeUInt16LE(messageOffset, SIGNATURE_OFFSETS_START + 8);
+  data.writeUInt16LE(message.length, SIGNATURE_OFFSETS_START + 10);
+  data.writeUInt16LE(CURRENT_INSTRUCTION, SIGNATURE_OFFSETS_START + 12);
+  data.set(publicKey, publicKeyOffset);
+  data.set(signature, signatureOffset);
+  data.set(message, messageOffset);
+
+  return new TransactionInstruction({
+    keys: [],
+    programId: SECP256R1_PROGRAM_ID,
+    data,
+  });
+};
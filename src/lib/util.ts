@@ -1,8 +1,17 @@
 import { AnchorProvider, Program } from "@project-serum/anchor";
+import BN from "bn.js";
 import { DidRegistry, IDL } from "../types/did_registry";
-import { DID_REGISTRY_PROGRAM_ID } from "./constants";
-import { Wallet } from "../types";
-import { Connection } from "@solana/web3.js";
+import {
+  DID_REGISTRY_PROGRAM_ID,
+  DID_SUCCESSION_SEED_PREFIX,
+  GATEWAY_PROGRAM_ID,
+  MAX_NAMESPACE_LENGTH,
+  MESSAGE_NONCE_SEED_PREFIX,
+  PROGRAM_CONFIG_SEED_PREFIX,
+} from "./constants";
+import { Execution, Namespace, Wallet } from "../types";
+import { Connection, PublicKey } from "@solana/web3.js";
+import { BitwiseVerificationMethodFlag } from "@identity.com/sol-did-client";
 
 export const makeProgram = (
   connection: Connection,
@@ -15,6 +24,98 @@ export const makeProgram = (
   );
 };
 
+// Combine verification method flags into the bitmask stored on a registry
+export const flagsToBitmask = (
+  flags: BitwiseVerificationMethodFlag[]
+): number => flags.reduce((bitmask, flag) => bitmask | flag, 0);
+
+// The seed of a registry address for an application namespace, which is empty for the default registry
+export const namespaceSeed = (namespace: Namespace): Buffer => {
+  const seed = Buffer.from(namespace);
+  if (seed.length > MAX_NAMESPACE_LENGTH) {
+    throw new Error(
+      `A namespace can be at most ${MAX_NAMESPACE_LENGTH} bytes long`
+    );
+  }
+  return seed;
+};
+
+// The address of the record of the successor of a DID
+export const getSuccessionAddress = (did: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(DID_SUCCESSION_SEED_PREFIX), did.toBuffer()],
+    DID_REGISTRY_PROGRAM_ID
+  )[0];
+
+// The address of the program config, which may not have been initialised
+export const getConfigAddress = (): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED_PREFIX)],
+    DID_REGISTRY_PROGRAM_ID
+  )[0];
+
+// The accounts of the program config, for instructions that are blocked while the program is paused
+export const configAccounts = () => ({ config: getConfigAddress() });
+
+// The address of the nonce of the messages authorising updates to a registry, which survives closing it
+export const getMessageNonceAddress = (registry: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(MESSAGE_NONCE_SEED_PREFIX), registry.toBuffer()],
+    DID_REGISTRY_PROGRAM_ID
+  )[0];
+
+// The nonce to include in the next message authorising an update to a registry
+export const fetchMessageNonce = async (
+  program: Program<DidRegistry>,
+  registry: PublicKey
+): Promise<BN> => {
+  const messageNonce = await program.account.messageNonce.fetchNullable(
+    getMessageNonceAddress(registry)
+  );
+  return messageNonce ? messageNonce.nonce : new BN(0);
+};
+
+// The address of the default gateway token (Civic Pass) of the owner in the gatekeeper network
+export const getGatewayTokenAddress = (
+  owner: PublicKey,
+  gatekeeperNetwork: PublicKey
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [
+      owner.toBuffer(),
+      Buffer.from("gateway"),
+      Buffer.alloc(8),
+      gatekeeperNetwork.toBuffer(),
+    ],
+    GATEWAY_PROGRAM_ID
+  )[0];
+
+// The gateway token to pass when registering a DID: the owner's gateway token, if the program config
+// names a gatekeeper network, or otherwise the program, which is ignored
+export const fetchGatewayTokenAccount = async (
+  program: Program<DidRegistry>,
+  owner: PublicKey
+): Promise<PublicKey> => {
+  const config = await program.account.programConfig.fetchNullable(
+    getConfigAddress()
+  );
+  if (!config || config.gatekeeperNetwork.equals(PublicKey.default)) {
+    return DID_REGISTRY_PROGRAM_ID;
+  }
+
+  return getGatewayTokenAddress(owner, config.gatekeeperNetwork);
+};
+
+// Build an execution whose instruction depends on on-chain state, e.g. whether a registry has a history,
+// without making the caller await it before calling rpc, transaction or instruction
+export const deferredExecution = (
+  build: () => Promise<Execution>
+): Execution => ({
+  rpc: () => build().then((execution) => execution.rpc()),
+  transaction: () => build().then((execution) => execution.transaction()),
+  instruction: () => build().then((execution) => execution.instruction()),
+});
+
 export const confirm = async (
   connection: Connection,
   txSig: string
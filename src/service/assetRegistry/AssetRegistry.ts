@@ -0,0 +1,211 @@
+import {
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { utils } from "@project-serum/anchor";
+import {
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
+import { CommonRegistry } from "../../lib/CommonRegistry";
+import { MAX_RENT_PAYERS, SPACE_BUFFER } from "../../lib/constants";
+import {
+  configAccounts,
+  fetchGatewayTokenAccount,
+  makeProgram,
+} from "../../lib/util";
+import { AssetRegistryEntry, Execution, Wallet } from "../../types";
+
+export const ASSET_REGISTRY_SEED_PREFIX = "asset_registry";
+
+/**
+ * A registry of DIDs linked to an asset (an NFT), identified by its mint.
+ *
+ * Updates are made by the wallet, which must hold the asset. By default, the asset is expected
+ * in the wallet's associated token account.
+ */
+export class AssetRegistry extends CommonRegistry {
+  protected constructor(
+    protected wallet: Wallet,
+    connection: Connection,
+    protected mint: PublicKey,
+    cluster: ExtendedCluster,
+    protected tokenAccount: PublicKey
+  ) {
+    super(mint.toBytes(), ASSET_REGISTRY_SEED_PREFIX, cluster);
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Given a did count, calculate the size in bytes of the registry required to store it.
+   *
+   * NOTE: This must stay in sync with AssetRegistry::calculate_size in the program.
+   */
+  protected static calculateMaxCount(sizeInBytes: number): number {
+    const entrySpace =
+      sizeInBytes -
+      8 - // discriminator
+      1 - // version
+      32 - // mint
+      4 - // vec length field
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
+    return Math.floor(entrySpace / 64);
+  }
+
+  public async analyseSpace(): Promise<{
+    count: number;
+    maxCount: number;
+    sizeBytes: number;
+  }> {
+    const registryAccountInfo =
+      await this.program.account.assetRegistry.getAccountInfo(
+        this.registryAddress
+      );
+
+    if (!registryAccountInfo) return { count: 0, maxCount: 0, sizeBytes: 0 };
+
+    const sizeBytes = registryAccountInfo.data.length;
+
+    const coder = this.program.account.assetRegistry.coder.accounts;
+    const account = coder.decode("assetRegistry", registryAccountInfo.data);
+    const didCount = account.entries.length;
+    const maxCount = AssetRegistry.calculateMaxCount(sizeBytes);
+
+    return { count: didCount, maxCount, sizeBytes };
+  }
+
+  /**
+   * List the DIDs registered against the asset, along with the holders that registered them
+   */
+  async listEntries(): Promise<AssetRegistryEntry[]> {
+    const registryAccount =
+      await this.program.account.assetRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    if (!registryAccount) return [];
+
+    return registryAccount.entries.map(
+      (entry: { did: PublicKey; holder: PublicKey }) => ({
+        did: DidSolIdentifier.create(entry.did, this.cluster).toString(),
+        holder: entry.holder,
+      })
+    );
+  }
+
+  async listDIDs(): Promise<string[]> {
+    const entries = await this.listEntries();
+    return entries.map((entry) => entry.did);
+  }
+
+  protected async resizeInstructionIfNeeded(): Promise<TransactionInstruction | null> {
+    const space = await this.analyseSpace();
+
+    // If we have space, don't resize
+    // Also, don't resize if maxCount is at 0, as this indicates that the registry is not yet initialised
+    if (space.count < space.maxCount || space.maxCount === 0) return null;
+
+    return this.program.methods
+      .resizeAssetRegistry(space.count + SPACE_BUFFER)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+      })
+      .instruction();
+  }
+
+  /**
+   * Register a DID against the asset.
+   * The wallet must be an authority on the DID, either directly, or through the given chain of controller DIDs.
+   */
+  async register(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const resizeInstruction = await this.resizeInstructionIfNeeded();
+
+    return this.program.methods
+      .registerDidForAsset(account.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        holder: this.wallet.publicKey,
+        mint: this.mint,
+        tokenAccount: this.tokenAccount,
+        did: account.authority,
+        didAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain))
+      .preInstructions(resizeInstruction ? [resizeInstruction] : []);
+  }
+
+  /**
+   * Remove a DID from the asset's registry.
+   * The wallet must be an authority on the DID, either directly, or through the given chain of controller DIDs.
+   */
+  remove(did: string, controllerChain: string[] = []): Execution {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .removeDidForAsset(account.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        holder: this.wallet.publicKey,
+        tokenAccount: this.tokenAccount,
+        did: account.authority,
+        didAccount: account.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Remove all DIDs registered by previous holders of the asset, e.g. after it has been transferred to the wallet.
+   */
+  clearPreviousHolders(): Execution {
+    return this.program.methods.clearPreviousAssetHolderDids().accounts({
+      config: configAccounts(),
+      registry: this.registryAddress,
+      holder: this.wallet.publicKey,
+      tokenAccount: this.tokenAccount,
+    });
+  }
+
+  /**
+   * Grow the registry to fit the given number of DIDs
+   */
+  resize(didCount: number): Execution {
+    return this.program.methods.resizeAssetRegistry(didCount).accounts({
+      config: configAccounts(),
+      registry: this.registryAddress,
+      payer: this.wallet.publicKey,
+    });
+  }
+
+  /**
+   * @param mint The mint of the asset
+   * @param wallet The holder of the asset, which submits, and pays for, the registry updates
+   * @param tokenAccount The token account holding the asset (defaults to the wallet's associated token account)
+   */
+  static for(
+    mint: PublicKey,
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta",
+    tokenAccount: PublicKey = utils.token.associatedAddress({
+      mint,
+      owner: wallet.publicKey,
+    })
+  ): AssetRegistry {
+    return new AssetRegistry(wallet, connection, mint, cluster, tokenAccount);
+  }
+}
@@ -0,0 +1,218 @@
+import {
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
+import BN from "bn.js";
+import { sha256 } from "@noble/hashes/sha256";
+import { CommonRegistry } from "../../lib/CommonRegistry";
+import {
+  CHAIN_ACCOUNT_REGISTER_DID_PREFIX,
+  CHAIN_ACCOUNT_REMOVE_DID_PREFIX,
+  MAX_RENT_PAYERS,
+  SPACE_BUFFER,
+} from "../../lib/constants";
+import { configAccounts, makeProgram } from "../../lib/util";
+import { Execution, Wallet } from "../../types";
+
+export const CHAIN_ACCOUNT_REGISTRY_SEED_PREFIX = "chain_account_registry";
+
+/**
+ * A registry of DIDs for an account on another chain, identified by its CAIP-10 account id,
+ * e.g. eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb
+ *
+ * Updates are authorised by the chain account signing a message (see the *Message functions)
+ * in the format of its namespace. For eip155 accounts, this is an Ethereum personal_sign signature.
+ * The wallet submits, and pays for, the transactions.
+ */
+export class ChainAccountRegistry extends CommonRegistry {
+  protected constructor(
+    protected wallet: Wallet,
+    connection: Connection,
+    protected accountId: string,
+    cluster: ExtendedCluster
+  ) {
+    // the registry is seeded by the hash of the account id, as the id may be too long to be a seed
+    super(sha256(accountId), CHAIN_ACCOUNT_REGISTRY_SEED_PREFIX, cluster);
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Given a did count, calculate the size in bytes of the registry required to store it.
+   *
+   * NOTE: This must stay in sync with ChainAccountRegistry::calculate_size in the program.
+   */
+  protected calculateMaxCount(sizeInBytes: number): number {
+    const didSpace =
+      sizeInBytes -
+      8 - // discriminator
+      1 - // version
+      4 * 3 - // namespace, reference and address length fields
+      this.accountId.length - // namespace, reference and address
+      8 - // nonce
+      4 - // vec length field
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
+    return Math.floor(didSpace / 32);
+  }
+
+  public async analyseSpace(): Promise<{
+    count: number;
+    maxCount: number;
+    sizeBytes: number;
+  }> {
+    const registryAccountInfo =
+      await this.program.account.chainAccountRegistry.getAccountInfo(
+        this.registryAddress
+      );
+
+    if (!registryAccountInfo) return { count: 0, maxCount: 0, sizeBytes: 0 };
+
+    const sizeBytes = registryAccountInfo.data.length;
+
+    const coder = this.program.account.chainAccountRegistry.coder.accounts;
+    const account = coder.decode(
+      "chainAccountRegistry",
+      registryAccountInfo.data
+    );
+    const didCount = account.dids.length;
+    const maxCount = this.calculateMaxCount(sizeBytes);
+
+    return { count: didCount, maxCount, sizeBytes };
+  }
+
+  async listDIDs(): Promise<string[]> {
+    const registryAccount =
+      await this.program.account.chainAccountRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    if (!registryAccount) return [];
+
+    return registryAccount.dids.map((identifier: PublicKey) =>
+      DidSolIdentifier.create(identifier, this.cluster).toString()
+    );
+  }
+
+  /**
+   * Build a message that the chain account must sign to authorise an update to its registry.
+   * The message ends with the registry nonce, so it can only be used once.
+   *
+   * NOTE: This must stay in sync with chain_account_message in the program.
+   */
+  private async message(prefix: string, did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    const registryAccount =
+      await this.program.account.chainAccountRegistry.fetchNullable(
+        this.registryAddress
+      );
+    const nonce: BN = registryAccount ? registryAccount.nonce : new BN(0);
+
+    return Buffer.concat([
+      Buffer.from(prefix),
+      this.registryAddress.toBuffer(),
+      account.authority.toBuffer(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ]);
+  }
+
+  /**
+   * Build the message that the chain account must sign to authorise registering a DID
+   */
+  registrationMessage(did: string): Promise<Buffer> {
+    return this.message(CHAIN_ACCOUNT_REGISTER_DID_PREFIX, did);
+  }
+
+  /**
+   * Build the message that the chain account must sign to authorise removing a DID
+   */
+  removalMessage(did: string): Promise<Buffer> {
+    return this.message(CHAIN_ACCOUNT_REMOVE_DID_PREFIX, did);
+  }
+
+  protected async resizeInstructionIfNeeded(): Promise<TransactionInstruction | null> {
+    const space = await this.analyseSpace();
+
+    // If we have space, don't resize
+    // Also, don't resize if maxCount is at 0, as this indicates that the registry is not yet initialised
+    if (space.count < space.maxCount || space.maxCount === 0) return null;
+
+    return this.program.methods
+      .resizeChainAccountRegistry(space.count + SPACE_BUFFER)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+      })
+      .instruction();
+  }
+
+  /**
+   * Register a DID against the chain account, using its signature of the registrationMessage.
+   * The chain account must be an authority on the DID.
+   */
+  async register(did: string, signature: Uint8Array): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const resizeInstruction = await this.resizeInstructionIfNeeded();
+
+    return this.program.methods
+      .registerDidForChainAccount(
+        this.accountId,
+        Buffer.from(signature),
+        account.bump
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        did: account.authority,
+        didAccount: account.account,
+      })
+      .preInstructions(resizeInstruction ? [resizeInstruction] : []);
+  }
+
+  /**
+   * Remove a DID, using the chain account's signature of the removalMessage
+   */
+  remove(did: string, signature: Uint8Array): Execution {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .removeDidForChainAccount(Buffer.from(signature))
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        did: account.authority,
+      });
+  }
+
+  /**
+   * Grow the registry to fit the given number of DIDs
+   */
+  resize(didCount: number): Execution {
+    return this.program.methods
+      .resizeChainAccountRegistry(didCount)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * @param accountId The CAIP-10 account id. eip155 addresses must be lowercase.
+   * @param wallet Submits, and pays for, the registry updates
+   */
+  static forAccountId(
+    accountId: string,
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta"
+  ): ChainAccountRegistry {
+    return new ChainAccountRegistry(wallet, connection, accountId, cluster);
+  }
+}
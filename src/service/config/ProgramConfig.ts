@@ -0,0 +1,149 @@
+import { Program } from "@project-serum/anchor";
+import { Connection, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { DidRegistry } from "../../types/did_registry";
+import { DID_REGISTRY_PROGRAM_ID } from "../../lib/constants";
+import { getConfigAddress, makeProgram } from "../../lib/util";
+import { Execution, ProgramConfigState, Wallet } from "../../types";
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+/**
+ * Manage the program-wide config: its admin, the emergency pause switch, the registry limits and fee,
+ * and the gatekeeper network for gateway token gating.
+ * Until the config is initialised, nothing is paused and there are no limits or fees.
+ */
+export class ProgramConfig {
+  private program: Program<DidRegistry>;
+
+  private constructor(private wallet: Wallet, connection: Connection) {
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Initialise the config, making the wallet its admin.
+   * The wallet must be the upgrade authority of the program.
+   */
+  initialize(
+    initialCapacity: number,
+    maxEntries = 0,
+    registryFee = 0
+  ): Execution {
+    const [programData] = PublicKey.findProgramAddressSync(
+      [DID_REGISTRY_PROGRAM_ID.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    );
+
+    return this.program.methods
+      .initializeConfig(initialCapacity, maxEntries, new BN(registryFee))
+      .accounts({
+        config: getConfigAddress(),
+        admin: this.wallet.publicKey,
+        program: DID_REGISTRY_PROGRAM_ID,
+        programData,
+      });
+  }
+
+  /**
+   * Pause or unpause the program. While paused, registries can be closed but not changed.
+   */
+  setPaused(paused: boolean): Execution {
+    return this.program.methods.setPaused(paused).accounts({
+      config: getConfigAddress(),
+      admin: this.wallet.publicKey,
+    });
+  }
+
+  /**
+   * Set the capacity of new registries, the maximum number of DIDs in a registry (0 = no maximum),
+   * and the fee in lamports for creating a registry (0 = no fee)
+   */
+  setLimits(
+    initialCapacity: number,
+    maxEntries: number,
+    registryFee: number
+  ): Execution {
+    return this.program.methods
+      .setConfigLimits(initialCapacity, maxEntries, new BN(registryFee))
+      .accounts({
+        config: getConfigAddress(),
+        admin: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * Propose a new admin, which takes over once it accepts (see acceptAdmin).
+   * Proposing the default public key cancels a proposal.
+   */
+  proposeAdmin(newAdmin: PublicKey): Execution {
+    return this.program.methods.proposeAdmin(newAdmin).accounts({
+      config: getConfigAddress(),
+      admin: this.wallet.publicKey,
+    });
+  }
+
+  /**
+   * Accept a proposal to make the wallet the admin
+   */
+  acceptAdmin(): Execution {
+    return this.program.methods.acceptAdmin().accounts({
+      config: getConfigAddress(),
+      pendingAdmin: this.wallet.publicKey,
+    });
+  }
+
+  /**
+   * Withdraw the registry fees collected in the config to the recipient (default: the wallet)
+   */
+  withdrawFees(recipient: PublicKey = this.wallet.publicKey): Execution {
+    return this.program.methods.withdrawFees().accounts({
+      config: getConfigAddress(),
+      admin: this.wallet.publicKey,
+      recipient,
+    });
+  }
+
+  /**
+   * Require authorities registering DIDs with register (key and controller registries) to hold
+   * a gateway token (Civic Pass) in the gatekeeper network. Pass null to remove the requirement.
+   */
+  setGatekeeperNetwork(gatekeeperNetwork: PublicKey | null): Execution {
+    return this.program.methods
+      .setGatekeeperNetwork(gatekeeperNetwork ?? PublicKey.default)
+      .accounts({
+        config: getConfigAddress(),
+        admin: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * The current config, or null if it has not been initialised (i.e. the defaults apply)
+   */
+  async fetch(): Promise<ProgramConfigState | null> {
+    const config = await this.program.account.programConfig.fetchNullable(
+      getConfigAddress()
+    );
+
+    if (!config) return null;
+
+    return {
+      admin: config.admin,
+      pendingAdmin: config.pendingAdmin.equals(PublicKey.default)
+        ? null
+        : config.pendingAdmin,
+      paused: config.paused,
+      initialCapacity: config.initialCapacity,
+      maxEntries: config.maxEntries,
+      registryFee: config.registryFee.toNumber(),
+      gatekeeperNetwork: config.gatekeeperNetwork.equals(PublicKey.default)
+        ? null
+        : config.gatekeeperNetwork,
+    };
+  }
+
+  static for(wallet: Wallet, connection: Connection) {
+    return new ProgramConfig(wallet, connection);
+  }
+}
@@ -4,7 +4,8 @@ import {
   ExtendedCluster,
 } from "@identity.com/sol-did-client";
 import { CommonRegistry } from "../../lib/CommonRegistry";
-import { DidAccount } from "../../types";
+import { MAX_NAMESPACE_LENGTH, MAX_RENT_PAYERS } from "../../lib/constants";
+import { DidAccount, Namespace, RentPayer } from "../../types";
 
 export const CONTROLLER_REGISTRY_SEED_PREFIX = "controller_registry";
 
@@ -12,9 +13,10 @@ export abstract class AbstractControllerRegistry extends CommonRegistry {
   protected constructor(
     address: Uint8Array,
     seedPrefix: string,
-    cluster: ExtendedCluster
+    cluster: ExtendedCluster,
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
   }
 
   protected didAddressToAccount(): DidAccount {
@@ -41,7 +43,14 @@ export abstract class AbstractControllerRegistry extends CommonRegistry {
       8 - // discriminator
       1 - // version
       32 - // key
-      4; // vec length field
+      4 - // vec length field
+      8 - // removal delay
+      2 - // required flags
+      1 - // has history
+      4 - // namespace length field
+      MAX_NAMESPACE_LENGTH - // namespace
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
     return Math.floor(didSpace / 32);
   }
 
@@ -86,4 +95,16 @@ export abstract class AbstractControllerRegistry extends CommonRegistry {
       DidSolIdentifier.create(identifier, this.cluster).toString()
     );
   }
+
+  /**
+   * List the parties that paid rent for the registry, and the lamports each is refunded
+   */
+  async listRentPayers(): Promise<RentPayer[]> {
+    const registryAccount =
+      await this.program.account.controllerRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    return this.toRentPayers(registryAccount?.rentPayers ?? []);
+  }
 }
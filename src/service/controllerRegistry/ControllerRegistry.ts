@@ -1,11 +1,25 @@
-import { Connection, PublicKey, TransactionInstruction } from "@solana/web3.js";
 import {
+  AccountMeta,
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  BitwiseVerificationMethodFlag,
   DidSolIdentifier,
   ExtendedCluster,
 } from "@identity.com/sol-did-client";
 import { SPACE_BUFFER } from "../../lib/constants";
-import { Execution, Wallet } from "../../types";
-import { makeProgram } from "../../lib/util";
+import { Execution, Namespace, Wallet } from "../../types";
+import {
+  configAccounts,
+  deferredExecution,
+  fetchGatewayTokenAccount,
+  flagsToBitmask,
+  getSuccessionAddress,
+  makeProgram,
+} from "../../lib/util";
 import {
   AbstractControllerRegistry,
   CONTROLLER_REGISTRY_SEED_PREFIX,
@@ -14,6 +28,8 @@ import {
 export class ControllerRegistry extends AbstractControllerRegistry {
   // Pays for registry updates (defaults to the authority)
   protected payer: PublicKey;
+  // Controller DIDs linking the wallet to the registry DID, if the wallet is not a direct authority on it
+  protected controllerChain: string[];
 
   protected constructor(
     protected wallet: Wallet,
@@ -21,11 +37,14 @@ export class ControllerRegistry extends AbstractControllerRegistry {
     address: Uint8Array,
     seedPrefix: string,
     cluster: ExtendedCluster,
-    payer?: PublicKey
+    payer?: PublicKey,
+    controllerChain: string[] = [],
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
     this.program = makeProgram(connection, wallet);
     this.payer = payer || wallet.publicKey;
+    this.controllerChain = controllerChain;
   }
 
   protected async initInstructionIfNeeded(): Promise<TransactionInstruction | null> {
@@ -39,14 +58,20 @@ export class ControllerRegistry extends AbstractControllerRegistry {
     const account = this.didAddressToAccount();
 
     return this.program.methods
-      .createControllerRegistry(this.registryBump, account.bump)
+      .createControllerRegistry(
+        this.registryBump,
+        account.bump,
+        this.namespace
+      )
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         authority: this.wallet.publicKey,
         payer: this.payer,
         did: account.authority,
         didAccount: account.account,
       })
+      .remainingAccounts(this.controllerChainToAccounts(this.controllerChain))
       .instruction();
   }
 
@@ -57,14 +82,16 @@ export class ControllerRegistry extends AbstractControllerRegistry {
     // (indicating the account is not yet initialised)
     if (space.count < space.maxCount || space.maxCount === 0) return null;
 
-    return this.program.methods
-      .resizeControllerRegistry(space.count + SPACE_BUFFER)
-      .accounts({
-        registry: this.registryAddress,
-        authority: this.wallet.publicKey,
-        payer: this.payer,
-      })
-      .instruction();
+    return this.resize(space.count + SPACE_BUFFER).instruction();
+  }
+
+  // the history of the registry, if it has one, which must be passed when adding or removing DIDs
+  protected async fetchHistoryAccounts(): Promise<AccountMeta[]> {
+    const registryAccount =
+      await this.program.account.controllerRegistry.fetchNullable(
+        this.registryAddress
+      );
+    return this.historyAccounts(registryAccount?.hasHistory ?? false);
   }
 
   async register(did: string): Promise<Execution> {
@@ -81,23 +108,41 @@ export class ControllerRegistry extends AbstractControllerRegistry {
     return this.program.methods
       .registerControlledDid(didAccount.bump, controlledDidAccount.bump)
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         authority: this.wallet.publicKey,
         controlledDid: controlledDidAccount.authority,
         controlledDidAccount: controlledDidAccount.account,
         didAccount: didAccount.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
       })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(this.controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ])
       .preInstructions(preInstructions);
   }
 
   removePubkey(did: PublicKey): Execution {
     const didAccount = this.didAddressToAccount();
-    return this.program.methods.removeControlledDid(didAccount.bump).accounts({
-      registry: this.registryAddress,
-      authority: this.wallet.publicKey,
-      didToRemove: did,
-      didAccount: didAccount.account,
-    });
+    return deferredExecution(async () =>
+      this.program.methods
+        .removeControlledDid(didAccount.bump)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          didToRemove: did,
+          didAccount: didAccount.account,
+        })
+        .remainingAccounts([
+          ...this.controllerChainToAccounts(this.controllerChain),
+          ...(await this.fetchHistoryAccounts()),
+        ])
+    );
   }
 
   remove(did: string): Execution {
@@ -105,37 +150,383 @@ export class ControllerRegistry extends AbstractControllerRegistry {
     return this.removePubkey(account.authority);
   }
 
-  resize(did_count: number): Execution {
-    return this.program.methods.resizeControllerRegistry(did_count).accounts({
-      registry: this.registryAddress,
-      authority: this.wallet.publicKey,
+  /**
+   * Add a controlled DID to this registry, where the wallet is an authority on the controlled DID
+   * rather than on the registry DID. The controlled DID must list the registry DID as a controller.
+   * The registry must already exist.
+   */
+  selfRegister(
+    controlledDid: string,
+    controllerChain: string[] = []
+  ): Execution {
+    const controlledDidAccount = this.didToAccount(controlledDid);
+    const didAccount = this.didAddressToAccount();
+
+    return deferredExecution(async () =>
+      this.program.methods
+        .selfRegisterControlledDid(didAccount.bump, controlledDidAccount.bump)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          controlledDid: controlledDidAccount.authority,
+          controlledDidAccount: controlledDidAccount.account,
+          didAccount: didAccount.account,
+          gatewayToken: await fetchGatewayTokenAccount(
+            this.program,
+            this.wallet.publicKey
+          ),
+        })
+        .remainingAccounts([
+          ...this.controllerChainToAccounts(controllerChain),
+          ...(await this.fetchHistoryAccounts()),
+        ])
+    );
+  }
+
+  /**
+   * Remove a controlled DID from this registry, where the wallet is an authority on the controlled DID.
+   * If the registry has a removal delay, use selfRequestRemoval instead.
+   */
+  selfRemove(
+    controlledDid: string,
+    controllerChain: string[] = []
+  ): Execution {
+    const controlledDidAccount = this.didToAccount(controlledDid);
+
+    return deferredExecution(async () =>
+      this.program.methods
+        .selfRemoveControlledDid(controlledDidAccount.bump)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          controlledDid: controlledDidAccount.authority,
+          controlledDidAccount: controlledDidAccount.account,
+        })
+        .remainingAccounts([
+          ...this.controllerChainToAccounts(controllerChain),
+          ...(await this.fetchHistoryAccounts()),
+        ])
+    );
+  }
+
+  /**
+   * Replace a superseded DID with its successor, which must be controlled by the registry DID.
+   * The successor is read from the succession record of the DID (see DidSuccession).
+   * If the registry has a removal delay, the removal of the DID must have been requested, and the delay elapsed.
+   */
+  async replace(did: string): Promise<Execution> {
+    const succession = getSuccessionAddress(this.didToAccount(did).authority);
+    const { successor } = await this.program.account.didSuccession.fetch(
+      succession
+    );
+    const [successorAccount, successorBump] =
+      DidSolIdentifier.create(successor, this.cluster).dataAccount();
+    const didAccount = this.didAddressToAccount();
+
+    return this.program.methods
+      .replaceControlledDid(didAccount.bump, successorBump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        succession,
+        ...(await this.pendingRemovalAccounts(
+          this.didToAccount(did).authority,
+          this.wallet.publicKey
+        )),
+        successorAccount,
+        didAccount: didAccount.account,
+      })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(this.controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ]);
+  }
+
+  /**
+   * Remove DIDs that are no longer controlled by the registry DID.
+   * Anyone can prune a registry, so the wallet need not be an authority on any DID.
+   */
+  prune(dids: string[]): Execution {
+    const didAccount = this.didAddressToAccount();
+    const controlledDidAccounts = dids.flatMap((did) => {
+      const account = this.didToAccount(did);
+      return [
+        { pubkey: account.authority, isSigner: false, isWritable: false },
+        { pubkey: account.account, isSigner: false, isWritable: false },
+      ];
+    });
+
+    return deferredExecution(async () =>
+      this.program.methods
+        .pruneControlledDid(didAccount.bump)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          didAccount: didAccount.account,
+        })
+        .remainingAccounts([
+          ...controlledDidAccounts,
+          ...(await this.fetchHistoryAccounts()),
+        ])
+    );
+  }
+
+  /**
+   * Require removals from this registry to wait for the given number of slots before they can be finalised.
+   * The delay can only be increased, up to MAX_REMOVAL_DELAY (about 30 days).
+   */
+  setRemovalDelay(slots: number): Execution {
+    const didAccount = this.didAddressToAccount();
+    return this.program.methods
+      .setControllerRegistryRemovalDelay(new BN(slots), didAccount.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        didAccount: didAccount.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(this.controllerChain));
+  }
+
+  /**
+   * Require the wallet to have the given verification method flags on the registry DID in order to register DIDs
+   */
+  setRequiredFlags(flags: BitwiseVerificationMethodFlag[]): Execution {
+    const didAccount = this.didAddressToAccount();
+    return this.program.methods
+      .setControllerRegistryRequiredFlags(
+        flagsToBitmask(flags),
+        didAccount.bump
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        didAccount: didAccount.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(this.controllerChain));
+  }
+
+  /**
+   * Queue the removal of a controlled DID from a registry with a removal delay
+   */
+  requestRemoval(did: string): Execution {
+    const account = this.didToAccount(did);
+    const didAccount = this.didAddressToAccount();
+    return this.program.methods
+      .requestControlledDidRemoval(didAccount.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        pendingRemoval: this.getPendingRemovalAddress(account.authority),
+        payer: this.payer,
+        authority: this.wallet.publicKey,
+        didToRemove: account.authority,
+        didAccount: didAccount.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(this.controllerChain));
+  }
+
+  /**
+   * Request the removal of a controlled DID from a registry with a removal delay,
+   * where the wallet is an authority on the controlled DID.
+   * Any authority on the controlled DID can cancel the removal until the delay has elapsed.
+   */
+  selfRequestRemoval(
+    controlledDid: string,
+    controllerChain: string[] = []
+  ): Execution {
+    const controlledDidAccount = this.didToAccount(controlledDid);
+    return this.program.methods
+      .selfRequestControlledDidRemoval(controlledDidAccount.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        pendingRemoval: this.getPendingRemovalAddress(
+          controlledDidAccount.authority
+        ),
+        payer: this.payer,
+        authority: this.wallet.publicKey,
+        controlledDid: controlledDidAccount.authority,
+        controlledDidAccount: controlledDidAccount.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Remove a controlled DID whose removal was requested, once the removal delay has elapsed
+   */
+  async finalizeRemoval(did: string): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const pendingRemoval = this.getPendingRemovalAddress(account.authority);
+
+    return this.program.methods
+      .finalizeControlledDidRemoval()
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        pendingRemoval,
+        payer: await this.pendingRemovalPayer(pendingRemoval),
+      })
+      .remainingAccounts(await this.fetchHistoryAccounts());
+  }
+
+  /**
+   * Cancel a pending removal of a controlled DID. The authority must be an authority on the controlled DID.
+   */
+  async cancelRemoval(
+    did: string,
+    authority: PublicKey = this.wallet.publicKey,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const pendingRemoval = this.getPendingRemovalAddress(account.authority);
+
+    return this.program.methods
+      .cancelRemoval(account.bump)
+      .accounts({
+        config: configAccounts(),
+        pendingRemoval,
+        payer: await this.pendingRemovalPayer(pendingRemoval),
+        authority,
+        did: account.authority,
+        didAccount: account.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Start recording the DIDs added to and removed from the registry, keeping up to the given number of entries.
+   * The registry must already exist. If it had a history before (i.e. it was closed and created again),
+   * that history is reused at its current capacity.
+   */
+  createHistory(capacity: number): Execution {
+    const didAccount = this.didAddressToAccount();
+    return deferredExecution(async () =>
+      this.program.methods
+        .createControllerRegistryHistory(
+          await this.historyCapacity(capacity),
+          didAccount.bump
+        )
+        .accounts({
+          config: configAccounts(),
+          history: this.getHistoryAddress(),
+          registry: this.registryAddress,
+          payer: this.payer,
+          authority: this.wallet.publicKey,
+          didAccount: didAccount.account,
+        })
+        .remainingAccounts(
+          this.controllerChainToAccounts(this.controllerChain)
+        )
+    );
+  }
+
+  /**
+   * Increase the number of entries kept in the registry's history. Anyone can pay to grow a history.
+   */
+  resizeHistory(capacity: number): Execution {
+    return this.program.methods.resizeRegistryHistory(capacity).accounts({
+      config: configAccounts(),
+      history: this.getHistoryAddress(),
       payer: this.payer,
     });
   }
 
-  close(): Execution {
+  /**
+   * Resize the registry. The payer pays for any extra rent. If the registry shrinks,
+   * the rent freed is refunded to the parties that paid for it, unless they are in cosigners (see close).
+   */
+  resize(did_count: number, cosigners: PublicKey[] = []): Execution {
+    const didAccount = this.didAddressToAccount();
+    return deferredExecution(async () =>
+      this.program.methods
+        .resizeControllerRegistry(did_count, didAccount.bump)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          payer: this.payer,
+          didAccount: didAccount.account,
+        })
+        .remainingAccounts([
+          ...this.controllerChainToAccounts(this.controllerChain),
+          ...this.rentPayerAccounts(
+            await this.listRentPayers(),
+            this.payer,
+            cosigners
+          ),
+        ])
+    );
+  }
+
+  /**
+   * Close the registry. Its rent is refunded to the parties that paid for it, and anything left over
+   * is paid to the payer. Parties in cosigners waive their refunds in favour of the payer,
+   * and must sign the transaction.
+   */
+  close(cosigners: PublicKey[] = []): Execution {
     const account = this.didAddressToAccount();
-    return this.program.methods.closeControllerRegistry(account.bump).accounts({
+    return deferredExecution(async () =>
+      this.program.methods
+        .closeControllerRegistry(account.bump)
+        .accounts({
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          payer: this.payer,
+          did: account.authority,
+          didAccount: account.account,
+        })
+        .remainingAccounts([
+          ...this.controllerChainToAccounts(this.controllerChain),
+          ...(await this.fetchHistoryAccounts()),
+          ...this.rentPayerAccounts(
+            await this.listRentPayers(),
+            this.payer,
+            cosigners
+          ),
+        ])
+    );
+  }
+
+  /**
+   * Migrate a registry created by the first release of the program that was filled, and so cannot be loaded
+   * until the fields added since are appended. The payer pays the rent for the extra space.
+   * Anyone can migrate a registry.
+   */
+  migrate(): Execution {
+    return this.program.methods.migrateControllerRegistry().accounts({
+      config: configAccounts(),
       registry: this.registryAddress,
-      authority: this.wallet.publicKey,
       payer: this.payer,
-      did: account.authority,
-      didAccount: account.account,
     });
   }
 
+  /**
+   * Create a controller registry client for a DID.
+   * If the wallet is not a direct authority on the DID, pass the chain of controller DIDs
+   * that links the wallet to the DID, starting with the DID's controller.
+   */
   static for(
     wallet: Wallet,
     did: string,
     connection: Connection,
-    cluster: ExtendedCluster = "mainnet-beta"
+    cluster: ExtendedCluster = "mainnet-beta",
+    controllerChain: string[] = [],
+    namespace?: Namespace
   ) {
     return new ControllerRegistry(
       wallet,
       connection,
       DidSolIdentifier.parse(did).authority.toBuffer(),
       CONTROLLER_REGISTRY_SEED_PREFIX,
-      cluster
+      cluster,
+      undefined,
+      controllerChain,
+      namespace
     );
   }
 }
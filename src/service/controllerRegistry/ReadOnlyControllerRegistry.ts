@@ -5,6 +5,7 @@ import {
 } from "@identity.com/sol-did-client";
 import { dummyAuthority } from "../../lib/constants";
 import { makeProgram } from "../../lib/util";
+import { Namespace } from "../../types";
 import {
   AbstractControllerRegistry,
   CONTROLLER_REGISTRY_SEED_PREFIX,
@@ -15,22 +16,25 @@ export class ReadOnlyControllerRegistry extends AbstractControllerRegistry {
     connection: Connection,
     address: Uint8Array,
     seedPrefix: string,
-    cluster: ExtendedCluster
+    cluster: ExtendedCluster,
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
     this.program = makeProgram(connection, dummyAuthority);
   }
 
   static for(
     did: string,
     connection: Connection,
-    cluster: ExtendedCluster = "mainnet-beta"
+    cluster: ExtendedCluster = "mainnet-beta",
+    namespace?: Namespace
   ): ReadOnlyControllerRegistry {
     return new ReadOnlyControllerRegistry(
       connection,
       DidSolIdentifier.parse(did).authority.toBuffer(),
       CONTROLLER_REGISTRY_SEED_PREFIX,
-      cluster
+      cluster,
+      namespace
     );
   }
 }
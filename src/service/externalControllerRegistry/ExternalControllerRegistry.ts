@@ -0,0 +1,198 @@
+import {
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
+import { sha256 } from "@noble/hashes/sha256";
+import { CommonRegistry } from "../../lib/CommonRegistry";
+import { MAX_RENT_PAYERS, SPACE_BUFFER } from "../../lib/constants";
+import {
+  configAccounts,
+  fetchGatewayTokenAccount,
+  makeProgram,
+} from "../../lib/util";
+import { Execution, Wallet } from "../../types";
+
+export const EXTERNAL_CONTROLLER_REGISTRY_SEED_PREFIX =
+  "external_controller_registry";
+
+/**
+ * A registry of did:sol DIDs controlled by a non-did:sol DID, e.g. did:web:example.com,
+ * which the controlled DIDs list in their other controllers.
+ *
+ * The controller cannot sign Solana transactions, so updates are made by the wallet,
+ * which must be an authority on the controlled DID.
+ */
+export class ExternalControllerRegistry extends CommonRegistry {
+  protected constructor(
+    protected wallet: Wallet,
+    connection: Connection,
+    protected controller: string,
+    cluster: ExtendedCluster
+  ) {
+    // the registry is seeded by the hash of the controller, as it may be too long to be a seed
+    super(
+      sha256(controller),
+      EXTERNAL_CONTROLLER_REGISTRY_SEED_PREFIX,
+      cluster
+    );
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Given a did count, calculate the size in bytes of the registry required to store it.
+   *
+   * NOTE: This must stay in sync with ExternalControllerRegistry::calculate_size in the program.
+   */
+  protected calculateMaxCount(sizeInBytes: number): number {
+    const didSpace =
+      sizeInBytes -
+      8 - // discriminator
+      1 - // version
+      4 - // controller length field
+      Buffer.from(this.controller).length - // controller
+      4 - // vec length field
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
+    return Math.floor(didSpace / 32);
+  }
+
+  public async analyseSpace(): Promise<{
+    count: number;
+    maxCount: number;
+    sizeBytes: number;
+  }> {
+    const registryAccountInfo =
+      await this.program.account.externalControllerRegistry.getAccountInfo(
+        this.registryAddress
+      );
+
+    if (!registryAccountInfo) return { count: 0, maxCount: 0, sizeBytes: 0 };
+
+    const sizeBytes = registryAccountInfo.data.length;
+
+    const coder =
+      this.program.account.externalControllerRegistry.coder.accounts;
+    const account = coder.decode(
+      "externalControllerRegistry",
+      registryAccountInfo.data
+    );
+    const didCount = account.controlledDids.length;
+    const maxCount = this.calculateMaxCount(sizeBytes);
+
+    return { count: didCount, maxCount, sizeBytes };
+  }
+
+  async listDIDs(): Promise<string[]> {
+    const registryAccount =
+      await this.program.account.externalControllerRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    if (!registryAccount) return [];
+
+    return registryAccount.controlledDids.map((identifier: PublicKey) =>
+      DidSolIdentifier.create(identifier, this.cluster).toString()
+    );
+  }
+
+  protected async resizeInstructionIfNeeded(): Promise<TransactionInstruction | null> {
+    const space = await this.analyseSpace();
+
+    // If we have space, don't resize
+    // Also, don't resize if maxCount is at 0, as this indicates that the registry is not yet initialised
+    if (space.count < space.maxCount || space.maxCount === 0) return null;
+
+    return this.program.methods
+      .resizeExternalControllerRegistry(space.count + SPACE_BUFFER)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+      })
+      .instruction();
+  }
+
+  /**
+   * Register a DID that lists the controller in its other controllers.
+   * The wallet must be an authority on the DID, either directly, or through the given chain of controller DIDs.
+   */
+  async register(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const resizeInstruction = await this.resizeInstructionIfNeeded();
+
+    return this.program.methods
+      .registerDidForExternalController(this.controller, account.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        authority: this.wallet.publicKey,
+        controlledDid: account.authority,
+        controlledDidAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain))
+      .preInstructions(resizeInstruction ? [resizeInstruction] : []);
+  }
+
+  /**
+   * Remove a DID from the registry.
+   * The wallet must be an authority on the DID, either directly, or through the given chain of controller DIDs.
+   */
+  remove(did: string, controllerChain: string[] = []): Execution {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .removeDidForExternalController(account.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        controlledDid: account.authority,
+        controlledDidAccount: account.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Grow the registry to fit the given number of DIDs
+   */
+  resize(didCount: number): Execution {
+    return this.program.methods
+      .resizeExternalControllerRegistry(didCount)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * @param controller The non-did:sol controller DID, e.g. did:web:example.com
+   * @param wallet An authority on the controlled DIDs, which submits, and pays for, the registry updates
+   */
+  static for(
+    controller: string,
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta"
+  ): ExternalControllerRegistry {
+    return new ExternalControllerRegistry(
+      wallet,
+      connection,
+      controller,
+      cluster
+    );
+  }
+}
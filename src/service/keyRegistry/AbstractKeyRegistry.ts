@@ -4,17 +4,34 @@ import {
   ExtendedCluster,
 } from "@identity.com/sol-did-client";
 import { CommonRegistry } from "../../lib/CommonRegistry";
+import {
+  EthAddressForm,
+  EthRegistryEntry,
+  Namespace,
+  RegistrationInvitation,
+  RentPayer,
+} from "../../types";
+import {
+  DID_REGISTRY_PROGRAM_ID,
+  KEY_REGISTRY_DID_SIZE,
+  MAX_NAMESPACE_LENGTH,
+  MAX_RENT_PAYERS,
+  REGISTRATION_INBOX_SEED_PREFIX,
+  REGISTRATION_INVITATION_SEED_PREFIX,
+} from "../../lib/constants";
 
 export const KEY_REGISTRY_SEED_PREFIX = "key_registry";
 export const ETH_KEY_REGISTRY_SEED_PREFIX = "eth_key_registry";
+export const BTC_KEY_REGISTRY_SEED_PREFIX = "btc_key_registry";
 
 export abstract class AbstractKeyRegistry extends CommonRegistry {
   protected constructor(
     address: Uint8Array,
     seedPrefix: string,
-    cluster: ExtendedCluster
+    cluster: ExtendedCluster,
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
   }
 
   /**
@@ -28,8 +45,17 @@ export abstract class AbstractKeyRegistry extends CommonRegistry {
       8 - // discriminator
       1 - // version
       32 - // key
-      4; // vec length field
-    return Math.floor(didSpace / 32);
+      4 - // vec length field
+      8 - // removal delay
+      2 - // required flags
+      4 - // eth address forms length field
+      4 - // expiries length field
+      1 - // has history
+      4 - // namespace length field
+      MAX_NAMESPACE_LENGTH - // namespace
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
+    return Math.floor(didSpace / KEY_REGISTRY_DID_SIZE);
   }
 
   /**
@@ -66,8 +92,93 @@ export abstract class AbstractKeyRegistry extends CommonRegistry {
 
     if (!registryAccount) return [];
 
-    return registryAccount.dids.map((identifier: PublicKey) =>
-      DidSolIdentifier.create(identifier, this.cluster).toString()
+    // expired DIDs are ignored until they are swept from the registry
+    const now = Date.now() / 1000;
+    const isExpired = (index: number) => {
+      const expiresAt = registryAccount.expiries[index]?.toNumber() ?? 0;
+      return expiresAt !== 0 && expiresAt <= now;
+    };
+
+    return registryAccount.dids
+      .filter((_: PublicKey, index: number) => !isExpired(index))
+      .map((identifier: PublicKey) =>
+        DidSolIdentifier.create(identifier, this.cluster).toString()
+      );
+  }
+
+  /**
+   * List the parties that paid rent for the registry, in the order in which they first paid,
+   * along with the lamports each is refunded when the registry is shrunk or closed
+   */
+  async listRentPayers(): Promise<RentPayer[]> {
+    const registryAccount =
+      await this.program.account.keyRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    return this.toRentPayers(registryAccount?.rentPayers ?? []);
+  }
+
+  public getInvitationAddress(did: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(REGISTRATION_INVITATION_SEED_PREFIX),
+        this.address,
+        did.toBuffer(),
+      ],
+      DID_REGISTRY_PROGRAM_ID
+    )[0];
+  }
+
+  public getInboxAddress(): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(REGISTRATION_INBOX_SEED_PREFIX), this.address],
+      DID_REGISTRY_PROGRAM_ID
+    )[0];
+  }
+
+  /**
+   * List the invitations for the key to register DIDs, waiting to be accepted or rejected,
+   * as listed in the key's inbox
+   */
+  async listInvitations(): Promise<RegistrationInvitation[]> {
+    const inbox = await this.program.account.registrationInbox.fetchNullable(
+      this.getInboxAddress()
+    );
+    if (!inbox) return [];
+
+    const invitations =
+      await this.program.account.registrationInvitation.fetchMultiple(
+        inbox.dids.map((did) => this.getInvitationAddress(did))
+      );
+
+    return inbox.dids.map((did, i) => ({
+      did: DidSolIdentifier.create(did, this.cluster).toString(),
+      // every invitation listed in the inbox exists
+      inviter: (invitations[i] as { inviter: PublicKey }).inviter,
+    }));
+  }
+
+  /**
+   * For an eth address registry, list the registered DIDs along with the form
+   * in which each DID lists the eth address.
+   */
+  async listEthAddressEntries(): Promise<EthRegistryEntry[]> {
+    const registryAccount =
+      await this.program.account.keyRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    if (!registryAccount) return [];
+
+    // anchor decodes enums as an object with a single key, e.g. { publicKey: {} }
+    const forms = registryAccount.ethAddressForms.map(
+      (form: object) => Object.keys(form)[0] as EthAddressForm
     );
+
+    return registryAccount.dids.map((identifier: PublicKey, index: number) => ({
+      did: DidSolIdentifier.create(identifier, this.cluster).toString(),
+      form: forms[index],
+    }));
   }
 }
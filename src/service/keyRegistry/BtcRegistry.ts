@@ -0,0 +1,134 @@
+import { Connection } from "@solana/web3.js";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+import { BTC_KEY_REGISTRY_SEED_PREFIX } from "./AbstractKeyRegistry";
+import { Registry } from "./Registry";
+import { Execution, Wallet } from "../../types";
+import {
+  configAccounts,
+  fetchMessageNonce,
+  getMessageNonceAddress,
+} from "../../lib/util";
+import {
+  btcRegisterDidMessage,
+  btcRemoveDidMessage,
+  BtcAddressType,
+  decodeBtcAddress,
+} from "../../lib/bitcoin";
+
+export class BtcRegistry extends Registry {
+  /**
+   * Create a registry client for a mainnet P2PKH (1...) or P2WPKH (bc1q...) address.
+   * The wallet pays for registry updates - the address itself authorises them with BIP-137 signatures.
+   */
+  static forBtcAddress(
+    btcAddress: string,
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta"
+  ): BtcRegistry {
+    const { type, pubkeyHash } = decodeBtcAddress(btcAddress);
+    // the registry is seeded by the address type, followed by the public key hash
+    return new BtcRegistry(
+      wallet,
+      connection,
+      Uint8Array.from([type, ...pubkeyHash]),
+      BTC_KEY_REGISTRY_SEED_PREFIX,
+      cluster
+    );
+  }
+
+  private get addressType(): BtcAddressType {
+    return this.address[0];
+  }
+
+  private get pubkeyHash(): number[] {
+    return Array.from(this.address.slice(1));
+  }
+
+  private get addressTypeArg(): { p2pkh: {} } | { p2wpkh: {} } {
+    return this.addressType === BtcAddressType.P2pkh
+      ? { p2pkh: {} }
+      : { p2wpkh: {} };
+  }
+
+  /**
+   * Build the message that the Bitcoin address must sign (e.g. with a wallet's "Sign Message" feature)
+   * to register a DID. The message includes the registry nonce, so it can only be used once.
+   */
+  async registrationMessage(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    const nonce = await fetchMessageNonce(this.program, this.registryAddress);
+
+    return Buffer.from(
+      btcRegisterDidMessage(
+        this.registryAddress.toBase58(),
+        account.authority.toBase58(),
+        nonce.toString()
+      )
+    );
+  }
+
+  /**
+   * Register a DID using a BIP-137 signature of the registration message by the Bitcoin address.
+   * @param did The DID to register. The public key of the address must be listed on the DID.
+   * @param signature The 65-byte signature (header byte followed by r and s)
+   */
+  async registerSigned(did: string, signature: Uint8Array): Promise<Execution> {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .registerDidSignedByBtcAddress(
+        this.addressTypeArg,
+        this.pubkeyHash,
+        Array.from(signature),
+        account.bump
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        did: account.authority,
+        didAccount: account.account,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+      });
+  }
+
+  /**
+   * Build the message that the Bitcoin address must sign to remove a DID.
+   * The message includes the registry nonce, so it can only be used once.
+   */
+  async removalMessage(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    const nonce = await fetchMessageNonce(this.program, this.registryAddress);
+
+    return Buffer.from(
+      btcRemoveDidMessage(
+        this.registryAddress.toBase58(),
+        account.authority.toBase58(),
+        nonce.toString()
+      )
+    );
+  }
+
+  /**
+   * Remove a DID using a BIP-137 signature of the removal message by the Bitcoin address.
+   * @param did The DID to remove
+   * @param signature The 65-byte signature (header byte followed by r and s)
+   */
+  async removeSigned(did: string, signature: Uint8Array): Promise<Execution> {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .removeDidForBtcAddress(
+        this.addressTypeArg,
+        this.pubkeyHash,
+        Array.from(signature)
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        did: account.authority,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+      });
+  }
+}
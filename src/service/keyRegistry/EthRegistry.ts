@@ -1,18 +1,28 @@
-import { Connection } from "@solana/web3.js";
+import {
+  Connection,
+  Secp256k1Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 import { ExtendedCluster } from "@identity.com/sol-did-client";
 import { Wallet as EthWallet } from "@ethersproject/wallet";
 import BN from "bn.js";
 import { arrayify } from "@ethersproject/bytes";
 import { ETH_KEY_REGISTRY_SEED_PREFIX } from "./AbstractKeyRegistry";
 import { Registry } from "./Registry";
-import { Execution, Wallet } from "../../types";
+import { Execution, Namespace, Wallet } from "../../types";
+import {
+  configAccounts,
+  fetchMessageNonce,
+  getMessageNonceAddress,
+} from "../../lib/util";
 
 export class EthRegistry extends Registry {
   static forEthAddress(
     ethAddress: string,
     wallet: Wallet,
     connection: Connection,
-    cluster: ExtendedCluster = "mainnet-beta"
+    cluster: ExtendedCluster = "mainnet-beta",
+    namespace?: Namespace
   ): EthRegistry {
     const trimmedEthAddress = ethAddress.substring(2); // without 0x
     return new EthRegistry(
@@ -20,31 +30,45 @@ export class EthRegistry extends Registry {
       connection,
       Buffer.from(trimmedEthAddress, "hex"),
       ETH_KEY_REGISTRY_SEED_PREFIX,
-      cluster
+      cluster,
+      undefined,
+      namespace
     );
   }
 
-  async register(did: string): Promise<Execution> {
+  async register(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
     const account = this.didToAccount(did);
 
     return this.program.methods
-      .registerDidForEthAddress(Array.from(this.address), account.bump)
+      .registerDidForEthAddress(
+        Array.from(this.address),
+        account.bump,
+        this.namespace
+      )
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         authority: this.wallet.publicKey,
         did: account.authority,
         didAccount: account.account,
-      });
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  private static withStaticNonce(message: Buffer): Buffer {
+    // We do not use a dynamic nonce here - there is no risk of replay when adding a DID to a registry
+    const staticNonce = Buffer.from(new BN(0).toArray("le", 8));
+    return Buffer.concat([message, staticNonce]);
   }
 
   private async ethSignMessage(
     message: Buffer,
     signer: EthWallet
   ): Promise<{ signature: number[]; recoveryId: number }> {
-    // We do not use a dynamic nonce here - there is no risk of replay when adding a DID to a registry
-    const staticNonce = Buffer.from(new BN(0).toArray("le", 8));
-    const messageToSign = Buffer.concat([message, staticNonce]);
-    const signatureFull = await signer.signMessage(messageToSign);
+    const signatureFull = await signer.signMessage(message);
 
     const signatureBytes = arrayify(signatureFull);
     const signature = Array.from(signatureBytes.slice(0, -1));
@@ -60,10 +84,10 @@ export class EthRegistry extends Registry {
 
   async registerSigned(did: string, ethWallet: EthWallet): Promise<Execution> {
     const account = this.didToAccount(did);
-    // TODO: Change message to come directly from instruction OR only accept update via cryptid
-    // TODO: This allows replay attacks
+    // The message has no nonce, so the signature can be used again to register the DID after it is removed.
+    // registerSignedViaPrecompile binds the message to the registry and a nonce.
     const signature = await this.ethSignMessage(
-      account.authority.toBuffer(),
+      EthRegistry.withStaticNonce(account.authority.toBuffer()),
       ethWallet
     );
 
@@ -79,12 +103,82 @@ export class EthRegistry extends Registry {
       .registerDidSignedByEthAddress(
         Array.from(this.address),
         signature,
-        account.bump
+        account.bump,
+        this.namespace
       )
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         did: account.authority,
         didAccount: account.account,
       });
   }
+
+  /**
+   * Build the message that the eth address signs to register a DID with registerSignedViaPrecompile.
+   * The message includes the registry and its nonce, so it can only be used once.
+   *
+   * NOTE: This must stay in sync with eth_register_did_message in the program.
+   */
+  async registrationMessage(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    const nonce = await fetchMessageNonce(this.program, this.registryAddress);
+
+    return Buffer.from(
+      [
+        "did-registry:register_did",
+        this.registryAddress.toBase58(),
+        account.authority.toBase58(),
+        nonce.toString(),
+      ].join(":")
+    );
+  }
+
+  /**
+   * Register a DID signed with an eth key, where the signature is verified by the secp256k1 program
+   * rather than recovered by the registry program. This uses far fewer compute units than registerSigned.
+   */
+  async registerSignedViaPrecompile(
+    did: string,
+    ethWallet: EthWallet
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const signedMessage = await this.registrationMessage(did);
+    const { signature, recoveryId } = await this.ethSignMessage(
+      signedMessage,
+      ethWallet
+    );
+
+    // The secp256k1 program hashes the message as-is, so it must include the prefix added by signMessage
+    const message = Buffer.concat([
+      Buffer.from(`\x19Ethereum Signed Message:\n${signedMessage.length}`),
+      signedMessage,
+    ]);
+
+    const signatureInstruction =
+      Secp256k1Program.createInstructionWithEthAddress({
+        ethAddress: this.address,
+        message,
+        signature: Buffer.from(signature),
+        recoveryId,
+        // the secp256k1 instruction is the first in the transaction
+        instructionIndex: 0,
+      });
+
+    return this.program.methods
+      .registerDidSignedByEthAddressViaPrecompile(
+        Array.from(this.address),
+        account.bump,
+        this.namespace
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        did: account.authority,
+        didAccount: account.account,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+      })
+      .preInstructions([signatureInstruction]);
+  }
 }
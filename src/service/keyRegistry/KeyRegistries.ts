@@ -0,0 +1,125 @@
+import { Program } from "@project-serum/anchor";
+import { AccountMeta, Connection, PublicKey } from "@solana/web3.js";
+import { DidSolIdentifier } from "@identity.com/sol-did-client";
+import { DidRegistry } from "../../types/did_registry";
+import {
+  DID_REGISTRY_PROGRAM_ID,
+  REGISTRY_HISTORY_SEED_PREFIX,
+} from "../../lib/constants";
+import {
+  configAccounts,
+  deferredExecution,
+  makeProgram,
+  namespaceSeed,
+} from "../../lib/util";
+import { Execution, Namespace, Wallet } from "../../types";
+import { KEY_REGISTRY_SEED_PREFIX } from "./AbstractKeyRegistry";
+
+/**
+ * Update the registries of many keys in a single transaction,
+ * e.g. to register a DID against all of its keys at once.
+ *
+ * Every key must sign the transaction, so the executions returned here should be
+ * converted into transactions, and signed by each key.
+ */
+export class KeyRegistries {
+  private program: Program<DidRegistry>;
+  // The application namespace of the registries (empty = the default registries)
+  private namespace: Buffer;
+
+  private constructor(
+    private keys: PublicKey[],
+    private wallet: Wallet,
+    connection: Connection,
+    namespace: Namespace
+  ) {
+    this.program = makeProgram(connection, wallet);
+    this.namespace = namespaceSeed(namespace);
+  }
+
+  private registryAddress(key: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from(KEY_REGISTRY_SEED_PREFIX), key.toBuffer(), this.namespace],
+      DID_REGISTRY_PROGRAM_ID
+    )[0];
+  }
+
+  /**
+   * The keys and their registries, as expected by the program,
+   * followed by the histories of the registries that have one
+   */
+  private async keyRegistryAccounts(): Promise<AccountMeta[]> {
+    const registries = this.keys.map((key) => this.registryAddress(key));
+    const registryAccounts =
+      await this.program.account.keyRegistry.fetchMultiple(registries);
+
+    const histories = registries
+      .filter((_, i) => registryAccounts[i]?.hasHistory)
+      .map((registry) => ({
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from(REGISTRY_HISTORY_SEED_PREFIX), registry.toBuffer()],
+          DID_REGISTRY_PROGRAM_ID
+        )[0],
+        isSigner: false,
+        isWritable: true,
+      }));
+
+    return [
+      ...this.keys.flatMap((key, i) => [
+        { pubkey: key, isSigner: true, isWritable: false },
+        { pubkey: registries[i], isSigner: false, isWritable: true },
+      ]),
+      ...histories,
+    ];
+  }
+
+  /**
+   * Register a DID against all the keys. Each key must be a direct authority on the DID.
+   * Registries that do not exist yet are created, and paid for by the wallet.
+   */
+  register(did: string): Execution {
+    const didSolIdentifier = DidSolIdentifier.parse(did);
+    const [didAccount, didBump] = didSolIdentifier.dataAccount();
+
+    return deferredExecution(async () =>
+      this.program.methods
+        .registerDidForKeys(didBump, this.namespace)
+        .accounts({
+          config: configAccounts(),
+          payer: this.wallet.publicKey,
+          did: didSolIdentifier.authority,
+          didAccount,
+        })
+        .remainingAccounts(await this.keyRegistryAccounts())
+    );
+  }
+
+  /**
+   * Remove a DID from the registries of all the keys.
+   */
+  remove(did: string): Execution {
+    return deferredExecution(async () =>
+      this.program.methods
+        .removeDidForKeys(this.namespace)
+        .accounts({
+          config: configAccounts(),
+          did: DidSolIdentifier.parse(did).authority,
+        })
+        .remainingAccounts(await this.keyRegistryAccounts())
+    );
+  }
+
+  /**
+   * @param keys The keys whose registries are updated
+   * @param wallet Submits, and pays for, the transaction
+   * @param namespace The application namespace of the registries (default: the default registries)
+   */
+  static for(
+    keys: PublicKey[],
+    wallet: Wallet,
+    connection: Connection,
+    namespace: Namespace = ""
+  ): KeyRegistries {
+    return new KeyRegistries(keys, wallet, connection, namespace);
+  }
+}
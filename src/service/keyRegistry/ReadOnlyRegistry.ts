@@ -7,42 +7,48 @@ import {
 } from "./AbstractKeyRegistry";
 import { dummyAuthority } from "../../lib/constants";
 import { makeProgram } from "../../lib/util";
+import { Namespace } from "../../types";
 
 export class ReadOnlyRegistry extends AbstractKeyRegistry {
   private constructor(
     connection: Connection,
     address: Uint8Array,
     seedPrefix: string,
-    cluster: ExtendedCluster
+    cluster: ExtendedCluster,
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
     this.program = makeProgram(connection, dummyAuthority);
   }
 
   static for(
     publicKey: PublicKey,
     connection: Connection,
-    cluster: ExtendedCluster = "mainnet-beta"
+    cluster: ExtendedCluster = "mainnet-beta",
+    namespace?: Namespace
   ): ReadOnlyRegistry {
     return new ReadOnlyRegistry(
       connection,
       publicKey.toBuffer(),
       KEY_REGISTRY_SEED_PREFIX,
-      cluster
+      cluster,
+      namespace
     );
   }
 
   static forEthAddress(
     ethAddress: string,
     connection: Connection,
-    cluster: ExtendedCluster = "mainnet-beta"
+    cluster: ExtendedCluster = "mainnet-beta",
+    namespace?: Namespace
   ): ReadOnlyRegistry {
     const trimmedEthAddress = ethAddress.substring(2); // without 0x
     return new ReadOnlyRegistry(
       connection,
       Buffer.from(trimmedEthAddress, "hex"),
       ETH_KEY_REGISTRY_SEED_PREFIX,
-      cluster
+      cluster,
+      namespace
     );
   }
 }
@@ -1,12 +1,39 @@
-import { Connection, PublicKey, TransactionInstruction } from "@solana/web3.js";
-import { ExtendedCluster } from "@identity.com/sol-did-client";
+import {
+  AccountMeta,
+  Connection,
+  Ed25519Program,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import {
+  BitwiseVerificationMethodFlag,
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
 import {
   AbstractKeyRegistry,
   KEY_REGISTRY_SEED_PREFIX,
 } from "./AbstractKeyRegistry";
-import { SPACE_BUFFER } from "../../lib/constants";
-import { Execution, Wallet } from "../../types";
-import { makeProgram } from "../../lib/util";
+import {
+  CLOSE_MESSAGE_PREFIX,
+  REGISTER_DID_MESSAGE_PREFIX,
+  REMOVE_DID_MESSAGE_PREFIX,
+  RESIZE_MESSAGE_PREFIX,
+  SPACE_BUFFER,
+} from "../../lib/constants";
+import { Execution, Namespace, Wallet } from "../../types";
+import {
+  configAccounts,
+  deferredExecution,
+  fetchGatewayTokenAccount,
+  fetchMessageNonce,
+  flagsToBitmask,
+  getMessageNonceAddress,
+  getSuccessionAddress,
+  makeProgram,
+} from "../../lib/util";
 
 export class Registry extends AbstractKeyRegistry {
   // Pays for registry updates (defaults to the authority)
@@ -18,9 +45,10 @@ export class Registry extends AbstractKeyRegistry {
     address: Uint8Array,
     seedPrefix: string,
     cluster: ExtendedCluster,
-    payer?: PublicKey
+    payer?: PublicKey,
+    namespace?: Namespace
   ) {
-    super(address, seedPrefix, cluster);
+    super(address, seedPrefix, cluster, namespace);
     this.program = makeProgram(connection, wallet);
     this.payer = payer || wallet.publicKey;
   }
@@ -34,8 +62,9 @@ export class Registry extends AbstractKeyRegistry {
     if (registryAccount) return null;
 
     return this.program.methods
-      .createKeyRegistry(this.registryBump)
+      .createKeyRegistry(this.registryBump, this.namespace)
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         payer: this.payer,
         authority: this.wallet.publicKey,
@@ -54,6 +83,7 @@ export class Registry extends AbstractKeyRegistry {
     return this.program.methods
       .resizeKeyRegistry(space.count + SPACE_BUFFER)
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         authority: this.wallet.publicKey,
         payer: this.payer,
@@ -61,7 +91,23 @@ export class Registry extends AbstractKeyRegistry {
       .instruction();
   }
 
-  async register(did: string): Promise<Execution> {
+  // the history of the registry, if it has one, which must be passed when adding or removing DIDs
+  protected async fetchHistoryAccounts(): Promise<AccountMeta[]> {
+    const registryAccount =
+      await this.program.account.keyRegistry.fetchNullable(
+        this.registryAddress
+      );
+    return this.historyAccounts(registryAccount?.hasHistory ?? false);
+  }
+
+  /**
+   * Register a DID against the key. The key must be an authority on the DID, either directly,
+   * or through the given chain of controller DIDs.
+   */
+  async register(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
     const account = this.didToAccount(did);
 
     const initInstruction = await this.initInstructionIfNeeded();
@@ -74,48 +120,682 @@ export class Registry extends AbstractKeyRegistry {
     return this.program.methods
       .registerDid(account.bump)
       .accounts({
+        config: configAccounts(),
         registry: this.registryAddress,
         authority: this.wallet.publicKey,
         did: account.authority,
         didAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
       })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ])
       .preInstructions(preInstructions);
   }
 
-  removePubkey(did: PublicKey): Execution {
-    return this.program.methods.removeDid().accounts({
-      registry: this.registryAddress,
-      authority: this.wallet.publicKey,
-      did,
+  /**
+   * Build a message that the key must sign to authorise an update to its registry.
+   * The message includes the registry nonce, so it can only be used once.
+   *
+   * NOTE: This must stay in sync with signed_message in the program.
+   */
+  private async signedMessage(
+    prefix: string,
+    data: Uint8Array
+  ): Promise<Buffer> {
+    const nonce = await fetchMessageNonce(this.program, this.registryAddress);
+
+    return Buffer.concat([
+      Buffer.from(prefix),
+      this.registryAddress.toBuffer(),
+      Buffer.from(data),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ]);
+  }
+
+  private async signatureInstruction(
+    message: Promise<Buffer>,
+    signature: Uint8Array
+  ): Promise<TransactionInstruction> {
+    return Ed25519Program.createInstructionWithPublicKey({
+      publicKey: this.address,
+      message: await message,
+      signature,
     });
   }
 
+  /**
+   * Build the message that the key must sign to authorise registering a DID via registerWithSignedMessage.
+   */
+  registrationMessage(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    return this.signedMessage(
+      REGISTER_DID_MESSAGE_PREFIX,
+      account.authority.toBuffer()
+    );
+  }
+
+  /**
+   * Build the message that the key must sign to authorise removing a DID via removeWithSignedMessage.
+   */
+  removalMessage(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    return this.signedMessage(
+      REMOVE_DID_MESSAGE_PREFIX,
+      account.authority.toBuffer()
+    );
+  }
+
+  /**
+   * Build the message that the key must sign to authorise resizing the registry via resizeWithSignedMessage.
+   * The payer pays for (or is refunded) the change in rent, so it is included in the message.
+   */
+  resizeMessage(didCount: number): Promise<Buffer> {
+    const didCountBytes = Buffer.alloc(4);
+    didCountBytes.writeUInt32LE(didCount);
+    return this.signedMessage(
+      RESIZE_MESSAGE_PREFIX,
+      Buffer.concat([didCountBytes, this.payer.toBuffer()])
+    );
+  }
+
+  /**
+   * Build the message that the key must sign to authorise closing the registry via closeWithSignedMessage.
+   * The payer receives the registry rent, so it is included in the message.
+   */
+  closeMessage(): Promise<Buffer> {
+    return this.signedMessage(CLOSE_MESSAGE_PREFIX, this.payer.toBuffer());
+  }
+
+  /**
+   * Register a DID using a signature of the registration message by the key, rather than the key signing the transaction.
+   * The transaction is paid for by the payer (e.g. a relayer), which also pays for the registry if it does not yet exist.
+   */
+  async registerWithSignedMessage(
+    did: string,
+    signature: Uint8Array,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const signatureInstruction = await this.signatureInstruction(
+      this.registrationMessage(did),
+      signature
+    );
+
+    return this.program.methods
+      .registerDidWithSignedMessage(account.bump, this.namespace)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.payer,
+        authority: new PublicKey(this.address),
+        did: account.authority,
+        didAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          new PublicKey(this.address)
+        ),
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ])
+      .preInstructions([signatureInstruction]);
+  }
+
+  /**
+   * Remove a DID using a signature of the removal message by the key, rather than the key signing the transaction.
+   */
+  async removeWithSignedMessage(
+    did: string,
+    signature: Uint8Array
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const signatureInstruction = await this.signatureInstruction(
+      this.removalMessage(did),
+      signature
+    );
+
+    return this.program.methods
+      .removeDidWithSignedMessage()
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.payer,
+        authority: new PublicKey(this.address),
+        did: account.authority,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(await this.fetchHistoryAccounts())
+      .preInstructions([signatureInstruction]);
+  }
+
+  /**
+   * Resize the registry using a signature of the resize message by the key, rather than the key signing the transaction.
+   */
+  async resizeWithSignedMessage(
+    didCount: number,
+    signature: Uint8Array
+  ): Promise<Execution> {
+    const signatureInstruction = await this.signatureInstruction(
+      this.resizeMessage(didCount),
+      signature
+    );
+
+    return this.program.methods
+      .resizeKeyRegistryWithSignedMessage(didCount)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.payer,
+        authority: new PublicKey(this.address),
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(
+        this.rentPayerAccounts(await this.listRentPayers(), this.payer, [])
+      )
+      .preInstructions([signatureInstruction]);
+  }
+
+  /**
+   * Close the registry using a signature of the close message by the key, rather than the key signing the transaction.
+   */
+  async closeWithSignedMessage(signature: Uint8Array): Promise<Execution> {
+    const signatureInstruction = await this.signatureInstruction(
+      this.closeMessage(),
+      signature
+    );
+
+    return this.program.methods
+      .closeKeyRegistryWithSignedMessage()
+      .accounts({
+        registry: this.registryAddress,
+        payer: this.payer,
+        authority: new PublicKey(this.address),
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts([
+        ...(await this.fetchHistoryAccounts()),
+        ...this.rentPayerAccounts(await this.listRentPayers(), this.payer, []),
+      ])
+      .preInstructions([signatureInstruction]);
+  }
+
+  /**
+   * Register a DID against the key, which expires at the given time, e.g. for an event ticket.
+   * Once expired, the DID is no longer listed, and can be removed by anyone using sweep.
+   */
+  async registerWithExpiry(
+    did: string,
+    expiresAt: Date,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+
+    const initInstruction = await this.initInstructionIfNeeded();
+    const resizeInstruction = await this.resizeInstructionIfNeeded();
+    const preInstructions = [
+      ...(initInstruction ? [initInstruction] : []),
+      ...(resizeInstruction ? [resizeInstruction] : []),
+    ];
+
+    return this.program.methods
+      .registerDidWithExpiry(
+        account.bump,
+        new BN(Math.floor(expiresAt.getTime() / 1000))
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        did: account.authority,
+        didAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
+      })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ])
+      .preInstructions(preInstructions);
+  }
+
+  /**
+   * Remove all expired DIDs from the registry, and compact it, refunding the rent freed to its rent payers.
+   * Anyone can sweep a registry, so the wallet need not be the key (see forRelayer).
+   */
+  sweep(): Execution {
+    const authority = new PublicKey(this.address);
+    return deferredExecution(async () =>
+      this.program.methods
+        .sweepExpiredDids()
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority,
+        })
+        .remainingAccounts([
+          ...(await this.fetchHistoryAccounts()),
+          ...this.rentPayerAccounts(await this.listRentPayers(), authority, []),
+        ])
+    );
+  }
+
+  removePubkey(did: PublicKey): Execution {
+    return deferredExecution(async () =>
+      this.program.methods
+        .removeDid()
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          did,
+        })
+        .remainingAccounts(await this.fetchHistoryAccounts())
+    );
+  }
+
   remove(did: string): Execution {
     const account = this.didToAccount(did);
     return this.removePubkey(account.authority);
   }
 
-  resize(did_count: number): Execution {
-    return this.program.methods.resizeKeyRegistry(did_count).accounts({
+  /**
+   * Invite the key to register a DID. The wallet must be an authority on the DID, either directly,
+   * or through the given chain of controller DIDs, so the registry client should be created with forRelayer.
+   * The payer pays the rent and a deposit for the invitation, which is refunded if the key accepts it.
+   */
+  invite(did: string, controllerChain: string[] = []): Execution {
+    const account = this.didToAccount(did);
+    return this.program.methods
+      .inviteDid(account.bump)
+      .accounts({
+        config: configAccounts(),
+        invitation: this.getInvitationAddress(account.authority),
+        inbox: this.getInboxAddress(),
+        inviter: this.payer,
+        authority: this.wallet.publicKey,
+        key: new PublicKey(this.address),
+        did: account.authority,
+        didAccount: account.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Accept an invitation to register a DID. As with register, the key must be an authority on the DID.
+   */
+  async acceptInvitation(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const invitation = this.getInvitationAddress(account.authority);
+    const { inviter } =
+      await this.program.account.registrationInvitation.fetch(invitation);
+
+    const initInstruction = await this.initInstructionIfNeeded();
+    const resizeInstruction = await this.resizeInstructionIfNeeded();
+    const preInstructions = [
+      ...(initInstruction ? [initInstruction] : []),
+      ...(resizeInstruction ? [resizeInstruction] : []),
+    ];
+
+    return this.program.methods
+      .acceptInvitation(account.bump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        invitation,
+        inbox: this.getInboxAddress(),
+        inviter,
+        didAccount: account.account,
+        gatewayToken: await fetchGatewayTokenAccount(
+          this.program,
+          this.wallet.publicKey
+        ),
+      })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ])
+      .preInstructions(preInstructions);
+  }
+
+  /**
+   * Reject an invitation to register a DID. The key receives the deposit of the invitation.
+   */
+  async rejectInvitation(did: string): Promise<Execution> {
+    const invitation = this.getInvitationAddress(
+      this.didToAccount(did).authority
+    );
+    const { inviter } =
+      await this.program.account.registrationInvitation.fetch(invitation);
+
+    return this.program.methods.rejectInvitation().accounts({
+      config: configAccounts(),
+      authority: this.wallet.publicKey,
+      invitation,
+      inbox: this.getInboxAddress(),
+      inviter,
+    });
+  }
+
+  /**
+   * Cancel an invitation for the key to register a DID, which the key has not accepted or rejected yet.
+   * The payer must be the account that paid for the invitation (see invite), and receives its rent and deposit back.
+   */
+  cancelInvitation(did: string): Execution {
+    return this.program.methods.cancelInvitation().accounts({
+      config: configAccounts(),
+      inviter: this.payer,
+      invitation: this.getInvitationAddress(this.didToAccount(did).authority),
+      inbox: this.getInboxAddress(),
+    });
+  }
+
+  /**
+   * Replace a superseded DID with its successor, which the key must be an authority on.
+   * The successor is read from the succession record of the DID (see DidSuccession).
+   * If the registry has a removal delay, the removal of the DID must have been requested, and the delay elapsed.
+   */
+  async replace(
+    did: string,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const succession = getSuccessionAddress(this.didToAccount(did).authority);
+    const { successor } = await this.program.account.didSuccession.fetch(
+      succession
+    );
+    const [successorAccount, successorBump] =
+      DidSolIdentifier.create(successor, this.cluster).dataAccount();
+
+    return this.program.methods
+      .replaceDid(successorBump)
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+        succession,
+        ...(await this.pendingRemovalAccounts(
+          this.didToAccount(did).authority,
+          this.wallet.publicKey
+        )),
+        successorAccount,
+      })
+      .remainingAccounts([
+        ...this.controllerChainToAccounts(controllerChain),
+        ...(await this.fetchHistoryAccounts()),
+      ]);
+  }
+
+  /**
+   * Remove a DID that the key is no longer an authority on. Anyone can prune a registry,
+   * so the wallet need not be the key (see forRelayer).
+   * If shrink is set, the registry is shrunk by the space of one DID, and the rent freed is refunded to its rent payers.
+   * There is no bounty for pruning - the wallet receives only rent not owed to a rent payer.
+   * @param controllers All the controllers of the DID, if it has any. The key must not be an authority on them.
+   */
+  prune(did: string, shrink = false, controllers: string[] = []): Execution {
+    const account = this.didToAccount(did);
+    const controllerAccounts = controllers.flatMap((controller) => {
+      const controllerAccount = this.didToAccount(controller);
+      return [
+        {
+          pubkey: controllerAccount.authority,
+          isSigner: false,
+          isWritable: false,
+        },
+        {
+          pubkey: controllerAccount.account,
+          isSigner: false,
+          isWritable: false,
+        },
+      ];
+    });
+
+    return deferredExecution(async () =>
+      this.program.methods
+        .pruneDid(account.bump, shrink)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          pruner: this.wallet.publicKey,
+          did: account.authority,
+          didAccount: account.account,
+        })
+        .remainingAccounts([
+          ...controllerAccounts,
+          ...(await this.fetchHistoryAccounts()),
+          ...(shrink
+            ? this.rentPayerAccounts(
+                await this.listRentPayers(),
+                this.wallet.publicKey,
+                []
+              )
+            : []),
+        ])
+    );
+  }
+
+  /**
+   * Require removals from this registry to wait for the given number of slots before they can be finalised.
+   * The delay can only be increased, up to MAX_REMOVAL_DELAY (about 30 days).
+   */
+  setRemovalDelay(slots: number): Execution {
+    return this.program.methods
+      .setKeyRegistryRemovalDelay(new BN(slots))
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * Require the key to have the given verification method flags on any DID registered in this registry
+   */
+  setRequiredFlags(flags: BitwiseVerificationMethodFlag[]): Execution {
+    return this.program.methods
+      .setKeyRegistryRequiredFlags(flagsToBitmask(flags))
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        authority: this.wallet.publicKey,
+      });
+  }
+
+  /**
+   * Queue the removal of a DID from a registry with a removal delay
+   */
+  requestRemoval(did: string): Execution {
+    const account = this.didToAccount(did);
+    return this.program.methods.requestDidRemoval().accounts({
+      config: configAccounts(),
       registry: this.registryAddress,
+      pendingRemoval: this.getPendingRemovalAddress(account.authority),
+      payer: this.payer,
       authority: this.wallet.publicKey,
+      did: account.authority,
+    });
+  }
+
+  /**
+   * Remove a DID whose removal was requested, once the removal delay has elapsed
+   */
+  async finalizeRemoval(did: string): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const pendingRemoval = this.getPendingRemovalAddress(account.authority);
+
+    return this.program.methods
+      .finalizeDidRemoval()
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        pendingRemoval,
+        payer: await this.pendingRemovalPayer(pendingRemoval),
+      })
+      .remainingAccounts(await this.fetchHistoryAccounts());
+  }
+
+  /**
+   * Cancel a pending removal of a DID. The authority must be an authority on the DID being removed.
+   */
+  async cancelRemoval(
+    did: string,
+    authority: PublicKey = this.wallet.publicKey,
+    controllerChain: string[] = []
+  ): Promise<Execution> {
+    const account = this.didToAccount(did);
+    const pendingRemoval = this.getPendingRemovalAddress(account.authority);
+
+    return this.program.methods
+      .cancelRemoval(account.bump)
+      .accounts({
+        config: configAccounts(),
+        pendingRemoval,
+        payer: await this.pendingRemovalPayer(pendingRemoval),
+        authority,
+        did: account.authority,
+        didAccount: account.account,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain));
+  }
+
+  /**
+   * Start recording the DIDs added to and removed from the registry, keeping up to the given number of entries.
+   * The registry must already exist. If it had a history before (i.e. it was closed and created again),
+   * that history is reused at its current capacity.
+   */
+  createHistory(capacity: number): Execution {
+    return deferredExecution(async () =>
+      this.program.methods
+        .createKeyRegistryHistory(await this.historyCapacity(capacity))
+        .accounts({
+          config: configAccounts(),
+          history: this.getHistoryAddress(),
+          registry: this.registryAddress,
+          payer: this.payer,
+          authority: this.wallet.publicKey,
+        })
+    );
+  }
+
+  /**
+   * Increase the number of entries kept in the registry's history. Anyone can pay to grow a history.
+   */
+  resizeHistory(capacity: number): Execution {
+    return this.program.methods.resizeRegistryHistory(capacity).accounts({
+      config: configAccounts(),
+      history: this.getHistoryAddress(),
       payer: this.payer,
     });
   }
 
-  close(): Execution {
-    return this.program.methods.closeKeyRegistry().accounts({
+  /**
+   * Resize the registry. The payer pays for any extra rent. If the registry shrinks,
+   * the rent freed is refunded to the parties that paid for it, unless they are in cosigners,
+   * in which case it is paid to the payer, and they must sign the transaction.
+   */
+  resize(did_count: number, cosigners: PublicKey[] = []): Execution {
+    return deferredExecution(async () =>
+      this.program.methods
+        .resizeKeyRegistry(did_count)
+        .accounts({
+          config: configAccounts(),
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          payer: this.payer,
+        })
+        .remainingAccounts(
+          this.rentPayerAccounts(
+            await this.listRentPayers(),
+            this.payer,
+            cosigners
+          )
+        )
+    );
+  }
+
+  /**
+   * Close the registry. Its rent is refunded to the parties that paid for it, and anything left over
+   * is paid to the payer. Parties in cosigners waive their refunds in favour of the payer,
+   * and must sign the transaction.
+   */
+  close(cosigners: PublicKey[] = []): Execution {
+    return deferredExecution(async () =>
+      this.program.methods
+        .closeKeyRegistry()
+        .accounts({
+          registry: this.registryAddress,
+          authority: this.wallet.publicKey,
+          payer: this.payer,
+        })
+        .remainingAccounts([
+          ...(await this.fetchHistoryAccounts()),
+          ...this.rentPayerAccounts(
+            await this.listRentPayers(),
+            this.payer,
+            cosigners
+          ),
+        ])
+    );
+  }
+
+  /**
+   * Migrate a registry created by the first release of the program that was filled, and so cannot be loaded
+   * until the fields added since are appended. The payer pays the rent for the extra space.
+   * Anyone can migrate a registry, so the wallet need not be the key (see forRelayer).
+   */
+  migrate(): Execution {
+    return this.program.methods.migrateKeyRegistry().accounts({
+      config: configAccounts(),
       registry: this.registryAddress,
-      authority: this.wallet.publicKey,
       payer: this.payer,
     });
   }
 
+  /**
+   * Create a registry client for a key that does not sign transactions itself,
+   * e.g. for a relayer submitting registrations authorised by registerWithSignedMessage.
+   * The relayer wallet pays for the transactions.
+   */
+  static forRelayer(
+    key: PublicKey,
+    relayer: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta",
+    namespace?: Namespace
+  ) {
+    return new Registry(
+      relayer,
+      connection,
+      key.toBuffer(),
+      KEY_REGISTRY_SEED_PREFIX,
+      cluster,
+      undefined,
+      namespace
+    );
+  }
+
   static for(
     wallet: Wallet,
     connection: Connection,
     cluster: ExtendedCluster = "mainnet-beta",
-    payer?: PublicKey
+    payer?: PublicKey,
+    namespace?: Namespace
   ) {
     return new Registry(
       wallet,
@@ -123,7 +803,8 @@ export class Registry extends AbstractKeyRegistry {
       wallet.publicKey.toBuffer(),
       KEY_REGISTRY_SEED_PREFIX,
       cluster,
-      payer
+      payer,
+      namespace
     );
   }
 }
@@ -0,0 +1,309 @@
+import {
+  Connection,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
+import { sha256 } from "@noble/hashes/sha256";
+import { CommonRegistry } from "../../lib/CommonRegistry";
+import {
+  MAX_RENT_PAYERS,
+  PASSKEY_CLOSE_PREFIX,
+  PASSKEY_REGISTER_DID_PREFIX,
+  PASSKEY_REMOVE_DID_PREFIX,
+  PASSKEY_RESIZE_PREFIX,
+} from "../../lib/constants";
+import {
+  createSecp256r1Instruction,
+  p256SignatureFromDer,
+  passkeyChallenge,
+} from "../../lib/passkey";
+import {
+  configAccounts,
+  fetchMessageNonce,
+  getMessageNonceAddress,
+  makeProgram,
+} from "../../lib/util";
+import { Execution, RentPayer, Wallet, WebAuthnAssertion } from "../../types";
+
+export const PASSKEY_REGISTRY_SEED_PREFIX = "passkey_registry";
+
+/**
+ * A registry of DIDs for a passkey (a secp256r1 / P-256 key).
+ * Every update is authorised by a WebAuthn assertion of the passkey over a challenge
+ * (see the *Challenge functions), rather than by a transaction signer.
+ * The wallet submits, and pays for, the transactions.
+ */
+export class PasskeyRegistry extends CommonRegistry {
+  protected constructor(
+    protected wallet: Wallet,
+    connection: Connection,
+    // the compressed public key of the passkey
+    protected key: Uint8Array,
+    cluster: ExtendedCluster
+  ) {
+    // the registry is seeded by the hash of the key, as the key is too long to be a seed
+    super(sha256(key), PASSKEY_REGISTRY_SEED_PREFIX, cluster);
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Given a did count, calculate the size in bytes of the registry required to store it.
+   *
+   * NOTE: This must stay in sync with PasskeyRegistry::calculate_size in the program.
+   */
+  protected static calculateMaxCount(sizeInBytes: number): number {
+    const didSpace =
+      sizeInBytes -
+      8 - // discriminator
+      1 - // version
+      33 - // key
+      4 - // vec length field
+      4 - // rent payers length field
+      MAX_RENT_PAYERS * 40; // rent payers
+    return Math.floor(didSpace / 32);
+  }
+
+  public async analyseSpace(): Promise<{
+    count: number;
+    maxCount: number;
+    sizeBytes: number;
+  }> {
+    const registryAccountInfo =
+      await this.program.account.passkeyRegistry.getAccountInfo(
+        this.registryAddress
+      );
+
+    if (!registryAccountInfo) return { count: 0, maxCount: 0, sizeBytes: 0 };
+
+    const sizeBytes = registryAccountInfo.data.length;
+
+    const coder = this.program.account.passkeyRegistry.coder.accounts;
+    const account = coder.decode("passkeyRegistry", registryAccountInfo.data);
+    const didCount = account.dids.length;
+    const maxCount = PasskeyRegistry.calculateMaxCount(sizeBytes);
+
+    return { count: didCount, maxCount, sizeBytes };
+  }
+
+  async listDIDs(): Promise<string[]> {
+    const registryAccount =
+      await this.program.account.passkeyRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    if (!registryAccount) return [];
+
+    return registryAccount.dids.map((identifier: PublicKey) =>
+      DidSolIdentifier.create(identifier, this.cluster).toString()
+    );
+  }
+
+  /**
+   * List the parties that paid rent for the registry, in the order in which they first paid,
+   * along with the lamports each is refunded when the registry is shrunk or closed
+   */
+  async listRentPayers(): Promise<RentPayer[]> {
+    const registryAccount =
+      await this.program.account.passkeyRegistry.fetchNullable(
+        this.registryAddress
+      );
+
+    return this.toRentPayers(registryAccount?.rentPayers ?? []);
+  }
+
+  private async challenge(prefix: string, data: Uint8Array): Promise<Buffer> {
+    const nonce = await fetchMessageNonce(this.program, this.registryAddress);
+
+    return passkeyChallenge(prefix, this.registryAddress, data, nonce);
+  }
+
+  /**
+   * The challenge that the passkey must sign to register a DID.
+   * The challenge includes the registry nonce, so each assertion can only be used once.
+   */
+  registrationChallenge(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    return this.challenge(
+      PASSKEY_REGISTER_DID_PREFIX,
+      account.authority.toBuffer()
+    );
+  }
+
+  /**
+   * The challenge that the passkey must sign to remove a DID
+   */
+  removalChallenge(did: string): Promise<Buffer> {
+    const account = this.didToAccount(did);
+    return this.challenge(
+      PASSKEY_REMOVE_DID_PREFIX,
+      account.authority.toBuffer()
+    );
+  }
+
+  /**
+   * The challenge that the passkey must sign to resize the registry.
+   * The wallet pays for (or is refunded) the change in rent, so it is included in the challenge.
+   */
+  resizeChallenge(didCount: number): Promise<Buffer> {
+    const didCountBytes = Buffer.alloc(4);
+    didCountBytes.writeUInt32LE(didCount);
+    return this.challenge(
+      PASSKEY_RESIZE_PREFIX,
+      Buffer.concat([didCountBytes, this.wallet.publicKey.toBuffer()])
+    );
+  }
+
+  /**
+   * The challenge that the passkey must sign to close the registry.
+   * The wallet receives the registry rent, so it is included in the challenge.
+   */
+  closeChallenge(): Promise<Buffer> {
+    return this.challenge(
+      PASSKEY_CLOSE_PREFIX,
+      this.wallet.publicKey.toBuffer()
+    );
+  }
+
+  private signatureInstruction(
+    assertion: WebAuthnAssertion
+  ): TransactionInstruction {
+    // a WebAuthn signature is over the authenticator data and the hash of the client data
+    const signedMessage = Buffer.concat([
+      assertion.authenticatorData,
+      sha256(assertion.clientDataJSON),
+    ]);
+    return createSecp256r1Instruction(
+      this.key,
+      signedMessage,
+      p256SignatureFromDer(assertion.signature)
+    );
+  }
+
+  private static toProgramAssertion(assertion: WebAuthnAssertion) {
+    return {
+      authenticatorData: Buffer.from(assertion.authenticatorData),
+      clientDataJson: Buffer.from(assertion.clientDataJSON),
+    };
+  }
+
+  /**
+   * Register a DID against the passkey, using an assertion over the registrationChallenge.
+   * The passkey must be an authority on the DID, either directly,
+   * or through the given chain of controller DIDs.
+   */
+  register(
+    did: string,
+    assertion: WebAuthnAssertion,
+    controllerChain: string[] = []
+  ): Execution {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .registerDidWithPasskey(
+        Array.from(this.key),
+        PasskeyRegistry.toProgramAssertion(assertion),
+        account.bump
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        did: account.authority,
+        didAccount: account.account,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(this.controllerChainToAccounts(controllerChain))
+      .preInstructions([this.signatureInstruction(assertion)]);
+  }
+
+  /**
+   * Remove a DID, using an assertion over the removalChallenge
+   */
+  remove(did: string, assertion: WebAuthnAssertion): Execution {
+    const account = this.didToAccount(did);
+
+    return this.program.methods
+      .removeDidWithPasskey(PasskeyRegistry.toProgramAssertion(assertion))
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        did: account.authority,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([this.signatureInstruction(assertion)]);
+  }
+
+  /**
+   * Resize the registry to fit the given number of DIDs, using an assertion over the resizeChallenge.
+   * If it shrinks, the rent freed is refunded to the parties that paid for it.
+   */
+  async resize(
+    didCount: number,
+    assertion: WebAuthnAssertion
+  ): Promise<Execution> {
+    return this.program.methods
+      .resizePasskeyRegistry(
+        didCount,
+        PasskeyRegistry.toProgramAssertion(assertion)
+      )
+      .accounts({
+        config: configAccounts(),
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(
+        this.rentPayerAccounts(
+          await this.listRentPayers(),
+          this.wallet.publicKey,
+          []
+        )
+      )
+      .preInstructions([this.signatureInstruction(assertion)]);
+  }
+
+  /**
+   * Close the registry, using an assertion over the closeChallenge.
+   * Its rent is refunded to the parties that paid for it, and anything left over to the wallet.
+   */
+  async close(assertion: WebAuthnAssertion): Promise<Execution> {
+    return this.program.methods
+      .closePasskeyRegistry(PasskeyRegistry.toProgramAssertion(assertion))
+      .accounts({
+        registry: this.registryAddress,
+        payer: this.wallet.publicKey,
+        messageNonce: getMessageNonceAddress(this.registryAddress),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .remainingAccounts(
+        this.rentPayerAccounts(
+          await this.listRentPayers(),
+          this.wallet.publicKey,
+          []
+        )
+      )
+      .preInstructions([this.signatureInstruction(assertion)]);
+  }
+
+  /**
+   * @param key The compressed (33-byte) public key of the passkey
+   * @param wallet Submits, and pays for, the registry updates
+   */
+  static for(
+    key: Uint8Array,
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta"
+  ): PasskeyRegistry {
+    return new PasskeyRegistry(wallet, connection, key, cluster);
+  }
+}
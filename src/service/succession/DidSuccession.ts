@@ -0,0 +1,126 @@
+import { Program } from "@project-serum/anchor";
+import { Connection } from "@solana/web3.js";
+import {
+  DidSolIdentifier,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
+import { DidRegistry } from "../../types/did_registry";
+import {
+  configAccounts,
+  getSuccessionAddress,
+  makeProgram,
+} from "../../lib/util";
+import { Execution, Wallet } from "../../types";
+
+/**
+ * Record that a DID has been superseded by a successor DID, e.g. after migrating a user to a new DID.
+ * Registries containing the superseded DID can then replace it with its successor,
+ * and clients can resolve it to its successor.
+ */
+export class DidSuccession {
+  private program: Program<DidRegistry>;
+
+  private constructor(
+    private wallet: Wallet,
+    connection: Connection,
+    private cluster: ExtendedCluster
+  ) {
+    this.program = makeProgram(connection, wallet);
+  }
+
+  /**
+   * Record that the DID is superseded by the successor.
+   * The wallet must be a direct authority on both DIDs.
+   * The record can later be changed (see updateSuccessor) or removed (see revokeSuccessor).
+   */
+  setSuccessor(did: string, successor: string): Execution {
+    const didSolIdentifier = DidSolIdentifier.parse(did);
+    const [didAccount, didBump] = didSolIdentifier.dataAccount();
+    const successorSolIdentifier = DidSolIdentifier.parse(successor);
+    const [successorAccount, successorBump] =
+      successorSolIdentifier.dataAccount();
+
+    return this.program.methods
+      .setDidSuccessor(didBump, successorBump)
+      .accounts({
+        config: configAccounts(),
+        succession: getSuccessionAddress(didSolIdentifier.authority),
+        payer: this.wallet.publicKey,
+        authority: this.wallet.publicKey,
+        did: didSolIdentifier.authority,
+        didAccount,
+        successor: successorSolIdentifier.authority,
+        successorAccount,
+      });
+  }
+
+  /**
+   * Change the successor recorded for the DID.
+   * The wallet must be a direct authority on both the DID and the new successor.
+   */
+  updateSuccessor(did: string, successor: string): Execution {
+    const didSolIdentifier = DidSolIdentifier.parse(did);
+    const [didAccount, didBump] = didSolIdentifier.dataAccount();
+    const successorSolIdentifier = DidSolIdentifier.parse(successor);
+    const [successorAccount, successorBump] =
+      successorSolIdentifier.dataAccount();
+
+    return this.program.methods
+      .updateDidSuccessor(didBump, successorBump)
+      .accounts({
+        config: configAccounts(),
+        succession: getSuccessionAddress(didSolIdentifier.authority),
+        authority: this.wallet.publicKey,
+        did: didSolIdentifier.authority,
+        didAccount,
+        successor: successorSolIdentifier.authority,
+        successorAccount,
+      });
+  }
+
+  /**
+   * Remove the successor recorded for the DID. The wallet must be a direct authority on the DID.
+   * The rent of the record is refunded to the account that paid for it.
+   */
+  async revokeSuccessor(did: string): Promise<Execution> {
+    const didSolIdentifier = DidSolIdentifier.parse(did);
+    const [didAccount, didBump] = didSolIdentifier.dataAccount();
+    const succession = getSuccessionAddress(didSolIdentifier.authority);
+    const { payer } = await this.program.account.didSuccession.fetch(
+      succession
+    );
+
+    return this.program.methods.revokeDidSuccessor(didBump).accounts({
+      config: configAccounts(),
+      succession,
+      payer,
+      authority: this.wallet.publicKey,
+      did: didSolIdentifier.authority,
+      didAccount,
+    });
+  }
+
+  /**
+   * The DID that directly supersedes the given DID, or null if it has not been superseded
+   */
+  async successorOf(did: string): Promise<string | null> {
+    const succession = await this.program.account.didSuccession.fetchNullable(
+      getSuccessionAddress(DidSolIdentifier.parse(did).authority)
+    );
+
+    if (!succession) return null;
+
+    return DidSolIdentifier.create(
+      succession.successor,
+      this.cluster
+    ).toString();
+  }
+
+  static for(
+    wallet: Wallet,
+    connection: Connection,
+    cluster: ExtendedCluster = "mainnet-beta"
+  ) {
+    return new DidSuccession(wallet, connection, cluster);
+  }
+}
@@ -8,6 +8,16 @@ export type DidRegistry = {
         "Create an empty DID registry for a given solana key"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -33,6 +43,60 @@ export type DidRegistry = {
         {
           "name": "bump",
           "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "createKeyRegistryHistory",
+      "docs": [
+        "Start recording the DIDs added to and removed from an authority's registry,",
+        "keeping up to the given number of entries. An existing history is reused at its current capacity."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
         }
       ]
     },
@@ -42,6 +106,16 @@ export type DidRegistry = {
         "Add a DID to an authority's registry"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -74,6 +148,15 @@ export type DidRegistry = {
             "Specifically, the did account is checked to see if it has the authority as a signer",
             "Since it can be a generative DID, we do not use Account<DidAccount> here"
           ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
         }
       ],
       "args": [
@@ -84,11 +167,22 @@ export type DidRegistry = {
       ]
     },
     {
-      "name": "removeDid",
+      "name": "registerDidWithExpiry",
       "docs": [
-        "Remove a DID from an authority's registry"
+        "Add a DID to an authority's registry that expires at the given unix timestamp, e.g. for an event ticket.",
+        "Once expired, the DID is ignored, and can be removed by anyone with sweep_expired_dids."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -102,33 +196,6 @@ export type DidRegistry = {
             "The authority that owns the registry"
           ]
         },
-        {
-          "name": "did",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "The DID to remove from the registry"
-          ]
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "registerDidForEthAddress",
-      "docs": [
-        "Add a DID to an eth address's registry, if the solana signer is also an authority"
-      ],
-      "accounts": [
-        {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "did",
           "isMut": false,
@@ -150,51 +217,99 @@ export type DidRegistry = {
           ]
         },
         {
-          "name": "systemProgram",
+          "name": "gatewayToken",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
         }
       ],
       "args": [
-        {
-          "name": "ethAddress",
-          "type": {
-            "array": [
-              "u8",
-              20
-            ]
-          }
-        },
         {
           "name": "didBump",
           "type": "u8"
+        },
+        {
+          "name": "expiresAt",
+          "type": "i64"
         }
       ]
     },
     {
-      "name": "registerDidSignedByEthAddress",
+      "name": "removeDid",
       "docs": [
-        "Add a DID to an eth address's registry, without requiring the solana signer to be an authority on the DID"
+        "Remove a DID from an authority's registry"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidForKeys",
+      "docs": [
+        "Add a DID to the registries of many keys at once, creating any registries that do not exist yet.",
+        "The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories",
+        "of the registries that have one, in the same order. Each key must sign, and be a direct authority on the DID."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "payer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "Pays for any registries that do not exist yet"
+          ]
         },
         {
           "name": "did",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The DID to add to the registry. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the payer."
+            "The DID to add to the registries. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
           ]
         },
         {
@@ -202,9 +317,7 @@ export type DidRegistry = {
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document",
-            "This can safely be a DidAccount, rather than UncheckedAccount,",
-            "since, for the DID to include an eth address it must be a non-generative DID."
+            "The account containing the DID document"
           ]
         },
         {
@@ -214,113 +327,94 @@ export type DidRegistry = {
         }
       ],
       "args": [
-        {
-          "name": "ethAddress",
-          "type": {
-            "array": [
-              "u8",
-              20
-            ]
-          }
-        },
-        {
-          "name": "ethSignature",
-          "type": {
-            "defined": "Secp256k1RawSignature"
-          }
-        },
         {
           "name": "didBump",
           "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
         }
       ]
     },
     {
-      "name": "resizeKeyRegistry",
+      "name": "removeDidForKeys",
+      "docs": [
+        "Remove a DID from the registries of many keys at once.",
+        "The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories",
+        "of the registries that have one, in the same order. Each key must sign."
+      ],
       "accounts": [
         {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
         },
         {
-          "name": "systemProgram",
+          "name": "did",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registries"
+          ]
         }
       ],
       "args": [
         {
-          "name": "didCount",
-          "type": "u32"
+          "name": "namespace",
+          "type": "bytes"
         }
       ]
     },
     {
-      "name": "closeKeyRegistry",
-      "accounts": [
-        {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "createControllerRegistry",
+      "name": "pruneDid",
       "docs": [
-        "Create an empty controller registry for a given DID"
+        "Remove a DID from a key's registry, once the key is no longer an authority on it,",
+        "e.g. because the key was removed from the DID, or the DID account was closed.",
+        "Anyone can prune a registry. If shrink is set, the registry is shrunk by the space of one DID,",
+        "and the rent freed is refunded to the registry's rent payers, whose accounts are passed after the history, if any.",
+        "There is no bounty for pruning: the pruner receives only rent that is not owed to a rent payer,",
+        "which is none for a registry whose rent payers are all recorded.",
+        "",
+        "If the DID has controllers, the key may still be an authority on it through one of them,",
+        "so the (did, did account) pairs of all of its controllers are passed first, and the key must not be",
+        "an authority on any of them. DIDs whose controllers have controllers themselves cannot be pruned."
       ],
       "accounts": [
         {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
         },
         {
-          "name": "payer",
+          "name": "registry",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "pruner",
           "isMut": true,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "The account pruning the registry, which receives any rent freed by shrinking it that is not owed to a rent payer"
+          ]
         },
         {
           "name": "did",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The DID to create the registry for. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "The DID to remove from the registry"
           ]
         },
         {
@@ -328,34 +422,41 @@ export type DidRegistry = {
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "The account containing the DID document. This may have been closed.",
             "Since it can be a generative DID, we do not use Account<DidAccount> here"
           ]
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
+          "name": "didBump",
           "type": "u8"
         },
         {
-          "name": "didBump",
-          "type": "u8"
+          "name": "shrink",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "registerControlledDid",
+      "name": "replaceDid",
       "docs": [
-        "Add a controlled DID to an authority's controller registry"
+        "Replace a superseded DID in an authority's registry with its successor, in place.",
+        "The authority must be an authority on the successor, as when registering it.",
+        "If the registry has a removal delay, the superseded DID is removed by the replacement,",
+        "so its removal must first be requested (see request_did_removal) and the delay must have elapsed."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -370,72 +471,109 @@ export type DidRegistry = {
           ]
         },
         {
-          "name": "controlledDid",
+          "name": "succession",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "The record of the superseded DID and its successor"
           ]
         },
         {
-          "name": "controlledDidAccount",
-          "isMut": false,
+          "name": "pendingRemoval",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "The account containing the controlled DID document",
-            "This document must contain registry.did as a controller (checked by SolDid)."
+            "The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.",
+            "Otherwise it is ignored (see take_pending_removal)."
           ]
         },
         {
-          "name": "didAccount",
+          "name": "pendingRemovalPayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "successorAccount",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document that this registry applies to.",
-            "This is required, in order to check that the authority is an authority on the DID",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
-            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+            "The account containing the successor DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer"
           ]
         }
       ],
       "args": [
         {
-          "name": "didBump",
-          "type": "u8"
-        },
-        {
-          "name": "controlledDidBump",
+          "name": "successorBump",
           "type": "u8"
         }
       ]
     },
     {
-      "name": "removeControlledDid",
+      "name": "inviteDid",
       "docs": [
-        "Remove a controlled DID from a controller registry"
+        "Invite a key to register a DID, as an authority on the DID (e.g. an issuer proposing a DID to a wallet).",
+        "The invitation is listed in the key's inbox, and holds a deposit as protection against spam,",
+        "until the key accepts or rejects it, or the inviter cancels it."
       ],
       "accounts": [
         {
-          "name": "registry",
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "invitation",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent and deposit for the invitation"
+          ]
+        },
         {
           "name": "authority",
           "isMut": false,
           "isSigner": true,
           "docs": [
-            "The authority that owns the registry"
+            "An authority on the DID"
           ]
         },
         {
-          "name": "didToRemove",
+          "name": "key",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The DID to remove from the registry"
+            "The key invited to register the DID"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to register. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
           ]
         },
         {
@@ -443,11 +581,15 @@ export type DidRegistry = {
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document that this registry applies to.",
-            "This is required, in order to check that the authority is an authority on the DID",
+            "The account containing the DID document",
             "Specifically, the did account is checked to see if it has the authority as a signer",
             "Since it can be a generative DID, we do not use Account<DidAccount> here"
           ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -458,62 +600,58 @@ export type DidRegistry = {
       ]
     },
     {
-      "name": "resizeControllerRegistry",
+      "name": "acceptInvitation",
+      "docs": [
+        "Accept an invitation to register a DID, adding it to the key's registry.",
+        "As when registering a DID directly, the key must be an authority on the DID.",
+        "The rent and deposit of the invitation are refunded to the inviter."
+      ],
       "accounts": [
         {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
         },
         {
-          "name": "payer",
+          "name": "registry",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
           "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "didCount",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "closeControllerRegistry",
-      "accounts": [
-        {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
+          "isSigner": true,
+          "docs": [
+            "The key that was invited, which owns the registry"
+          ]
         },
         {
-          "name": "payer",
+          "name": "invitation",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "The invitation, whose rent and deposit are refunded to the inviter"
+          ]
         },
         {
-          "name": "authority",
+          "name": "inbox",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
         },
         {
-          "name": "did",
-          "isMut": false,
+          "name": "inviter",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "The DID whose registry is being closed. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "The account that paid for the invitation"
           ]
         },
         {
@@ -527,9 +665,13 @@ export type DidRegistry = {
           ]
         },
         {
-          "name": "systemProgram",
+          "name": "gatewayToken",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
         }
       ],
       "args": [
@@ -538,165 +680,151 @@ export type DidRegistry = {
           "type": "u8"
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "controllerRegistry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "did",
-            "type": "publicKey"
-          },
-          {
-            "name": "controlledDids",
-            "type": {
-              "vec": "publicKey"
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "keyRegistry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "authority",
-            "type": "publicKey"
-          },
-          {
-            "name": "dids",
-            "type": {
-              "vec": "publicKey"
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "Secp256k1RawSignature",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "signature",
-            "type": {
-              "array": [
-                "u8",
-                64
-              ]
-            }
-          },
-          {
-            "name": "recoveryId",
-            "type": "u8"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "DIDError",
-      "msg": "An error occurred evaluating the DID"
-    },
-    {
-      "code": 6001,
-      "name": "NotAuthority",
-      "msg": "The key is not an authority on the DID"
-    },
-    {
-      "code": 6002,
-      "name": "NotController",
-      "msg": "The registry DID is not an controller of the DID being added"
-    },
-    {
-      "code": 6003,
-      "name": "DIDRegistered",
-      "msg": "The DID is already registered"
-    },
-    {
-      "code": 6004,
-      "name": "DIDNotRegistered",
-      "msg": "Attempt to remove a DID that is not registered"
-    },
-    {
-      "code": 6005,
-      "name": "InvalidEthSignature",
-      "msg": "The Eth signature did not sign the message"
-    },
-    {
-      "code": 6006,
-      "name": "WrongEthSigner",
-      "msg": "The Eth signature was signed by the wrong address"
     },
     {
-      "code": 6007,
-      "name": "RegistryFull",
-      "msg": "The registry has exceeded its maximum size - use the resize instruction to get more space"
-    }
-  ]
-};
-
-export const IDL: DidRegistry = {
-  "version": "0.1.0",
-  "name": "did_registry",
-  "instructions": [
-    {
-      "name": "createKeyRegistry",
+      "name": "rejectInvitation",
       "docs": [
-        "Create an empty DID registry for a given solana key"
+        "Reject an invitation to register a DID. The deposit of the invitation is paid to the key,",
+        "and its rent is refunded to the inviter."
       ],
       "accounts": [
         {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
         },
         {
-          "name": "payer",
+          "name": "authority",
           "isMut": true,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "The key that was invited, which receives the deposit"
+          ]
         },
         {
-          "name": "authority",
+          "name": "invitation",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "The invitation, whose rent is refunded to the inviter"
+          ]
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the invitation"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelInvitation",
+      "docs": [
+        "Cancel an invitation to register a DID that the key has not accepted or rejected yet.",
+        "Only the inviter can cancel it, and receives the rent and deposit of the invitation back."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The account that paid for the invitation, which receives its rent and deposit back"
+          ]
+        },
+        {
+          "name": "invitation",
+          "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
         }
       ],
-      "args": [
+      "args": []
+    },
+    {
+      "name": "sweepExpiredDids",
+      "docs": [
+        "Remove all expired DIDs from a key's registry, and compact it, refunding the rent freed to the registry's",
+        "rent payers, whose accounts are passed after the history, if any. Rent not owed to a rent payer is paid to the key.",
+        "Anyone can sweep a registry."
+      ],
+      "accounts": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry, which receives any rent freed by compacting it that is not owed to a rent payer"
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "registerDid",
+      "name": "setKeyRegistryRemovalDelay",
       "docs": [
-        "Add a DID to an authority's registry"
+        "Set the number of slots that a removal from an authority's registry must wait before it can be finalised.",
+        "The delay can only be increased, so that a compromised key cannot use it to bypass the delay."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -709,46 +837,86 @@ export const IDL: DidRegistry = {
           "docs": [
             "The authority that owns the registry"
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "did",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "The DID to add to the registry. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+          "name": "removalDelay",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setKeyRegistryRequiredFlags",
+      "docs": [
+        "Set the verification method flags that a key must have on a DID in order to register it in an authority's registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
           ]
         },
         {
-          "name": "didAccount",
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
           "isMut": false,
-          "isSigner": false,
+          "isSigner": true,
           "docs": [
-            "The account containing the DID document",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
-            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+            "The authority that owns the registry"
           ]
         }
       ],
       "args": [
         {
-          "name": "didBump",
-          "type": "u8"
+          "name": "requiredFlags",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "removeDid",
+      "name": "requestDidRemoval",
       "docs": [
-        "Remove a DID from an authority's registry"
+        "Queue the removal of a DID from an authority's registry, if the registry has a removal delay"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "authority",
           "isMut": false,
@@ -764,6 +932,48 @@ export const IDL: DidRegistry = {
           "docs": [
             "The DID to remove from the registry"
           ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finalizeDidRemoval",
+      "docs": [
+        "Remove a DID from an authority's registry, once its removal delay has elapsed"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
         }
       ],
       "args": []
@@ -774,6 +984,16 @@ export const IDL: DidRegistry = {
         "Add a DID to an eth address's registry, if the solana signer is also an authority"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -823,6 +1043,10 @@ export const IDL: DidRegistry = {
         {
           "name": "didBump",
           "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
         }
       ]
     },
@@ -832,6 +1056,16 @@ export const IDL: DidRegistry = {
         "Add a DID to an eth address's registry, without requiring the solana signer to be an authority on the DID"
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -887,12 +1121,32 @@ export const IDL: DidRegistry = {
         {
           "name": "didBump",
           "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
         }
       ]
     },
     {
-      "name": "resizeKeyRegistry",
+      "name": "registerDidSignedByEthAddressViaPrecompile",
+      "docs": [
+        "Add a DID to an eth address's registry, where the eth signature is verified by the secp256k1 program",
+        "in the preceding instruction, rather than being recovered in this program, which is expensive in compute units.",
+        "Unlike register_did_signed_by_eth_address, the signed message includes the registry and its nonce,",
+        "so the signature cannot be replayed."
+      ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -904,40 +1158,40 @@ export const IDL: DidRegistry = {
           "isSigner": true
         },
         {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
         },
         {
-          "name": "systemProgram",
+          "name": "didAccount",
           "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "didCount",
-          "type": "u32"
-        }
-      ]
-    },
-    {
-      "name": "closeKeyRegistry",
-      "accounts": [
-        {
-          "name": "registry",
-          "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include an eth address it must be a non-generative DID."
+          ]
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256k1 signature instruction"
+          ]
         },
         {
-          "name": "authority",
+          "name": "messageNonce",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
         },
         {
           "name": "systemProgram",
@@ -945,14 +1199,45 @@ export const IDL: DidRegistry = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "ethAddress",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
     },
     {
-      "name": "createControllerRegistry",
+      "name": "registerDidSignedByBtcAddress",
       "docs": [
-        "Create an empty controller registry for a given DID"
+        "Add a DID to a Bitcoin address's registry, authorised by a BIP-137 signature of the registration message",
+        "by the address. P2PKH and P2WPKH addresses are supported.",
+        "The address is identified by its type and public key hash. The DID must list the address's secp256k1",
+        "public key, as an EcdsaSecp256k1VerificationKey2019 verification method with the capabilityInvocation flag."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -963,19 +1248,14 @@ export const IDL: DidRegistry = {
           "isMut": true,
           "isSigner": true
         },
-        {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
         {
           "name": "did",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The DID to create the registry for. This is the did \"identifier\", not the did account",
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
             "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "note - this may or may not be the same as the payer."
           ]
         },
         {
@@ -984,8 +1264,16 @@ export const IDL: DidRegistry = {
           "isSigner": false,
           "docs": [
             "The account containing the DID document",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
-            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include a Bitcoin address it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
           ]
         },
         {
@@ -996,8 +1284,28 @@ export const IDL: DidRegistry = {
       ],
       "args": [
         {
-          "name": "bump",
-          "type": "u8"
+          "name": "addressType",
+          "type": {
+            "defined": "BtcAddressType"
+          }
+        },
+        {
+          "name": "pubkeyHash",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              65
+            ]
+          }
         },
         {
           "name": "didBump",
@@ -1006,41 +1314,118 @@ export const IDL: DidRegistry = {
       ]
     },
     {
-      "name": "registerControlledDid",
+      "name": "removeDidForBtcAddress",
       "docs": [
-        "Add a controlled DID to an authority's controller registry"
+        "Remove a DID from a Bitcoin address's registry, authorised by a BIP-137 signature of the removal message",
+        "by the address. The DID need not list the address any more."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "did",
           "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message. It exists, since the DID was registered with a signed message"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "addressType",
+          "type": {
+            "defined": "BtcAddressType"
+          }
+        },
+        {
+          "name": "pubkeyHash",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              65
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerDidWithSignedMessage",
+      "docs": [
+        "Add a DID to a key's registry, authorised by an ed25519 signature of the key over a registration message,",
+        "rather than by the key signing the transaction. This allows a relayer to submit and pay for the registration."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
           "isSigner": true,
           "docs": [
-            "The authority that owns the registry"
+            "The relayer, paying for the transaction and, if needed, the registry"
           ]
         },
         {
-          "name": "controlledDid",
+          "name": "authority",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the registration message in the preceding ed25519 instruction"
           ]
         },
         {
-          "name": "controlledDidAccount",
+          "name": "did",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the controlled DID document",
-            "This document must contain registry.did as a controller (checked by SolDid)."
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
           ]
         },
         {
@@ -1048,11 +1433,40 @@ export const IDL: DidRegistry = {
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document that this registry applies to.",
-            "This is required, in order to check that the authority is an authority on the DID",
+            "The account containing the DID document",
             "Specifically, the did account is checked to see if it has the authority as a signer",
             "Since it can be a generative DID, we do not use Account<DidAccount> here"
           ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -1061,32 +1475,52 @@ export const IDL: DidRegistry = {
           "type": "u8"
         },
         {
-          "name": "controlledDidBump",
-          "type": "u8"
+          "name": "namespace",
+          "type": "bytes"
         }
       ]
     },
     {
-      "name": "removeControlledDid",
+      "name": "removeDidWithSignedMessage",
       "docs": [
-        "Remove a controlled DID from a controller registry"
+        "Remove a DID from a key's registry, authorised by an ed25519 signature of the key over a removal message,",
+        "rather than by the key signing the transaction."
       ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the nonce account, if it does not exist yet"
+          ]
+        },
         {
           "name": "authority",
           "isMut": false,
-          "isSigner": true,
+          "isSigner": false,
           "docs": [
-            "The authority that owns the registry"
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the removal message in the preceding ed25519 instruction"
           ]
         },
         {
-          "name": "didToRemove",
+          "name": "did",
           "isMut": false,
           "isSigner": false,
           "docs": [
@@ -1094,27 +1528,46 @@ export const IDL: DidRegistry = {
           ]
         },
         {
-          "name": "didAccount",
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document that this registry applies to.",
-            "This is required, in order to check that the authority is an authority on the DID",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
-            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+            "The instructions sysvar, used to find the ed25519 signature instruction"
           ]
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "didBump",
-          "type": "u8"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "resizeControllerRegistry",
+      "name": "resizeKeyRegistryWithSignedMessage",
+      "docs": [
+        "Resize a key's registry, authorised by an ed25519 signature of the key over a resize message,",
+        "rather than by the key signing the transaction."
+      ],
       "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
         {
           "name": "registry",
           "isMut": true,
@@ -1123,12 +1576,36 @@ export const IDL: DidRegistry = {
         {
           "name": "payer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "Pays for any extra rent, or receives the rent freed that is not owed to the registry's rent payers.",
+            "It is included in the signed message."
+          ]
         },
         {
           "name": "authority",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the resize message in the preceding ed25519 instruction"
+          ]
+        },
+        {
+          "name": "messageNonce",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
         },
         {
           "name": "systemProgram",
@@ -1144,7 +1621,11 @@ export const IDL: DidRegistry = {
       ]
     },
     {
-      "name": "closeControllerRegistry",
+      "name": "closeKeyRegistryWithSignedMessage",
+      "docs": [
+        "Close a key's registry, authorised by an ed25519 signature of the key over a close message,",
+        "rather than by the key signing the transaction."
+      ],
       "accounts": [
         {
           "name": "registry",
@@ -1154,33 +1635,77 @@ export const IDL: DidRegistry = {
         {
           "name": "payer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": true,
+          "docs": [
+            "Receives the registry rent that is not owed to its rent payers. It is included in the signed message."
+          ]
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the close message in the preceding ed25519 instruction"
+          ]
         },
         {
-          "name": "did",
-          "isMut": false,
+          "name": "messageNonce",
+          "isMut": true,
           "isSigner": false,
           "docs": [
-            "The DID whose registry is being closed. This is the did \"identifier\", not the did account",
-            "i.e. did:sol:<identifier>",
-            "note - this may or may not be the same as the authority."
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
           ]
         },
         {
-          "name": "didAccount",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "The account containing the DID document",
-            "Specifically, the did account is checked to see if it has the authority as a signer",
-            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeKeyRegistry",
+      "docs": [
+        "Resize an authority's registry. The payer pays for any extra rent,",
+        "and the rent freed by shrinking it is refunded to the parties that paid for it,",
+        "whose accounts are passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
           ]
         },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1189,15 +1714,8087 @@ export const IDL: DidRegistry = {
       ],
       "args": [
         {
-          "name": "didBump",
-          "type": "u8"
+          "name": "didCount",
+          "type": "u32"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
+    {
+      "name": "closeKeyRegistry",
+      "docs": [
+        "Close an authority's registry. Its rent is refunded to the parties that paid for it,",
+        "whose accounts are passed in the remaining accounts, and anything left over is paid to the payer.",
+        "A party that co-signs the transaction waives its refund in favour of the payer.",
+        "If the registry has a history, the removal of its remaining DIDs is recorded,",
+        "and the history is passed before the accounts of the parties that paid for it."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateKeyRegistry",
+      "docs": [
+        "Migrate a key (or eth address) registry created by the first release of the program that was filled,",
+        "leaving no room for the fields appended since, so that it cannot be loaded until it is migrated.",
+        "Registries in the original layout that were not filled load without migration.",
+        "The payer pays the rent for the appended fields. Anyone can migrate a registry."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "A registry (key or eth address) in the layout of the first release, which cannot be loaded as a KeyRegistry"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent for the space of the fields appended to the registry"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidWithPasskey",
+      "docs": [
+        "Add a DID to a passkey's registry, authorised by a WebAuthn assertion of the passkey over the",
+        "registration challenge. The assertion signature is verified by the secp256r1 program in the preceding instruction.",
+        "The registry is created if it does not yet exist."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the transaction and, if needed, the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the passkey as a verification method",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "key",
+          "type": {
+            "array": [
+              "u8",
+              33
+            ]
+          }
+        },
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidWithPasskey",
+      "docs": [
+        "Remove a DID from a passkey's registry, authorised by a WebAuthn assertion of the passkey"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the nonce account, if it does not exist yet"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "resizePasskeyRegistry",
+      "docs": [
+        "Resize a passkey's registry, authorised by a WebAuthn assertion of the passkey.",
+        "As with resize_key_registry, the payer pays for any extra rent, and the rent freed by shrinking it",
+        "is refunded to the parties that paid for it, whose accounts are passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the extra rent, or is refunded any rent freed that is not owed to a rent payer.",
+            "It is included in the passkey's challenge."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        },
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "closePasskeyRegistry",
+      "docs": [
+        "Close a passkey's registry, authorised by a WebAuthn assertion of the passkey.",
+        "Its rent is refunded to the parties that paid for it, as in close_key_registry."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Receives the registry rent that is not owed to a rent payer. It is included in the passkey's challenge."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerDidForChainAccount",
+      "docs": [
+        "Add a DID to the registry of an account on another chain, identified by its CAIP-10 account id.",
+        "The chain account must be an authority on the DID, and sign the registration message",
+        "in the format of its namespace. The registry is created if it does not yet exist."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include a chain account it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "accountId",
+          "type": "string"
+        },
+        {
+          "name": "signature",
+          "type": "bytes"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForChainAccount",
+      "docs": [
+        "Remove a DID from a chain account's registry, authorised by a signature of the removal message",
+        "by the chain account, in the format of its namespace"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "signature",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "resizeChainAccountRegistry",
+      "docs": [
+        "Grow a chain account's registry to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "createControllerRegistry",
+      "docs": [
+        "Create an empty controller registry for a given DID"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to create the registry for. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "createControllerRegistryHistory",
+      "docs": [
+        "Start recording the DIDs added to and removed from a controller registry,",
+        "keeping up to the given number of entries. An existing history is reused at its current capacity."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document of the registry's DID",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "registerControlledDid",
+      "docs": [
+        "Add a controlled DID to an authority's controller registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeControlledDid",
+      "docs": [
+        "Remove a controlled DID from a controller registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "didToRemove",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRegisterControlledDid",
+      "docs": [
+        "Add a controlled DID to the registry of its controller DID, as an authority on the controlled DID.",
+        "This allows a controlled DID to list itself, without waiting for the controller to act."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that it controls the controlled DID"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRemoveControlledDid",
+      "docs": [
+        "Remove a controlled DID from a controller registry, as an authority on the controlled DID.",
+        "This is allowed whether or not the DID is still controlled by the registry's DID.",
+        "Registries with a removal delay must use self_request_controlled_did_removal instead,",
+        "so that the other authorities on the controlled DID can cancel the removal."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "pruneControlledDid",
+      "docs": [
+        "Remove DIDs from a controller registry, once they are no longer controlled by the registry's DID,",
+        "e.g. because the controller was removed from the controlled DID.",
+        "Anyone can prune a registry. The controlled DIDs are passed in the remaining accounts,",
+        "as (did, did account) pairs, so that many entries can be pruned at once."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "replaceControlledDid",
+      "docs": [
+        "Replace a superseded DID in a controller registry with its successor, in place.",
+        "The successor must be controlled by the registry's DID, as when registering it.",
+        "If the registry has a removal delay, the superseded DID is removed by the replacement,",
+        "so its removal must first be requested (see request_controlled_did_removal) and the delay must have elapsed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry's DID"
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The record of the superseded DID and its successor"
+          ]
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.",
+            "Otherwise it is ignored (see take_pending_removal)."
+          ]
+        },
+        {
+          "name": "pendingRemovalPayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setControllerRegistryRemovalDelay",
+      "docs": [
+        "Set the number of slots that a removal from a controller registry must wait before it can be finalised.",
+        "The delay can only be increased, so that a compromised key cannot use it to bypass the delay."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "removalDelay",
+          "type": "u64"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setControllerRegistryRequiredFlags",
+      "docs": [
+        "Set the verification method flags that an authority must have on the registry DID in order to register DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "requiredFlags",
+          "type": "u16"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "requestControlledDidRemoval",
+      "docs": [
+        "Queue the removal of a controlled DID from a controller registry, if the registry has a removal delay"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didToRemove",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRequestControlledDidRemoval",
+      "docs": [
+        "Queue the removal of a controlled DID from a controller registry, as an authority on the controlled DID,",
+        "if the registry has a removal delay (see self_remove_controlled_did)."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "finalizeControlledDidRemoval",
+      "docs": [
+        "Remove a controlled DID from a controller registry, once its removal delay has elapsed"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeControllerRegistry",
+      "docs": [
+        "Resize a controller registry, as an authority on its DID. As with key registries, the payer pays for",
+        "any extra rent, and the rent freed by shrinking it is refunded to the parties that paid for it,",
+        "whose accounts are passed after the controller chain."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeControllerRegistry",
+      "docs": [
+        "Close a controller registry, refunding its rent and recording the removal of its remaining DIDs",
+        "as when closing a key registry. The history, if any, and the accounts of the parties that paid for it",
+        "are passed after the controller chain."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID whose registry is being closed. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "migrateControllerRegistry",
+      "docs": [
+        "Migrate a controller registry created by the first release of the program that was filled,",
+        "as with migrate_key_registry. Anyone can migrate a registry."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "A registry in the layout of the first release, which cannot be loaded as a ControllerRegistry"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent for the space of the fields appended to the registry"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidForExternalController",
+      "docs": [
+        "Add a DID to the registry of a non-did:sol controller DID (e.g. did:web or did:ethr).",
+        "The DID must list the controller in its other_controllers, and the authority must be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to list other controllers it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "controller",
+          "type": "string"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForExternalController",
+      "docs": [
+        "Remove a DID from the registry of a non-did:sol controller DID.",
+        "Any authority on the controlled DID can remove it, whether or not it still lists the controller."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resizeExternalControllerRegistry",
+      "docs": [
+        "Grow the registry of a non-did:sol controller DID to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "registerDidForAsset",
+      "docs": [
+        "Add a DID to an asset's registry. The holder must hold the asset, and be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The mint of the asset. Only NFTs (a supply of 1, with no decimals) can have a registry."
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the holder."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the holder, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForAsset",
+      "docs": [
+        "Remove a DID from an asset's registry. The holder must hold the asset, and be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "clearPreviousAssetHolderDids",
+      "docs": [
+        "Remove all DIDs registered by previous holders of an asset, e.g. after it has been transferred.",
+        "The current holder does not need to be an authority on the DIDs being removed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeAssetRegistry",
+      "docs": [
+        "Grow an asset's registry to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setDidSuccessor",
+      "docs": [
+        "Record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.",
+        "The authority must be a direct authority on both DIDs. The record can be updated or revoked",
+        "by an authority on the superseded DID (see update_did_successor and revoke_did_successor).",
+        "Registries containing the superseded DID can then replace it with its successor."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on both the superseded DID and its successor"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "successor",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID that supersedes it"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateDidSuccessor",
+      "docs": [
+        "Change the successor recorded for a superseded DID.",
+        "The authority must be a direct authority on both the superseded DID and the new successor,",
+        "so the new successor consents to superseding the DID, as when the record was created."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on both the superseded DID and its new successor"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "successor",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID that supersedes it from now on"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "revokeDidSuccessor",
+      "docs": [
+        "Remove the successor recorded for a DID, refunding the rent of the record to its payer.",
+        "The authority must be a direct authority on the superseded DID.",
+        "Registries that have already replaced the DID with its successor are not changed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the succession, which receives its rent back"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the superseded DID"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resizeRegistryHistory",
+      "docs": [
+        "Increase the number of entries kept in a registry's history. Anyone can pay to grow a history."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "cancelRemoval",
+      "docs": [
+        "Cancel a pending removal from a key or controller registry.",
+        "Any authority on the DID being removed can cancel it."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the DID being removed"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID being removed. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "initializeConfig",
+      "docs": [
+        "Create the program config, with the upgrade authority of the program as its admin"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initialCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "maxEntries",
+          "type": "u32"
+        },
+        {
+          "name": "registryFee",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setPaused",
+      "docs": [
+        "Pause or unpause the program. While paused, all instructions that change registries are blocked,",
+        "except for closing them."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setConfigLimits",
+      "docs": [
+        "Set the capacity of new registries, the maximum number of DIDs in a registry (0 = no maximum),",
+        "and the fee charged for creating a registry (0 = no fee).",
+        "Existing registries larger than the maximum are not affected, but cannot grow."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "initialCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "maxEntries",
+          "type": "u32"
+        },
+        {
+          "name": "registryFee",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "docs": [
+        "Propose a new admin for the program config, which becomes admin once it accepts.",
+        "Proposing the default pubkey cancels a proposal."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAdmin",
+      "docs": [
+        "Accept a proposal to become the admin of the program config"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingAdmin",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The key proposed as the next admin"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawFees",
+      "docs": [
+        "Withdraw the registry fees held in the program config"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that receives the fees"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setGatekeeperNetwork",
+      "docs": [
+        "Require a gateway token (Civic Pass) in the gatekeeper network to register DIDs",
+        "via register_did and register_controlled_did. Setting the default pubkey removes the requirement."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gatekeeperNetwork",
+          "type": "publicKey"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "assetRegistry",
+      "docs": [
+        "A registry of DIDs linked to an asset (an NFT), by the holders of the asset"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "The mint of the asset"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "vec": {
+                "defined": "AssetRegistryEntry"
+              }
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "chainAccountRegistry",
+      "docs": [
+        "A registry of DIDs for an account on another chain, identified by its CAIP-10 account id",
+        "(see https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The CAIP-2 namespace of the chain, e.g. \"eip155\""
+            ],
+            "type": "string"
+          },
+          {
+            "name": "reference",
+            "docs": [
+              "The CAIP-2 reference of the chain within its namespace, e.g. \"1\" for Ethereum mainnet"
+            ],
+            "type": "string"
+          },
+          {
+            "name": "address",
+            "docs": [
+              "The address of the account on the chain"
+            ],
+            "type": "string"
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "Incremented on each update, so that each signature can only be used once"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "controllerRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "controlledDids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "removalDelay",
+            "docs": [
+              "The number of slots a removal must wait before it can be finalised (0 = no delay)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "requiredFlags",
+            "docs": [
+              "The verification method flags that a key must have on a DID in order to register it (0 = any)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "hasHistory",
+            "docs": [
+              "Whether adds and removes are recorded in a RegistryHistory"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The application namespace of the registry, a seed of its address (empty = the default registry).",
+              "A DID has a separate controller registry in each namespace."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "didSuccession",
+      "docs": [
+        "A record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.",
+        "Created by an authority on both DIDs. An authority on the superseded DID can update or revoke it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "did",
+            "docs": [
+              "The superseded DID"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "successor",
+            "docs": [
+              "The DID that supersedes it"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "docs": [
+              "The account that paid the rent for this record, and is refunded when it is revoked"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "externalControllerRegistry",
+      "docs": [
+        "A registry of did:sol DIDs controlled by a non-did:sol DID (e.g. did:web or did:ethr),",
+        "which the controlled DIDs list in their other_controllers"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "controller",
+            "docs": [
+              "The controller DID, e.g. \"did:web:example.com\""
+            ],
+            "type": "string"
+          },
+          {
+            "name": "controlledDids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "keyRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "removalDelay",
+            "docs": [
+              "The number of slots a removal must wait before it can be finalised (0 = no delay)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "requiredFlags",
+            "docs": [
+              "The verification method flags that a key must have on a DID in order to register it (0 = any)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "ethAddressForms",
+            "docs": [
+              "For eth address registries, the form of the verification method that matched the address",
+              "for each registered DID, in the same order as dids. Empty for other registries."
+            ],
+            "type": {
+              "vec": {
+                "defined": "EthAddressForm"
+              }
+            }
+          },
+          {
+            "name": "expiries",
+            "docs": [
+              "The unix timestamp at which each registered DID expires (NO_EXPIRY = never), in the same order as dids"
+            ],
+            "type": {
+              "vec": "i64"
+            }
+          },
+          {
+            "name": "hasHistory",
+            "docs": [
+              "Whether adds and removes are recorded in a RegistryHistory"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The application namespace of the registry, a seed of its address (empty = the default registry).",
+              "A key (or eth address) has a separate registry in each namespace."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports each is refunded when it is shrunk or closed"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "messageNonce",
+      "docs": [
+        "The nonce included in the signed messages (or WebAuthn assertions) that authorise updates to a registry.",
+        "It is kept in its own account, which is never closed, so that it is not reset when the registry",
+        "is closed and created again, and each signature can only ever be used once."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry whose updates are authorised by messages including the nonce"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "Incremented on each update authorised by a signed message"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "passkeyRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "key",
+            "docs": [
+              "The compressed secp256r1 (P-256) public key of the passkey that owns the registry"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                33
+              ]
+            }
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingRemoval",
+      "docs": [
+        "A removal of a DID from a key or controller registry that is waiting for the",
+        "registry's removal delay to elapse."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry (key or controller) that the DID is being removed from"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "did",
+            "docs": [
+              "The DID being removed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "docs": [
+              "The account that paid the rent for this pending removal, and is refunded when it is closed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "unlockSlot",
+            "docs": [
+              "The slot from which the removal can be finalised"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "programConfig",
+      "docs": [
+        "The program-wide configuration, controlled by an admin key.",
+        "Until it is initialised, the defaults apply: nothing is paused, and there are no limits or fees."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "The key proposed as the next admin, which must accept before it becomes admin (default = none)"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "While set, all instructions that change registries are blocked, except for closing them"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "initialCapacity",
+            "docs": [
+              "The number of DIDs that a new key or controller registry has space for"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "maxEntries",
+            "docs": [
+              "The maximum number of DIDs that a registry can be resized to hold (0 = no maximum)"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "registryFee",
+            "docs": [
+              "The fee in lamports charged for creating a registry (0 = no fee).",
+              "Fees are held in this account until the admin withdraws them."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "gatekeeperNetwork",
+            "docs": [
+              "If set, registering a DID requires a valid gateway token (Civic Pass) in this gatekeeper network",
+              "for the authority (default = none)"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "registrationInbox",
+      "docs": [
+        "The DIDs that a key has been invited to register (see RegistrationInvitation), so that the key can find",
+        "its pending invitations from a single account. It is created by the first invitation, and never closed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "key",
+            "docs": [
+              "The key whose invitations are listed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "dids",
+            "docs": [
+              "The DIDs of the pending invitations"
+            ],
+            "type": {
+              "vec": "publicKey"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "registrationInvitation",
+      "docs": [
+        "An invitation by an authority on a DID for a key to register the DID, listed in the key's inbox",
+        "(see RegistrationInbox) to be accepted or rejected by the key, or cancelled by the inviter."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "key",
+            "docs": [
+              "The key invited to register the DID"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "did",
+            "docs": [
+              "The DID to register"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "inviter",
+            "docs": [
+              "The account that paid the rent and deposit for this invitation"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "registryHistory",
+      "docs": [
+        "The history of the DIDs added to and removed from a key or controller registry.",
+        "Once the capacity is reached, the oldest entries are overwritten."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry (key or controller) that this history records"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "capacity",
+            "docs": [
+              "The maximum number of entries kept"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "next",
+            "docs": [
+              "Once the history is full, the index of the oldest entry, which is overwritten next"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "vec": {
+                "defined": "HistoryEntry"
+              }
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "AssetRegistryEntry",
+      "docs": [
+        "A DID registered against an asset, along with the holder of the asset that registered it"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "holder",
+            "docs": [
+              "The holder of the asset at the time the DID was registered"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Secp256k1RawSignature",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "signature",
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          },
+          {
+            "name": "recoveryId",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "WebAuthnAssertion",
+      "docs": [
+        "The parts of a WebAuthn assertion that the passkey signed, alongside the signature itself",
+        "(which is verified by the secp256r1 program)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authenticatorData",
+            "type": "bytes"
+          },
+          {
+            "name": "clientDataJson",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BtcAddressType",
+      "docs": [
+        "The types of Bitcoin address that can be registered"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "P2pkh"
+          },
+          {
+            "name": "P2wpkh"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EthAddressForm",
+      "docs": [
+        "The form of the verification method through which an eth address is an authority on a DID"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Address"
+          },
+          {
+            "name": "PublicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HistoryEntry",
+      "docs": [
+        "A record of a DID being added to or removed from a registry"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": "HistoryAction"
+            }
+          },
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "signer",
+            "docs": [
+              "The key that authorised the change, or the default pubkey if anyone could make it (e.g. a sweep)"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HistoryAction",
+      "docs": [
+        "A change to the DIDs in a registry"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Add"
+          },
+          {
+            "name": "Remove"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RentPayer",
+      "docs": [
+        "A party that paid rent for a registry, and the lamports it is owed back when the registry is shrunk or closed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "DIDError",
+      "msg": "An error occurred evaluating the DID"
+    },
+    {
+      "code": 6001,
+      "name": "NotAuthority",
+      "msg": "The key is not an authority on the DID"
+    },
+    {
+      "code": 6002,
+      "name": "NotController",
+      "msg": "The registry DID is not an controller of the DID being added"
+    },
+    {
+      "code": 6003,
+      "name": "DIDRegistered",
+      "msg": "The DID is already registered"
+    },
+    {
+      "code": 6004,
+      "name": "DIDNotRegistered",
+      "msg": "Attempt to remove a DID that is not registered"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidEthSignature",
+      "msg": "The Eth signature did not sign the message"
+    },
+    {
+      "code": 6006,
+      "name": "WrongEthSigner",
+      "msg": "The Eth signature was signed by the wrong address"
+    },
+    {
+      "code": 6007,
+      "name": "RegistryFull",
+      "msg": "The registry has exceeded its maximum size - use the resize instruction to get more space"
+    },
+    {
+      "code": 6008,
+      "name": "RemovalDelayed",
+      "msg": "The registry has a removal delay - request the removal and finalise it once the delay has elapsed"
+    },
+    {
+      "code": 6009,
+      "name": "RemovalNotDelayed",
+      "msg": "The registry has no removal delay - remove the DID directly"
+    },
+    {
+      "code": 6010,
+      "name": "RemovalLocked",
+      "msg": "The removal delay has not yet elapsed"
+    },
+    {
+      "code": 6011,
+      "name": "RemovalDelayDecreased",
+      "msg": "The removal delay cannot be decreased"
+    },
+    {
+      "code": 6012,
+      "name": "RemovalDelayOverflow",
+      "msg": "The removal delay is too large"
+    },
+    {
+      "code": 6013,
+      "name": "RegistryNotEmpty",
+      "msg": "The registry cannot be closed while it contains DIDs protected by a removal delay"
+    },
+    {
+      "code": 6014,
+      "name": "ControllerChainTooLong",
+      "msg": "The controller chain exceeds the maximum length"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidFlags",
+      "msg": "Unknown verification method flags"
+    },
+    {
+      "code": 6016,
+      "name": "MissingRequiredFlags",
+      "msg": "The key does not have the verification method flags required by the registry"
+    },
+    {
+      "code": 6017,
+      "name": "MissingSignatureInstruction",
+      "msg": "The transaction must include a signature verification instruction immediately before this instruction"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidSignatureInstruction",
+      "msg": "The signature verification instruction must contain a single signature, with its data in the same instruction"
+    },
+    {
+      "code": 6019,
+      "name": "WrongSigner",
+      "msg": "The message was signed by the wrong key"
+    },
+    {
+      "code": 6020,
+      "name": "WrongMessage",
+      "msg": "The signed message does not match the expected message"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidWebAuthnAssertion",
+      "msg": "The WebAuthn assertion is not a valid assertion by a present user"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidAccountId",
+      "msg": "The account id is not a valid CAIP-10 account id"
+    },
+    {
+      "code": 6023,
+      "name": "UnsupportedNamespace",
+      "msg": "Signatures by accounts of this CAIP-2 namespace are not supported"
+    },
+    {
+      "code": 6024,
+      "name": "RegistryShrink",
+      "msg": "The registry can only grow"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidBtcSignature",
+      "msg": "The Bitcoin signature is not a valid BIP-137 signature for the address type"
+    },
+    {
+      "code": 6026,
+      "name": "WrongBtcSigner",
+      "msg": "The Bitcoin signature was signed by the wrong address"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidAsset",
+      "msg": "The asset must be an NFT, with a supply of 1 and no decimals"
+    },
+    {
+      "code": 6028,
+      "name": "NotAssetHolder",
+      "msg": "The signer does not hold the asset"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidKeyRegistryPairs",
+      "msg": "Keys and their registries must be passed as (key, registry) pairs"
+    },
+    {
+      "code": 6030,
+      "name": "StillAuthority",
+      "msg": "The key is still an authority on the DID"
+    },
+    {
+      "code": 6031,
+      "name": "DIDHasControllers",
+      "msg": "The DID has controllers - pass all of them, which must not have controllers themselves"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidDidAccountPairs",
+      "msg": "DIDs and their accounts must be passed as (did, did account) pairs"
+    },
+    {
+      "code": 6033,
+      "name": "StillControlled",
+      "msg": "The DID is still controlled by the registry's DID"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidSuccessor",
+      "msg": "A DID cannot be its own successor"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidExpiry",
+      "msg": "The expiry must be in the future"
+    },
+    {
+      "code": 6036,
+      "name": "NoExpiredDids",
+      "msg": "The registry contains no expired DIDs"
+    },
+    {
+      "code": 6037,
+      "name": "HistoryRequired",
+      "msg": "The registry records its history, so the history must be passed after the other remaining accounts"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidHistory",
+      "msg": "The history is not the history of the registry"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidHistoryCapacity",
+      "msg": "The history capacity must be greater than zero, and can only be increased"
+    },
+    {
+      "code": 6040,
+      "name": "ProgramPaused",
+      "msg": "The program is paused"
+    },
+    {
+      "code": 6041,
+      "name": "RegistryLimitExceeded",
+      "msg": "The registry would exceed the maximum number of DIDs"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidConfig",
+      "msg": "The program config is invalid"
+    },
+    {
+      "code": 6043,
+      "name": "NotPendingAdmin",
+      "msg": "The signer is not the proposed admin"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidGatewayToken",
+      "msg": "The authority does not have a valid gateway token in the required gatekeeper network"
+    },
+    {
+      "code": 6045,
+      "name": "TooManyRentPayers",
+      "msg": "The registry has recorded the maximum number of rent payers"
+    },
+    {
+      "code": 6046,
+      "name": "RentPayerMissing",
+      "msg": "The accounts of the registry's rent payers must be passed after the other remaining accounts"
+    },
+    {
+      "code": 6047,
+      "name": "GatewayTokenNotSupported",
+      "msg": "The program config requires a gateway token, which cannot be verified for this authority"
+    },
+    {
+      "code": 6048,
+      "name": "NamespaceTooLong",
+      "msg": "The namespace is longer than the maximum length of a namespace"
+    },
+    {
+      "code": 6049,
+      "name": "RegistryNotLegacy",
+      "msg": "The registry can be loaded in its current layout, so it does not need to be migrated"
+    },
+    {
+      "code": 6050,
+      "name": "InboxFull",
+      "msg": "The key has too many pending invitations - it must accept or reject some first"
+    }
+  ]
+};
+
+export const IDL: DidRegistry = {
+  "version": "0.1.0",
+  "name": "did_registry",
+  "instructions": [
+    {
+      "name": "createKeyRegistry",
+      "docs": [
+        "Create an empty DID registry for a given solana key"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "createKeyRegistryHistory",
+      "docs": [
+        "Start recording the DIDs added to and removed from an authority's registry,",
+        "keeping up to the given number of entries. An existing history is reused at its current capacity."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "registerDid",
+      "docs": [
+        "Add a DID to an authority's registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "registerDidWithExpiry",
+      "docs": [
+        "Add a DID to an authority's registry that expires at the given unix timestamp, e.g. for an event ticket.",
+        "Once expired, the DID is ignored, and can be removed by anyone with sweep_expired_dids."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "expiresAt",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "removeDid",
+      "docs": [
+        "Remove a DID from an authority's registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidForKeys",
+      "docs": [
+        "Add a DID to the registries of many keys at once, creating any registries that do not exist yet.",
+        "The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories",
+        "of the registries that have one, in the same order. Each key must sign, and be a direct authority on the DID."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for any registries that do not exist yet"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registries. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForKeys",
+      "docs": [
+        "Remove a DID from the registries of many keys at once.",
+        "The keys are passed in the remaining accounts as (key, registry) pairs, followed by the histories",
+        "of the registries that have one, in the same order. Each key must sign."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registries"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "pruneDid",
+      "docs": [
+        "Remove a DID from a key's registry, once the key is no longer an authority on it,",
+        "e.g. because the key was removed from the DID, or the DID account was closed.",
+        "Anyone can prune a registry. If shrink is set, the registry is shrunk by the space of one DID,",
+        "and the rent freed is refunded to the registry's rent payers, whose accounts are passed after the history, if any.",
+        "There is no bounty for pruning: the pruner receives only rent that is not owed to a rent payer,",
+        "which is none for a registry whose rent payers are all recorded.",
+        "",
+        "If the DID has controllers, the key may still be an authority on it through one of them,",
+        "so the (did, did account) pairs of all of its controllers are passed first, and the key must not be",
+        "an authority on any of them. DIDs whose controllers have controllers themselves cannot be pruned."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pruner",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The account pruning the registry, which receives any rent freed by shrinking it that is not owed to a rent payer"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document. This may have been closed.",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "shrink",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "replaceDid",
+      "docs": [
+        "Replace a superseded DID in an authority's registry with its successor, in place.",
+        "The authority must be an authority on the successor, as when registering it.",
+        "If the registry has a removal delay, the superseded DID is removed by the replacement,",
+        "so its removal must first be requested (see request_did_removal) and the delay must have elapsed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The record of the superseded DID and its successor"
+          ]
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.",
+            "Otherwise it is ignored (see take_pending_removal)."
+          ]
+        },
+        {
+          "name": "pendingRemovalPayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "inviteDid",
+      "docs": [
+        "Invite a key to register a DID, as an authority on the DID (e.g. an issuer proposing a DID to a wallet).",
+        "The invitation is listed in the key's inbox, and holds a deposit as protection against spam,",
+        "until the key accepts or rejects it, or the inviter cancels it."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "invitation",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent and deposit for the invitation"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the DID"
+          ]
+        },
+        {
+          "name": "key",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key invited to register the DID"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to register. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "acceptInvitation",
+      "docs": [
+        "Accept an invitation to register a DID, adding it to the key's registry.",
+        "As when registering a DID directly, the key must be an authority on the DID.",
+        "The rent and deposit of the invitation are refunded to the inviter."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The key that was invited, which owns the registry"
+          ]
+        },
+        {
+          "name": "invitation",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The invitation, whose rent and deposit are refunded to the inviter"
+          ]
+        },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the invitation"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "rejectInvitation",
+      "docs": [
+        "Reject an invitation to register a DID. The deposit of the invitation is paid to the key,",
+        "and its rent is refunded to the inviter."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The key that was invited, which receives the deposit"
+          ]
+        },
+        {
+          "name": "invitation",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The invitation, whose rent is refunded to the inviter"
+          ]
+        },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the invitation"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelInvitation",
+      "docs": [
+        "Cancel an invitation to register a DID that the key has not accepted or rejected yet.",
+        "Only the inviter can cancel it, and receives the rent and deposit of the invitation back."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "inviter",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The account that paid for the invitation, which receives its rent and deposit back"
+          ]
+        },
+        {
+          "name": "invitation",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "inbox",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The inbox of the key, which lists the invitation"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweepExpiredDids",
+      "docs": [
+        "Remove all expired DIDs from a key's registry, and compact it, refunding the rent freed to the registry's",
+        "rent payers, whose accounts are passed after the history, if any. Rent not owed to a rent payer is paid to the key.",
+        "Anyone can sweep a registry."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry, which receives any rent freed by compacting it that is not owed to a rent payer"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setKeyRegistryRemovalDelay",
+      "docs": [
+        "Set the number of slots that a removal from an authority's registry must wait before it can be finalised.",
+        "The delay can only be increased, so that a compromised key cannot use it to bypass the delay."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "removalDelay",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setKeyRegistryRequiredFlags",
+      "docs": [
+        "Set the verification method flags that a key must have on a DID in order to register it in an authority's registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "requiredFlags",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "requestDidRemoval",
+      "docs": [
+        "Queue the removal of a DID from an authority's registry, if the registry has a removal delay"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finalizeDidRemoval",
+      "docs": [
+        "Remove a DID from an authority's registry, once its removal delay has elapsed"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidForEthAddress",
+      "docs": [
+        "Add a DID to an eth address's registry, if the solana signer is also an authority"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "ethAddress",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "registerDidSignedByEthAddress",
+      "docs": [
+        "Add a DID to an eth address's registry, without requiring the solana signer to be an authority on the DID"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include an eth address it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "ethAddress",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "ethSignature",
+          "type": {
+            "defined": "Secp256k1RawSignature"
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "registerDidSignedByEthAddressViaPrecompile",
+      "docs": [
+        "Add a DID to an eth address's registry, where the eth signature is verified by the secp256k1 program",
+        "in the preceding instruction, rather than being recovered in this program, which is expensive in compute units.",
+        "Unlike register_did_signed_by_eth_address, the signed message includes the registry and its nonce,",
+        "so the signature cannot be replayed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include an eth address it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256k1 signature instruction"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "ethAddress",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "registerDidSignedByBtcAddress",
+      "docs": [
+        "Add a DID to a Bitcoin address's registry, authorised by a BIP-137 signature of the registration message",
+        "by the address. P2PKH and P2WPKH addresses are supported.",
+        "The address is identified by its type and public key hash. The DID must list the address's secp256k1",
+        "public key, as an EcdsaSecp256k1VerificationKey2019 verification method with the capabilityInvocation flag."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include a Bitcoin address it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "addressType",
+          "type": {
+            "defined": "BtcAddressType"
+          }
+        },
+        {
+          "name": "pubkeyHash",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              65
+            ]
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForBtcAddress",
+      "docs": [
+        "Remove a DID from a Bitcoin address's registry, authorised by a BIP-137 signature of the removal message",
+        "by the address. The DID need not list the address any more."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message. It exists, since the DID was registered with a signed message"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "addressType",
+          "type": {
+            "defined": "BtcAddressType"
+          }
+        },
+        {
+          "name": "pubkeyHash",
+          "type": {
+            "array": [
+              "u8",
+              20
+            ]
+          }
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              65
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerDidWithSignedMessage",
+      "docs": [
+        "Add a DID to a key's registry, authorised by an ed25519 signature of the key over a registration message,",
+        "rather than by the key signing the transaction. This allows a relayer to submit and pay for the registration."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The relayer, paying for the transaction and, if needed, the registry"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the registration message in the preceding ed25519 instruction"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "removeDidWithSignedMessage",
+      "docs": [
+        "Remove a DID from a key's registry, authorised by an ed25519 signature of the key over a removal message,",
+        "rather than by the key signing the transaction."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the nonce account, if it does not exist yet"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the removal message in the preceding ed25519 instruction"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeKeyRegistryWithSignedMessage",
+      "docs": [
+        "Resize a key's registry, authorised by an ed25519 signature of the key over a resize message,",
+        "rather than by the key signing the transaction."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for any extra rent, or receives the rent freed that is not owed to the registry's rent payers.",
+            "It is included in the signed message."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the resize message in the preceding ed25519 instruction"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "closeKeyRegistryWithSignedMessage",
+      "docs": [
+        "Close a key's registry, authorised by an ed25519 signature of the key over a close message,",
+        "rather than by the key signing the transaction."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Receives the registry rent that is not owed to its rent payers. It is included in the signed message."
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The key that owns the registry. It does not sign the transaction,",
+            "but signs the close message in the preceding ed25519 instruction"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the signed message, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the ed25519 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeKeyRegistry",
+      "docs": [
+        "Resize an authority's registry. The payer pays for any extra rent,",
+        "and the rent freed by shrinking it is refunded to the parties that paid for it,",
+        "whose accounts are passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "closeKeyRegistry",
+      "docs": [
+        "Close an authority's registry. Its rent is refunded to the parties that paid for it,",
+        "whose accounts are passed in the remaining accounts, and anything left over is paid to the payer.",
+        "A party that co-signs the transaction waives its refund in favour of the payer.",
+        "If the registry has a history, the removal of its remaining DIDs is recorded,",
+        "and the history is passed before the accounts of the parties that paid for it."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateKeyRegistry",
+      "docs": [
+        "Migrate a key (or eth address) registry created by the first release of the program that was filled,",
+        "leaving no room for the fields appended since, so that it cannot be loaded until it is migrated.",
+        "Registries in the original layout that were not filled load without migration.",
+        "The payer pays the rent for the appended fields. Anyone can migrate a registry."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "A registry (key or eth address) in the layout of the first release, which cannot be loaded as a KeyRegistry"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent for the space of the fields appended to the registry"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidWithPasskey",
+      "docs": [
+        "Add a DID to a passkey's registry, authorised by a WebAuthn assertion of the passkey over the",
+        "registration challenge. The assertion signature is verified by the secp256r1 program in the preceding instruction.",
+        "The registry is created if it does not yet exist."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the transaction and, if needed, the registry"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the passkey as a verification method",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "key",
+          "type": {
+            "array": [
+              "u8",
+              33
+            ]
+          }
+        },
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidWithPasskey",
+      "docs": [
+        "Remove a DID from a passkey's registry, authorised by a WebAuthn assertion of the passkey"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the nonce account, if it does not exist yet"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "resizePasskeyRegistry",
+      "docs": [
+        "Resize a passkey's registry, authorised by a WebAuthn assertion of the passkey.",
+        "As with resize_key_registry, the payer pays for any extra rent, and the rent freed by shrinking it",
+        "is refunded to the parties that paid for it, whose accounts are passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays for the extra rent, or is refunded any rent freed that is not owed to a rent payer.",
+            "It is included in the passkey's challenge."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        },
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "closePasskeyRegistry",
+      "docs": [
+        "Close a passkey's registry, authorised by a WebAuthn assertion of the passkey.",
+        "Its rent is refunded to the parties that paid for it, as in close_key_registry."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Receives the registry rent that is not owed to a rent payer. It is included in the passkey's challenge."
+          ]
+        },
+        {
+          "name": "messageNonce",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The nonce included in the passkey's challenge, kept apart from the registry so that it survives closing it"
+          ]
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The instructions sysvar, used to find the secp256r1 signature instruction"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "assertion",
+          "type": {
+            "defined": "WebAuthnAssertion"
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerDidForChainAccount",
+      "docs": [
+        "Add a DID to the registry of an account on another chain, identified by its CAIP-10 account id.",
+        "The chain account must be an authority on the DID, and sign the registration message",
+        "in the format of its namespace. The registry is created if it does not yet exist."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the payer."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to include a chain account it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "accountId",
+          "type": "string"
+        },
+        {
+          "name": "signature",
+          "type": "bytes"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForChainAccount",
+      "docs": [
+        "Remove a DID from a chain account's registry, authorised by a signature of the removal message",
+        "by the chain account, in the format of its namespace"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "signature",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "resizeChainAccountRegistry",
+      "docs": [
+        "Grow a chain account's registry to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "createControllerRegistry",
+      "docs": [
+        "Create an empty controller registry for a given DID"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to create the registry for. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u8"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "namespace",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "createControllerRegistryHistory",
+      "docs": [
+        "Start recording the DIDs added to and removed from a controller registry,",
+        "keeping up to the given number of entries. An existing history is reused at its current capacity."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document of the registry's DID",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "registerControlledDid",
+      "docs": [
+        "Add a controlled DID to an authority's controller registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeControlledDid",
+      "docs": [
+        "Remove a controlled DID from a controller registry"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The authority that owns the registry"
+          ]
+        },
+        {
+          "name": "didToRemove",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRegisterControlledDid",
+      "docs": [
+        "Add a controlled DID to the registry of its controller DID, as an authority on the controlled DID.",
+        "This allows a controlled DID to list itself, without waiting for the controller to act."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that it controls the controlled DID"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRemoveControlledDid",
+      "docs": [
+        "Remove a controlled DID from a controller registry, as an authority on the controlled DID.",
+        "This is allowed whether or not the DID is still controlled by the registry's DID.",
+        "Registries with a removal delay must use self_request_controlled_did_removal instead,",
+        "so that the other authorities on the controlled DID can cancel the removal."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "pruneControlledDid",
+      "docs": [
+        "Remove DIDs from a controller registry, once they are no longer controlled by the registry's DID,",
+        "e.g. because the controller was removed from the controlled DID.",
+        "Anyone can prune a registry. The controlled DIDs are passed in the remaining accounts,",
+        "as (did, did account) pairs, so that many entries can be pruned at once."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "replaceControlledDid",
+      "docs": [
+        "Replace a superseded DID in a controller registry with its successor, in place.",
+        "The successor must be controlled by the registry's DID, as when registering it.",
+        "If the registry has a removal delay, the superseded DID is removed by the replacement,",
+        "so its removal must first be requested (see request_controlled_did_removal) and the delay must have elapsed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry's DID"
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The record of the superseded DID and its successor"
+          ]
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal of the superseded DID, if the registry has a removal delay, which must have elapsed.",
+            "Otherwise it is ignored (see take_pending_removal)."
+          ]
+        },
+        {
+          "name": "pendingRemovalPayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document",
+            "This document must contain registry.did as a controller (checked by SolDid)."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setControllerRegistryRemovalDelay",
+      "docs": [
+        "Set the number of slots that a removal from a controller registry must wait before it can be finalised.",
+        "The delay can only be increased, so that a compromised key cannot use it to bypass the delay."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "removalDelay",
+          "type": "u64"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setControllerRegistryRequiredFlags",
+      "docs": [
+        "Set the verification method flags that an authority must have on the registry DID in order to register DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "requiredFlags",
+          "type": "u16"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "requestControlledDidRemoval",
+      "docs": [
+        "Queue the removal of a controlled DID from a controller registry, if the registry has a removal delay"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didToRemove",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "selfRequestControlledDidRemoval",
+      "docs": [
+        "Queue the removal of a controlled DID from a controller registry, as an authority on the controlled DID,",
+        "if the registry has a removal delay (see self_remove_controlled_did)."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The pending removal, which can be finalised once the registry's removal delay has elapsed"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "finalizeControlledDidRemoval",
+      "docs": [
+        "Remove a controlled DID from a controller registry, once its removal delay has elapsed"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeControllerRegistry",
+      "docs": [
+        "Resize a controller registry, as an authority on its DID. As with key registries, the payer pays for",
+        "any extra rent, and the rent freed by shrinking it is refunded to the parties that paid for it,",
+        "whose accounts are passed after the controller chain."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the registry DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document that this registry applies to.",
+            "This is required, in order to check that the authority is an authority on the DID",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        },
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closeControllerRegistry",
+      "docs": [
+        "Close a controller registry, refunding its rent and recording the removal of its remaining DIDs",
+        "as when closing a key registry. The history, if any, and the accounts of the parties that paid for it",
+        "are passed after the controller chain."
+      ],
+      "accounts": [
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID whose registry is being closed. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the authority."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "migrateControllerRegistry",
+      "docs": [
+        "Migrate a controller registry created by the first release of the program that was filled,",
+        "as with migrate_key_registry. Anyone can migrate a registry."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "A registry in the layout of the first release, which cannot be loaded as a ControllerRegistry"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays the rent for the space of the fields appended to the registry"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerDidForExternalController",
+      "docs": [
+        "Add a DID to the registry of a non-did:sol controller DID (e.g. did:web or did:ethr).",
+        "The DID must list the controller in its other_controllers, and the authority must be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled did to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document",
+            "This can safely be a DidAccount, rather than UncheckedAccount,",
+            "since, for the DID to list other controllers it must be a non-generative DID."
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the authority, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "controller",
+          "type": "string"
+        },
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForExternalController",
+      "docs": [
+        "Remove a DID from the registry of a non-did:sol controller DID.",
+        "Any authority on the controlled DID can remove it, whether or not it still lists the controller."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the controlled DID"
+          ]
+        },
+        {
+          "name": "controlledDid",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The controlled DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "controlledDidAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the controlled DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "controlledDidBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resizeExternalControllerRegistry",
+      "docs": [
+        "Grow the registry of a non-did:sol controller DID to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "registerDidForAsset",
+      "docs": [
+        "Add a DID to an asset's registry. The holder must hold the asset, and be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": true,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The mint of the asset. Only NFTs (a supply of 1, with no decimals) can have a registry."
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to add to the registry. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>",
+            "note - this may or may not be the same as the holder."
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document"
+          ]
+        },
+        {
+          "name": "gatewayToken",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The gateway token of the holder, if the program config requires one (see verify_gateway_token).",
+            "Otherwise, this can be any account, e.g. the program."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "removeDidForAsset",
+      "docs": [
+        "Remove a DID from an asset's registry. The holder must hold the asset, and be an authority on the DID,",
+        "either directly, or through the chain of controller DIDs passed in the remaining accounts."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID to remove from the registry"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "clearPreviousAssetHolderDids",
+      "docs": [
+        "Remove all DIDs registered by previous holders of an asset, e.g. after it has been transferred.",
+        "The current holder does not need to be an authority on the DIDs being removed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "holder",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The current holder of the asset"
+          ]
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The token account of the holder that holds the asset"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resizeAssetRegistry",
+      "docs": [
+        "Grow an asset's registry to fit the given number of DIDs"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "registry",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didCount",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "setDidSuccessor",
+      "docs": [
+        "Record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.",
+        "The authority must be a direct authority on both DIDs. The record can be updated or revoked",
+        "by an authority on the superseded DID (see update_did_successor and revoke_did_successor).",
+        "Registries containing the superseded DID can then replace it with its successor."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on both the superseded DID and its successor"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "successor",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID that supersedes it"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "updateDidSuccessor",
+      "docs": [
+        "Change the successor recorded for a superseded DID.",
+        "The authority must be a direct authority on both the superseded DID and the new successor,",
+        "so the new successor consents to superseding the DID, as when the record was created."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on both the superseded DID and its new successor"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        },
+        {
+          "name": "successor",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID that supersedes it from now on"
+          ]
+        },
+        {
+          "name": "successorAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the successor DID document"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        },
+        {
+          "name": "successorBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "revokeDidSuccessor",
+      "docs": [
+        "Remove the successor recorded for a DID, refunding the rent of the record to its payer.",
+        "The authority must be a direct authority on the superseded DID.",
+        "Registries that have already replaced the DID with its successor are not changed."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "succession",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the succession, which receives its rent back"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the superseded DID"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The superseded DID"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the superseded DID document",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resizeRegistryHistory",
+      "docs": [
+        "Increase the number of entries kept in a registry's history. Anyone can pay to grow a history."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "history",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "capacity",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "cancelRemoval",
+      "docs": [
+        "Cancel a pending removal from a key or controller registry.",
+        "Any authority on the DID being removed can cancel it."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "accounts": [
+            {
+              "name": "config",
+              "isMut": false,
+              "isSigner": false
+            }
+          ]
+        },
+        {
+          "name": "pendingRemoval",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that paid for the pending removal, which receives its rent back"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "An authority on the DID being removed"
+          ]
+        },
+        {
+          "name": "did",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The DID being removed. This is the did \"identifier\", not the did account",
+            "i.e. did:sol:<identifier>"
+          ]
+        },
+        {
+          "name": "didAccount",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The account containing the DID document",
+            "Specifically, the did account is checked to see if it has the authority as a signer",
+            "Since it can be a generative DID, we do not use Account<DidAccount> here"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "didBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "initializeConfig",
+      "docs": [
+        "Create the program config, with the upgrade authority of the program as its admin"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "program",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "programData",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "initialCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "maxEntries",
+          "type": "u32"
+        },
+        {
+          "name": "registryFee",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setPaused",
+      "docs": [
+        "Pause or unpause the program. While paused, all instructions that change registries are blocked,",
+        "except for closing them."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setConfigLimits",
+      "docs": [
+        "Set the capacity of new registries, the maximum number of DIDs in a registry (0 = no maximum),",
+        "and the fee charged for creating a registry (0 = no fee).",
+        "Existing registries larger than the maximum are not affected, but cannot grow."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "initialCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "maxEntries",
+          "type": "u32"
+        },
+        {
+          "name": "registryFee",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "proposeAdmin",
+      "docs": [
+        "Propose a new admin for the program config, which becomes admin once it accepts.",
+        "Proposing the default pubkey cancels a proposal."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "acceptAdmin",
+      "docs": [
+        "Accept a proposal to become the admin of the program config"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "pendingAdmin",
+          "isMut": false,
+          "isSigner": true,
+          "docs": [
+            "The key proposed as the next admin"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawFees",
+      "docs": [
+        "Withdraw the registry fees held in the program config"
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The account that receives the fees"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setGatekeeperNetwork",
+      "docs": [
+        "Require a gateway token (Civic Pass) in the gatekeeper network to register DIDs",
+        "via register_did and register_controlled_did. Setting the default pubkey removes the requirement."
+      ],
+      "accounts": [
+        {
+          "name": "config",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "gatekeeperNetwork",
+          "type": "publicKey"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "assetRegistry",
+      "docs": [
+        "A registry of DIDs linked to an asset (an NFT), by the holders of the asset"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "mint",
+            "docs": [
+              "The mint of the asset"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "entries",
+            "type": {
+              "vec": {
+                "defined": "AssetRegistryEntry"
+              }
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "chainAccountRegistry",
+      "docs": [
+        "A registry of DIDs for an account on another chain, identified by its CAIP-10 account id",
+        "(see https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The CAIP-2 namespace of the chain, e.g. \"eip155\""
+            ],
+            "type": "string"
+          },
+          {
+            "name": "reference",
+            "docs": [
+              "The CAIP-2 reference of the chain within its namespace, e.g. \"1\" for Ethereum mainnet"
+            ],
+            "type": "string"
+          },
+          {
+            "name": "address",
+            "docs": [
+              "The address of the account on the chain"
+            ],
+            "type": "string"
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "Incremented on each update, so that each signature can only be used once"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "controllerRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "controlledDids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "removalDelay",
+            "docs": [
+              "The number of slots a removal must wait before it can be finalised (0 = no delay)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "requiredFlags",
+            "docs": [
+              "The verification method flags that a key must have on a DID in order to register it (0 = any)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "hasHistory",
+            "docs": [
+              "Whether adds and removes are recorded in a RegistryHistory"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The application namespace of the registry, a seed of its address (empty = the default registry).",
+              "A DID has a separate controller registry in each namespace."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "didSuccession",
+      "docs": [
+        "A record that a DID has been superseded by a successor DID, e.g. after migrating to a new DID.",
+        "Created by an authority on both DIDs. An authority on the superseded DID can update or revoke it."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "did",
+            "docs": [
+              "The superseded DID"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "successor",
+            "docs": [
+              "The DID that supersedes it"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "docs": [
+              "The account that paid the rent for this record, and is refunded when it is revoked"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "externalControllerRegistry",
+      "docs": [
+        "A registry of did:sol DIDs controlled by a non-did:sol DID (e.g. did:web or did:ethr),",
+        "which the controlled DIDs list in their other_controllers"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "controller",
+            "docs": [
+              "The controller DID, e.g. \"did:web:example.com\""
+            ],
+            "type": "string"
+          },
+          {
+            "name": "controlledDids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "keyRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "authority",
+            "type": "publicKey"
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "removalDelay",
+            "docs": [
+              "The number of slots a removal must wait before it can be finalised (0 = no delay)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "requiredFlags",
+            "docs": [
+              "The verification method flags that a key must have on a DID in order to register it (0 = any)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "ethAddressForms",
+            "docs": [
+              "For eth address registries, the form of the verification method that matched the address",
+              "for each registered DID, in the same order as dids. Empty for other registries."
+            ],
+            "type": {
+              "vec": {
+                "defined": "EthAddressForm"
+              }
+            }
+          },
+          {
+            "name": "expiries",
+            "docs": [
+              "The unix timestamp at which each registered DID expires (NO_EXPIRY = never), in the same order as dids"
+            ],
+            "type": {
+              "vec": "i64"
+            }
+          },
+          {
+            "name": "hasHistory",
+            "docs": [
+              "Whether adds and removes are recorded in a RegistryHistory"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "namespace",
+            "docs": [
+              "The application namespace of the registry, a seed of its address (empty = the default registry).",
+              "A key (or eth address) has a separate registry in each namespace."
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports each is refunded when it is shrunk or closed"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "messageNonce",
+      "docs": [
+        "The nonce included in the signed messages (or WebAuthn assertions) that authorise updates to a registry.",
+        "It is kept in its own account, which is never closed, so that it is not reset when the registry",
+        "is closed and created again, and each signature can only ever be used once."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry whose updates are authorised by messages including the nonce"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "Incremented on each update authorised by a signed message"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "passkeyRegistry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "key",
+            "docs": [
+              "The compressed secp256r1 (P-256) public key of the passkey that owns the registry"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                33
+              ]
+            }
+          },
+          {
+            "name": "dids",
+            "type": {
+              "vec": "publicKey"
+            }
+          },
+          {
+            "name": "rentPayers",
+            "docs": [
+              "The parties that paid rent for the registry, and the lamports owed to each (see KeyRegistry::rent_payers)"
+            ],
+            "type": {
+              "vec": {
+                "defined": "RentPayer"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "pendingRemoval",
+      "docs": [
+        "A removal of a DID from a key or controller registry that is waiting for the",
+        "registry's removal delay to elapse."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry (key or controller) that the DID is being removed from"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "did",
+            "docs": [
+              "The DID being removed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "payer",
+            "docs": [
+              "The account that paid the rent for this pending removal, and is refunded when it is closed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "unlockSlot",
+            "docs": [
+              "The slot from which the removal can be finalised"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
-      "name": "controllerRegistry",
+      "name": "programConfig",
+      "docs": [
+        "The program-wide configuration, controlled by an admin key.",
+        "Until it is initialised, the defaults apply: nothing is paused, and there are no limits or fees."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -1206,11 +9803,81 @@ export const IDL: DidRegistry = {
             "type": "u8"
           },
           {
-            "name": "did",
+            "name": "admin",
             "type": "publicKey"
           },
           {
-            "name": "controlledDids",
+            "name": "pendingAdmin",
+            "docs": [
+              "The key proposed as the next admin, which must accept before it becomes admin (default = none)"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "While set, all instructions that change registries are blocked, except for closing them"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "initialCapacity",
+            "docs": [
+              "The number of DIDs that a new key or controller registry has space for"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "maxEntries",
+            "docs": [
+              "The maximum number of DIDs that a registry can be resized to hold (0 = no maximum)"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "registryFee",
+            "docs": [
+              "The fee in lamports charged for creating a registry (0 = no fee).",
+              "Fees are held in this account until the admin withdraws them."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "gatekeeperNetwork",
+            "docs": [
+              "If set, registering a DID requires a valid gateway token (Civic Pass) in this gatekeeper network",
+              "for the authority (default = none)"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "registrationInbox",
+      "docs": [
+        "The DIDs that a key has been invited to register (see RegistrationInvitation), so that the key can find",
+        "its pending invitations from a single account. It is created by the first invitation, and never closed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "key",
+            "docs": [
+              "The key whose invitations are listed"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "dids",
+            "docs": [
+              "The DIDs of the pending invitations"
+            ],
             "type": {
               "vec": "publicKey"
             }
@@ -1219,7 +9886,11 @@ export const IDL: DidRegistry = {
       }
     },
     {
-      "name": "keyRegistry",
+      "name": "registrationInvitation",
+      "docs": [
+        "An invitation by an authority on a DID for a key to register the DID, listed in the key's inbox",
+        "(see RegistrationInbox) to be accepted or rejected by the key, or cancelled by the inviter."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -1228,13 +9899,69 @@ export const IDL: DidRegistry = {
             "type": "u8"
           },
           {
-            "name": "authority",
+            "name": "key",
+            "docs": [
+              "The key invited to register the DID"
+            ],
             "type": "publicKey"
           },
           {
-            "name": "dids",
+            "name": "did",
+            "docs": [
+              "The DID to register"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "inviter",
+            "docs": [
+              "The account that paid the rent and deposit for this invitation"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "registryHistory",
+      "docs": [
+        "The history of the DIDs added to and removed from a key or controller registry.",
+        "Once the capacity is reached, the oldest entries are overwritten."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "registry",
+            "docs": [
+              "The registry (key or controller) that this history records"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "capacity",
+            "docs": [
+              "The maximum number of entries kept"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "next",
+            "docs": [
+              "Once the history is full, the index of the oldest entry, which is overwritten next"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "entries",
             "type": {
-              "vec": "publicKey"
+              "vec": {
+                "defined": "HistoryEntry"
+              }
             }
           }
         ]
@@ -1242,6 +9969,28 @@ export const IDL: DidRegistry = {
     }
   ],
   "types": [
+    {
+      "name": "AssetRegistryEntry",
+      "docs": [
+        "A DID registered against an asset, along with the holder of the asset that registered it"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "holder",
+            "docs": [
+              "The holder of the asset at the time the DID was registered"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
     {
       "name": "Secp256k1RawSignature",
       "type": {
@@ -1262,6 +10011,128 @@ export const IDL: DidRegistry = {
           }
         ]
       }
+    },
+    {
+      "name": "WebAuthnAssertion",
+      "docs": [
+        "The parts of a WebAuthn assertion that the passkey signed, alongside the signature itself",
+        "(which is verified by the secp256r1 program)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authenticatorData",
+            "type": "bytes"
+          },
+          {
+            "name": "clientDataJson",
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "BtcAddressType",
+      "docs": [
+        "The types of Bitcoin address that can be registered"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "P2pkh"
+          },
+          {
+            "name": "P2wpkh"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EthAddressForm",
+      "docs": [
+        "The form of the verification method through which an eth address is an authority on a DID"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Address"
+          },
+          {
+            "name": "PublicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HistoryEntry",
+      "docs": [
+        "A record of a DID being added to or removed from a registry"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "did",
+            "type": "publicKey"
+          },
+          {
+            "name": "action",
+            "type": {
+              "defined": "HistoryAction"
+            }
+          },
+          {
+            "name": "slot",
+            "type": "u64"
+          },
+          {
+            "name": "signer",
+            "docs": [
+              "The key that authorised the change, or the default pubkey if anyone could make it (e.g. a sweep)"
+            ],
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "HistoryAction",
+      "docs": [
+        "A change to the DIDs in a registry"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Add"
+          },
+          {
+            "name": "Remove"
+          }
+        ]
+      }
+    },
+    {
+      "name": "RentPayer",
+      "docs": [
+        "A party that paid rent for a registry, and the lamports it is owed back when the registry is shrunk or closed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          }
+        ]
+      }
     }
   ],
   "errors": [
@@ -1304,6 +10175,221 @@ export const IDL: DidRegistry = {
       "code": 6007,
       "name": "RegistryFull",
       "msg": "The registry has exceeded its maximum size - use the resize instruction to get more space"
+    },
+    {
+      "code": 6008,
+      "name": "RemovalDelayed",
+      "msg": "The registry has a removal delay - request the removal and finalise it once the delay has elapsed"
+    },
+    {
+      "code": 6009,
+      "name": "RemovalNotDelayed",
+      "msg": "The registry has no removal delay - remove the DID directly"
+    },
+    {
+      "code": 6010,
+      "name": "RemovalLocked",
+      "msg": "The removal delay has not yet elapsed"
+    },
+    {
+      "code": 6011,
+      "name": "RemovalDelayDecreased",
+      "msg": "The removal delay cannot be decreased"
+    },
+    {
+      "code": 6012,
+      "name": "RemovalDelayOverflow",
+      "msg": "The removal delay is too large"
+    },
+    {
+      "code": 6013,
+      "name": "RegistryNotEmpty",
+      "msg": "The registry cannot be closed while it contains DIDs protected by a removal delay"
+    },
+    {
+      "code": 6014,
+      "name": "ControllerChainTooLong",
+      "msg": "The controller chain exceeds the maximum length"
+    },
+    {
+      "code": 6015,
+      "name": "InvalidFlags",
+      "msg": "Unknown verification method flags"
+    },
+    {
+      "code": 6016,
+      "name": "MissingRequiredFlags",
+      "msg": "The key does not have the verification method flags required by the registry"
+    },
+    {
+      "code": 6017,
+      "name": "MissingSignatureInstruction",
+      "msg": "The transaction must include a signature verification instruction immediately before this instruction"
+    },
+    {
+      "code": 6018,
+      "name": "InvalidSignatureInstruction",
+      "msg": "The signature verification instruction must contain a single signature, with its data in the same instruction"
+    },
+    {
+      "code": 6019,
+      "name": "WrongSigner",
+      "msg": "The message was signed by the wrong key"
+    },
+    {
+      "code": 6020,
+      "name": "WrongMessage",
+      "msg": "The signed message does not match the expected message"
+    },
+    {
+      "code": 6021,
+      "name": "InvalidWebAuthnAssertion",
+      "msg": "The WebAuthn assertion is not a valid assertion by a present user"
+    },
+    {
+      "code": 6022,
+      "name": "InvalidAccountId",
+      "msg": "The account id is not a valid CAIP-10 account id"
+    },
+    {
+      "code": 6023,
+      "name": "UnsupportedNamespace",
+      "msg": "Signatures by accounts of this CAIP-2 namespace are not supported"
+    },
+    {
+      "code": 6024,
+      "name": "RegistryShrink",
+      "msg": "The registry can only grow"
+    },
+    {
+      "code": 6025,
+      "name": "InvalidBtcSignature",
+      "msg": "The Bitcoin signature is not a valid BIP-137 signature for the address type"
+    },
+    {
+      "code": 6026,
+      "name": "WrongBtcSigner",
+      "msg": "The Bitcoin signature was signed by the wrong address"
+    },
+    {
+      "code": 6027,
+      "name": "InvalidAsset",
+      "msg": "The asset must be an NFT, with a supply of 1 and no decimals"
+    },
+    {
+      "code": 6028,
+      "name": "NotAssetHolder",
+      "msg": "The signer does not hold the asset"
+    },
+    {
+      "code": 6029,
+      "name": "InvalidKeyRegistryPairs",
+      "msg": "Keys and their registries must be passed as (key, registry) pairs"
+    },
+    {
+      "code": 6030,
+      "name": "StillAuthority",
+      "msg": "The key is still an authority on the DID"
+    },
+    {
+      "code": 6031,
+      "name": "DIDHasControllers",
+      "msg": "The DID has controllers - pass all of them, which must not have controllers themselves"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidDidAccountPairs",
+      "msg": "DIDs and their accounts must be passed as (did, did account) pairs"
+    },
+    {
+      "code": 6033,
+      "name": "StillControlled",
+      "msg": "The DID is still controlled by the registry's DID"
+    },
+    {
+      "code": 6034,
+      "name": "InvalidSuccessor",
+      "msg": "A DID cannot be its own successor"
+    },
+    {
+      "code": 6035,
+      "name": "InvalidExpiry",
+      "msg": "The expiry must be in the future"
+    },
+    {
+      "code": 6036,
+      "name": "NoExpiredDids",
+      "msg": "The registry contains no expired DIDs"
+    },
+    {
+      "code": 6037,
+      "name": "HistoryRequired",
+      "msg": "The registry records its history, so the history must be passed after the other remaining accounts"
+    },
+    {
+      "code": 6038,
+      "name": "InvalidHistory",
+      "msg": "The history is not the history of the registry"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidHistoryCapacity",
+      "msg": "The history capacity must be greater than zero, and can only be increased"
+    },
+    {
+      "code": 6040,
+      "name": "ProgramPaused",
+      "msg": "The program is paused"
+    },
+    {
+      "code": 6041,
+      "name": "RegistryLimitExceeded",
+      "msg": "The registry would exceed the maximum number of DIDs"
+    },
+    {
+      "code": 6042,
+      "name": "InvalidConfig",
+      "msg": "The program config is invalid"
+    },
+    {
+      "code": 6043,
+      "name": "NotPendingAdmin",
+      "msg": "The signer is not the proposed admin"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidGatewayToken",
+      "msg": "The authority does not have a valid gateway token in the required gatekeeper network"
+    },
+    {
+      "code": 6045,
+      "name": "TooManyRentPayers",
+      "msg": "The registry has recorded the maximum number of rent payers"
+    },
+    {
+      "code": 6046,
+      "name": "RentPayerMissing",
+      "msg": "The accounts of the registry's rent payers must be passed after the other remaining accounts"
+    },
+    {
+      "code": 6047,
+      "name": "GatewayTokenNotSupported",
+      "msg": "The program config requires a gateway token, which cannot be verified for this authority"
+    },
+    {
+      "code": 6048,
+      "name": "NamespaceTooLong",
+      "msg": "The namespace is longer than the maximum length of a namespace"
+    },
+    {
+      "code": 6049,
+      "name": "RegistryNotLegacy",
+      "msg": "The registry can be loaded in its current layout, so it does not need to be migrated"
+    },
+    {
+      "code": 6050,
+      "name": "InboxFull",
+      "msg": "The key has too many pending invitations - it must accept or reject some first"
     }
   ]
 };
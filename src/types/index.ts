@@ -17,5 +17,74 @@ export type DidAccount = {
   bump: number;
 };
 
+export type PendingRemoval = {
+  did: string;
+  // the slot from which the removal can be finalised
+  unlockSlot: number;
+};
+
+// An invitation for a key to register a DID, and the account that paid its deposit
+export type RegistrationInvitation = {
+  did: string;
+  inviter: PublicKey;
+};
+
+// A DID added to or removed from a registry, as recorded in its history.
+// The signer is the default public key for changes that anyone can make (e.g. a sweep).
+export type RegistryHistoryEntry = {
+  did: string;
+  action: "add" | "remove";
+  slot: number;
+  signer: PublicKey;
+};
+
+// The program-wide config. pendingAdmin is null if no admin has been proposed.
+export type ProgramConfigState = {
+  admin: PublicKey;
+  pendingAdmin: PublicKey | null;
+  paused: boolean;
+  initialCapacity: number;
+  // 0 = no maximum
+  maxEntries: number;
+  // in lamports, 0 = no fee
+  registryFee: number;
+  // the network in which registering authorities need a gateway token, or null if none is needed
+  gatekeeperNetwork: PublicKey | null;
+};
+
+// The application namespace of a registry, as a utf-8 string or as bytes (at most 32 bytes).
+// A key or DID has a separate registry in each namespace. The empty namespace is the default registry.
+export type Namespace = string | Uint8Array;
+
+// A party that paid rent for a registry, and the lamports it is refunded when the registry is shrunk or closed
+export type RentPayer = {
+  payer: PublicKey;
+  lamports: number;
+};
+
+// A WebAuthn assertion (navigator.credentials.get) by a passkey
+export type WebAuthnAssertion = {
+  authenticatorData: Uint8Array;
+  clientDataJSON: Uint8Array;
+  // the DER-encoded signature
+  signature: Uint8Array;
+};
+
+// A DID registered against an asset, and the holder of the asset that registered it
+export type AssetRegistryEntry = {
+  did: string;
+  holder: PublicKey;
+};
+
+// How an eth address is listed on a DID - either directly (EcdsaSecp256k1RecoveryMethod2020),
+// or as the secp256k1 public key that it is derived from (EcdsaSecp256k1VerificationKey2019)
+export type EthAddressForm = "address" | "publicKey";
+
+// A DID registered against an eth address, and the form in which the DID lists the address
+export type EthRegistryEntry = {
+  did: string;
+  form: EthAddressForm;
+};
+
 // The exported Anchor wallet type is messed up at the moment, so we define it indirectly here
 export type Wallet = AnchorProvider["wallet"];
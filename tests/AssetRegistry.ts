@@ -0,0 +1,140 @@
+import * as anchor from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { AssetRegistry } from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { createDIDAndAddKey, initializeDIDAccount } from "./util/did";
+import { createTestContext, fund, TestContext } from "./util/anchorUtils";
+import { createNFT, createToken, transferNFT } from "./util/asset";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Asset Registry", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  const forHolder = (mint: PublicKey, holder: TestContext) =>
+    AssetRegistry.for(mint, holder.authority, provider.connection, cluster);
+
+  const createHolder = async () => {
+    const context = createTestContext();
+    await fund(context.authority.publicKey);
+    return context;
+  };
+
+  let holder: TestContext;
+  let mint: PublicKey;
+  let did: string;
+
+  beforeEach("create a holder with an NFT and a DID", async () => {
+    holder = await createHolder();
+    mint = await createNFT(holder.keypair);
+    did = await initializeDIDAccount(holder.authority);
+  });
+
+  it("finds no DIDs registered by default for an asset", async () => {
+    expect(await forHolder(mint, holder).listDIDs()).to.be.empty;
+  });
+
+  it("can register a DID against an asset", async () => {
+    const registry = forHolder(mint, holder);
+    await registry.register(did).then((execution) => execution.rpc());
+
+    expect(await registry.listEntries()).to.deep.equal([
+      { did, holder: holder.authority.publicKey },
+    ]);
+  });
+
+  it("can register a DID that the holder is a key on", async () => {
+    const otherDid = await createDIDAndAddKey(holder.authority.publicKey);
+
+    const registry = forHolder(mint, holder);
+    await registry.register(otherDid).then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([otherDid]);
+  });
+
+  it("can remove a DID from an asset's registry", async () => {
+    const registry = forHolder(mint, holder);
+    await registry.register(did).then((execution) => execution.rpc());
+
+    await registry.remove(did).rpc();
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("fails to register a DID if the holder is not an authority on it", async () => {
+    const someoneElse = await createHolder();
+    const otherDid = await initializeDIDAccount(someoneElse.authority);
+
+    const shouldFail = forHolder(mint, holder)
+      .register(otherDid)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("fails to register a DID if the signer does not hold the asset", async () => {
+    const someoneElse = await createHolder();
+    const otherDid = await initializeDIDAccount(someoneElse.authority);
+    // the asset is in the holder's token account
+    const registry = AssetRegistry.for(
+      mint,
+      someoneElse.authority,
+      provider.connection,
+      cluster,
+      anchor.utils.token.associatedAddress({
+        mint,
+        owner: holder.authority.publicKey,
+      })
+    );
+
+    const shouldFail = registry
+      .register(otherDid)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAssetHolder/);
+  });
+
+  it("fails to register a DID against a token with a supply above 1", async () => {
+    const tokenMint = await createToken(holder.keypair, 2);
+
+    const shouldFail = forHolder(tokenMint, holder)
+      .register(did)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidAsset/);
+  });
+
+  it("lets a new holder clear the previous holder's DIDs", async () => {
+    await forHolder(mint, holder)
+      .register(did)
+      .then((execution) => execution.rpc());
+
+    const newHolder = await createHolder();
+    await transferNFT(mint, holder.keypair, newHolder.keypair);
+    const newDid = await initializeDIDAccount(newHolder.authority);
+    const registry = forHolder(mint, newHolder);
+    await registry.register(newDid).then((execution) => execution.rpc());
+
+    await registry.clearPreviousHolders().rpc();
+
+    expect(await registry.listDIDs()).to.deep.equal([newDid]);
+  });
+
+  it("fails to remove a DID once the asset has been transferred", async () => {
+    const registry = forHolder(mint, holder);
+    await registry.register(did).then((execution) => execution.rpc());
+
+    const newHolder = await createHolder();
+    await transferNFT(mint, holder.keypair, newHolder.keypair);
+
+    const shouldFail = registry.remove(did).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAssetHolder/);
+  });
+});
@@ -0,0 +1,230 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { BtcRegistry } from "../src";
+import { BTC_KEY_REGISTRY_SEED_PREFIX } from "../src/service/keyRegistry/AbstractKeyRegistry";
+import { DidRegistry } from "../target/types/did_registry";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { addSecp256k1KeyToDID, initializeDIDAccount } from "./util/did";
+import { createTestContext, fund } from "./util/anchorUtils";
+import { BtcKey, createBtcKey } from "./util/bitcoin";
+import { BtcAddressType } from "../src/lib/bitcoin";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Bitcoin Registry", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  const program = anchor.workspace.DidRegistry as Program<DidRegistry>;
+
+  const forBtcAddress = (address: string) =>
+    BtcRegistry.forBtcAddress(
+      address,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+
+  const createDIDWithBtcAddress = async (btcKey: BtcKey) => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    await addSecp256k1KeyToDID(authority, btcKey.publicKey);
+    return did;
+  };
+
+  let btcKey: BtcKey;
+
+  beforeEach("create a bitcoin key", () => {
+    btcKey = createBtcKey();
+  });
+
+  it("finds no DIDs registered by default for a bitcoin address", async () => {
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("can register a DID against a P2PKH address", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    await registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("can register a DID against a P2WPKH address", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2wpkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2wpkh
+    );
+    await registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+    // the P2PKH address of the same key has a separate registry
+    expect(await forBtcAddress(btcKey.p2pkhAddress).listDIDs()).to.be.empty;
+  });
+
+  it("sets the registry authority to the address", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2wpkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2wpkh
+    );
+    await registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    const addressSeed = [BtcAddressType.P2wpkh, ...btcKey.pubkeyHash];
+    const [registryAddress] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from(BTC_KEY_REGISTRY_SEED_PREFIX),
+        Buffer.from(addressSeed.slice(0, 1)),
+        Buffer.from(addressSeed.slice(1)),
+      ],
+      program.programId
+    );
+    const { authority } = await program.account.keyRegistry.fetch(
+      registryAddress
+    );
+    // the address type, followed by the public key hash
+    expect(Array.from(authority.toBytes().slice(0, 21))).to.deep.equal(
+      addressSeed
+    );
+  });
+
+  it("can remove a DID with a signature by the address", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    const registrationSignature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    await registry
+      .registerSigned(did, registrationSignature)
+      .then((execution) => execution.rpc());
+
+    const removalSignature = await btcKey.signMessage(
+      await registry.removalMessage(did),
+      BtcAddressType.P2pkh
+    );
+    await registry
+      .removeSigned(did, removalSignature)
+      .then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("fails to remove a DID with a registration signature", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    await registry
+      .registerSigned(
+        did,
+        await btcKey.signMessage(
+          await registry.registrationMessage(did),
+          BtcAddressType.P2pkh
+        )
+      )
+      .then((execution) => execution.rpc());
+
+    // a signature of the registration message for the current nonce
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    const shouldFail = registry
+      .removeSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongBtcSigner/);
+  });
+
+  it("fails to reuse a registration signature", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    await registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    // the nonce has changed, so the signature no longer matches the registration message
+    const shouldFail = registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongBtcSigner/);
+  });
+
+  it("fails to register a DID if the message is signed by a different address", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    const signature = await createBtcKey().signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    const shouldFail = registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongBtcSigner/);
+  });
+
+  it("fails to register a DID with a signature for a different address type", async () => {
+    const did = await createDIDWithBtcAddress(btcKey);
+    const registry = forBtcAddress(btcKey.p2wpkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    const shouldFail = registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidBtcSignature/);
+  });
+
+  it("fails to register a DID if the address is not an authority", async () => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    const registry = forBtcAddress(btcKey.p2pkhAddress);
+
+    const signature = await btcKey.signMessage(
+      await registry.registrationMessage(did),
+      BtcAddressType.P2pkh
+    );
+    const shouldFail = registry
+      .registerSigned(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+});
@@ -0,0 +1,160 @@
+import * as anchor from "@project-serum/anchor";
+import { Wallet as EthWallet } from "@ethersproject/wallet";
+import { arrayify } from "@ethersproject/bytes";
+import { ChainAccountRegistry } from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { addEthAddressToDID, initializeDIDAccount } from "./util/did";
+import { createTestContext, fund } from "./util/anchorUtils";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Chain Account Registry", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  const forAccountId = (accountId: string) =>
+    ChainAccountRegistry.forAccountId(
+      accountId,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+
+  const sign = async (ethWallet: EthWallet, message: Buffer) =>
+    arrayify(await ethWallet.signMessage(message));
+
+  const createDIDWithEthAddress = async (ethWallet: EthWallet) => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    await addEthAddressToDID(authority, ethWallet.address);
+    return did;
+  };
+
+  let ethWallet: EthWallet;
+  let registry: ChainAccountRegistry;
+
+  beforeEach("create an eth account", () => {
+    ethWallet = EthWallet.createRandom();
+    registry = forAccountId(`eip155:1:${ethWallet.address.toLowerCase()}`);
+  });
+
+  it("finds no DIDs registered by default for a chain account", async () => {
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("can register a DID against an eip155 account", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+
+    const signature = await sign(
+      ethWallet,
+      await registry.registrationMessage(did)
+    );
+    await registry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("can remove a DID from an eip155 account's registry", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+    const signature = await sign(
+      ethWallet,
+      await registry.registrationMessage(did)
+    );
+    await registry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    await registry
+      .remove(did, await sign(ethWallet, await registry.removalMessage(did)))
+      .rpc();
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("keeps separate registries for the same address on different chains", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+    const signature = await sign(
+      ethWallet,
+      await registry.registrationMessage(did)
+    );
+    await registry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    const polygonRegistry = forAccountId(
+      `eip155:137:${ethWallet.address.toLowerCase()}`
+    );
+
+    expect(await polygonRegistry.listDIDs()).to.be.empty;
+  });
+
+  it("fails to register a DID if the message is signed by a different account", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+
+    const signature = await sign(
+      EthWallet.createRandom(),
+      await registry.registrationMessage(did)
+    );
+    const shouldFail = registry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongEthSigner/);
+  });
+
+  it("fails to register a DID if the chain account is not an authority", async () => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+
+    const signature = await sign(
+      ethWallet,
+      await registry.registrationMessage(did)
+    );
+    const shouldFail = registry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("rejects a checksummed eip155 address", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+    const checksummedRegistry = forAccountId(`eip155:1:${ethWallet.address}`);
+
+    const signature = await sign(
+      ethWallet,
+      await checksummedRegistry.registrationMessage(did)
+    );
+    const shouldFail = checksummedRegistry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidAccountId/);
+  });
+
+  it("rejects an unsupported namespace", async () => {
+    const did = await createDIDWithEthAddress(ethWallet);
+    const cosmosRegistry = forAccountId(
+      "cosmos:cosmoshub-3:cosmos1t2uflqwqe0fsj0shcfkrvpukewcw40yjj6hdc0"
+    );
+
+    const signature = await sign(
+      ethWallet,
+      await cosmosRegistry.registrationMessage(did)
+    );
+    const shouldFail = cosmosRegistry
+      .register(did, signature)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/UnsupportedNamespace/);
+  });
+});
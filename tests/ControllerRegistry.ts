@@ -1,17 +1,26 @@
 import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
-import { Keypair } from "@solana/web3.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
 import { ControllerRegistry, ReadOnlyControllerRegistry } from "../src";
+import { MAX_REMOVAL_DELAY } from "../src/lib/constants";
 
 import { DidRegistry } from "../target/types/did_registry";
 import chai from "chai";
 import chaiAsPromised from "chai-as-promised";
-import { createDIDAndAddController, toDid } from "./util/did";
+import {
+  addControllerToDID,
+  createDIDAndAddController,
+  createDIDAndAddKey,
+  initializeDIDAccount,
+  removeControllersFromDID,
+  toDid,
+} from "./util/did";
 import {
   DidSolIdentifier,
   ExtendedCluster,
 } from "@identity.com/sol-did-client";
 import { times } from "./util/lang";
+import { createTestContext, fund, waitForSlots } from "./util/anchorUtils";
 
 chai.use(chaiAsPromised);
 const { expect } = chai;
@@ -82,6 +91,18 @@ describe("Controller Registry", () => {
     expect(registeredDids).to.include(controlledDid);
   });
 
+  it("records the payer as the rent payer of the registry", async () => {
+    const controlledDid = await createDIDAndAddController(did);
+    await registry.register(controlledDid).then((execution) => execution.rpc());
+
+    const rentPayers = await registry.listRentPayers();
+
+    expect(rentPayers).to.have.lengthOf(1);
+    expect(rentPayers[0].payer.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+  });
+
   it("cannot register the same DID twice", async () => {
     const controlledDid = await createDIDAndAddController(did);
 
@@ -124,6 +145,281 @@ describe("Controller Registry", () => {
     return expect(shouldFail).to.be.rejectedWith(/DIDNotRegistered/);
   });
 
+  it("queues removals when the registry has a removal delay", async () => {
+    const removalDelay = 5;
+    const controlledDid = await createDIDAndAddController(did);
+    await registry.register(controlledDid).then((execution) => execution.rpc());
+    await registry.setRemovalDelay(removalDelay).rpc();
+
+    const shouldFail = registry.remove(controlledDid).rpc();
+    await expect(shouldFail).to.be.rejectedWith(/RemovalDelayed/);
+
+    await registry.requestRemoval(controlledDid).rpc();
+    const pendingRemovals = await registry.listPendingRemovals();
+    expect(pendingRemovals.map((p) => p.did)).to.deep.equal([controlledDid]);
+
+    await waitForSlots(removalDelay);
+    await registry
+      .finalizeRemoval(controlledDid)
+      .then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).not.to.include(controlledDid);
+  });
+
+  it("cannot set a removal delay above the maximum", async () => {
+    const controlledDid = await createDIDAndAddController(did);
+    await registry.register(controlledDid).then((execution) => execution.rpc());
+
+    const shouldFail = registry.setRemovalDelay(MAX_REMOVAL_DELAY + 1).rpc();
+    return expect(shouldFail).to.be.rejectedWith(/RemovalDelayOverflow/);
+  });
+
+  context("as an authority on the controlled DID", () => {
+    // create a DID controlled by the registry's DID, returning a registry client
+    // whose wallet is the controlled DID's authority
+    const createControlledDID = async () => {
+      const { authority } = createTestContext();
+      await fund(authority.publicKey);
+      const controlledDid = await initializeDIDAccount(authority);
+      await addControllerToDID(authority, did);
+
+      const controlledRegistry = ControllerRegistry.for(
+        authority,
+        did,
+        program.provider.connection,
+        cluster
+      );
+
+      return { controlledDid, controlledRegistry };
+    };
+
+    // the registry must exist before a controlled DID can add itself
+    beforeEach("create registry", async () => {
+      const someControlledDid = await createDIDAndAddController(did);
+      await registry
+        .register(someControlledDid)
+        .then((execution) => execution.rpc());
+    });
+
+    it("can add itself to the registry", async () => {
+      const { controlledDid, controlledRegistry } = await createControlledDID();
+
+      await controlledRegistry.selfRegister(controlledDid).rpc();
+
+      expect(await registry.listDIDs()).to.include(controlledDid);
+    });
+
+    it("cannot add itself if the registry's DID is not a controller", async () => {
+      const { authority } = createTestContext();
+      await fund(authority.publicKey);
+      const uncontrolledDid = await initializeDIDAccount(authority);
+
+      const shouldFail = ControllerRegistry.for(
+        authority,
+        did,
+        program.provider.connection,
+        cluster
+      )
+        .selfRegister(uncontrolledDid)
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/NotController/);
+    });
+
+    it("cannot add a DID it is not an authority on", async () => {
+      const { controlledRegistry } = await createControlledDID();
+      const otherControlledDid = await createDIDAndAddController(did);
+
+      const shouldFail = controlledRegistry
+        .selfRegister(otherControlledDid)
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+    });
+
+    it("can remove itself from the registry", async () => {
+      const { controlledDid, controlledRegistry } = await createControlledDID();
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+
+      await controlledRegistry.selfRemove(controlledDid).rpc();
+
+      expect(await registry.listDIDs()).not.to.include(controlledDid);
+    });
+
+    it("must request its removal when the registry has a removal delay", async () => {
+      const removalDelay = 5;
+      const { controlledDid, controlledRegistry } = await createControlledDID();
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+
+      const shouldFail = controlledRegistry.selfRemove(controlledDid).rpc();
+      await expect(shouldFail).to.be.rejectedWith(/RemovalDelayed/);
+
+      await controlledRegistry.selfRequestRemoval(controlledDid).rpc();
+      await waitForSlots(removalDelay);
+      await registry
+        .finalizeRemoval(controlledDid)
+        .then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).not.to.include(controlledDid);
+    });
+
+    it("cannot bypass the removal delay through a controller chain", async () => {
+      const { controlledDid } = await createControlledDID();
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+      await registry.setRemovalDelay(5).rpc();
+
+      // the registry's DID controls the controlled DID, so its authority is an authority on it too
+      const shouldFail = registry.selfRemove(controlledDid, [did]).rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/RemovalDelayed/);
+    });
+  });
+
+  context("pruning", () => {
+    // register a DID, and then remove the registry's DID as its controller
+    const registerUncontrolledDID = async () => {
+      const { authority } = createTestContext();
+      await fund(authority.publicKey);
+      const controlledDid = await initializeDIDAccount(authority);
+      await addControllerToDID(authority, did);
+
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+      await removeControllersFromDID(authority);
+
+      return controlledDid;
+    };
+
+    // a client for a wallet that is not an authority on any of the DIDs
+    const prunerRegistry = async () => {
+      const { authority: pruner } = createTestContext();
+      await fund(pruner.publicKey);
+
+      return ControllerRegistry.for(
+        pruner,
+        did,
+        program.provider.connection,
+        cluster
+      );
+    };
+
+    it("lets anyone prune DIDs that are no longer controlled", async () => {
+      const uncontrolledDids = [
+        await registerUncontrolledDID(),
+        await registerUncontrolledDID(),
+      ];
+
+      await (await prunerRegistry()).prune(uncontrolledDids).rpc();
+
+      const registeredDids = await registry.listDIDs();
+      expect(registeredDids).not.to.include(uncontrolledDids[0]);
+      expect(registeredDids).not.to.include(uncontrolledDids[1]);
+    });
+
+    it("cannot prune a DID that is still controlled", async () => {
+      const controlledDid = await createDIDAndAddController(did);
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+
+      const shouldFail = (await prunerRegistry()).prune([controlledDid]).rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/StillControlled/);
+    });
+  });
+
+  context("through a controller chain", () => {
+    it("can register, resize, remove and close as an authority on a controller of the DID", async () => {
+      // the wallet is an authority on the organisation DID, which controls the registry DID
+      const orgDid = await createDIDAndAddKey(provider.wallet.publicKey);
+      const subsidiaryDid = await createDIDAndAddController(orgDid);
+      const subsidiaryRegistry = ControllerRegistry.for(
+        provider.wallet,
+        subsidiaryDid,
+        program.provider.connection,
+        cluster,
+        [orgDid]
+      );
+      const controlledDid = await createDIDAndAddController(subsidiaryDid);
+
+      await subsidiaryRegistry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+      await subsidiaryRegistry.resize(8).rpc();
+      await subsidiaryRegistry.remove(controlledDid).rpc();
+
+      expect(await subsidiaryRegistry.listDIDs()).to.be.empty;
+
+      await subsidiaryRegistry.close().rpc();
+    });
+
+    it("cannot close the registry of another DID", async () => {
+      const controlledDid = await createDIDAndAddController(did);
+      await registry
+        .register(controlledDid)
+        .then((execution) => execution.rpc());
+
+      // the attacker passes its own DID, on which it is an authority, along with the registry of another DID
+      const { authority: attacker, keypair: attackerKeypair } =
+        createTestContext();
+      await fund(attacker.publicKey);
+      const attackerDid = await initializeDIDAccount(attacker);
+      const [attackerDidAccount, attackerDidBump] = DidSolIdentifier.parse(
+        attackerDid
+      ).dataAccount();
+      const [registryAddress] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("controller_registry"),
+          provider.wallet.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const shouldFail = program.methods
+        .closeControllerRegistry(attackerDidBump)
+        .accounts({
+          registry: registryAddress,
+          payer: attacker.publicKey,
+          authority: attacker.publicKey,
+          did: attacker.publicKey,
+          didAccount: attackerDidAccount,
+        })
+        .signers([attackerKeypair])
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/ConstraintHasOne/);
+    });
+  });
+
+  it("records its history, which continues when the registry is created again", async () => {
+    const controlledDid = await createDIDAndAddController(did);
+    await registry.register(controlledDid).then((execution) => execution.rpc());
+    await registry.createHistory(4).rpc();
+
+    await registry.remove(controlledDid).rpc();
+    await registry.close().rpc();
+
+    // the registry is created again, and picks up its previous history
+    await registry.register(controlledDid).then((execution) => execution.rpc());
+    await registry.createHistory(4).rpc();
+    await registry.remove(controlledDid).rpc();
+
+    const history = await registry.listHistory();
+    expect(history.map((entry) => entry.action)).to.deep.equal([
+      "remove",
+      "remove",
+    ]);
+    expect(history[0].did).to.equal(controlledDid);
+  });
+
   it("automatically resizes when registering more than four DIDs", async () => {
     const fiveDids = await Promise.all(
       times(5)(() => createDIDAndAddController(did))
@@ -170,4 +466,54 @@ describe("Controller Registry", () => {
     const registeredDids = await registry.listDIDs();
     expect(registeredDids).to.deep.equal(fiveDids);
   });
+
+  it("keeps a namespaced registry separate from the default one", async () => {
+    const controlledDid = await createDIDAndAddController(did);
+    const namespacedRegistry = ControllerRegistry.for(
+      provider.wallet,
+      did,
+      program.provider.connection,
+      cluster,
+      [],
+      "gaming"
+    );
+
+    await namespacedRegistry
+      .register(controlledDid)
+      .then((execution) => execution.rpc());
+
+    expect(await namespacedRegistry.listDIDs()).to.deep.equal([controlledDid]);
+    expect(await registry.listDIDs()).to.be.empty;
+
+    await namespacedRegistry.close().rpc();
+  });
+
+  it("migrates a full registry in the layout of the first release, keeping its DIDs", async () => {
+    // loaded into the validator from tests/fixtures/legacy-controller-registry.json
+    const legacyDid = toDid(
+      new PublicKey("939oW8KRsC1FR96PumxYFswmqZ3ZWRMvDAR6H7btMBQs")
+    );
+    const legacyControlledDids = [
+      "7m8m9fmyMKMdCV87KSKk2Ko2j2FTWR57smgjF7VG8iky",
+      "6arSHS9xV9jLGsAH2diAnMrTv4s22EKJZob9jJngQ7E3",
+      "7T2Xn6gm78YceMHWZ1bhAqUcbonbR64fw7GVLf8ZjZAW",
+      "ADWbCLvL6xLFvZpZ9ty2HsauuZ7h4U8ZbSome2eabCf5",
+    ].map((key) => toDid(new PublicKey(key)));
+    const legacyRegistry = ControllerRegistry.for(
+      provider.wallet,
+      legacyDid,
+      program.provider.connection,
+      cluster
+    );
+
+    // the registry has no room for the fields appended since the first release
+    await expect(legacyRegistry.listDIDs()).to.be.rejected;
+
+    await legacyRegistry.migrate().rpc();
+
+    expect(await legacyRegistry.listDIDs()).to.deep.equal(legacyControlledDids);
+
+    const shouldFail = legacyRegistry.migrate().rpc();
+    return expect(shouldFail).to.be.rejectedWith(/RegistryNotLegacy/);
+  });
 });
@@ -0,0 +1,221 @@
+import * as anchor from "@project-serum/anchor";
+import { ControllerRegistry, DidSuccession, Registry } from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import {
+  addControllerToDID,
+  addKeyToDID,
+  createDIDAndAddKey,
+  initializeDIDAccount,
+  toDid,
+} from "./util/did";
+import {
+  createTestContext,
+  fund,
+  Wallet,
+  waitForSlots,
+} from "./util/anchorUtils";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+import { Keypair } from "@solana/web3.js";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("DID Succession", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  // a key that is an authority on both the superseded DIDs and their successors
+  let key: Wallet;
+  let succession: DidSuccession;
+  let registry: Registry;
+
+  // create a DID that the key is an authority on, optionally with a controller
+  const createDID = async (controller?: string) => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    await addKeyToDID(authority, key.publicKey);
+    if (controller) await addControllerToDID(authority, controller);
+    return did;
+  };
+
+  beforeEach("create a key", async () => {
+    ({ authority: key } = createTestContext());
+    await fund(key.publicKey);
+
+    succession = DidSuccession.for(key, provider.connection, cluster);
+    registry = Registry.for(key, provider.connection, cluster);
+  });
+
+  it("records the successor of a DID", async () => {
+    const did = await createDID();
+    const successor = await createDID();
+
+    await succession.setSuccessor(did, successor).rpc();
+
+    expect(await succession.successorOf(did)).to.equal(successor);
+  });
+
+  it("finds no successor for a DID that has not been superseded", async () => {
+    const did = await createDID();
+
+    expect(await succession.successorOf(did)).to.be.null;
+  });
+
+  it("cannot set a successor without being an authority on it", async () => {
+    const did = await createDID();
+    const successor = await createDIDAndAddKey(Keypair.generate().publicKey);
+
+    const shouldFail = succession.setSuccessor(did, successor).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("cannot set a DID as its own successor", async () => {
+    const did = await createDID();
+
+    const shouldFail = succession.setSuccessor(did, did).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidSuccessor/);
+  });
+
+  it("cannot set the successor of a DID twice", async () => {
+    const did = await createDID();
+    await succession.setSuccessor(did, await createDID()).rpc();
+
+    const shouldFail = succession.setSuccessor(did, await createDID()).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/already in use/);
+  });
+
+  it("can update the successor of a DID", async () => {
+    const did = await createDID();
+    await succession.setSuccessor(did, await createDID()).rpc();
+    const newSuccessor = await createDID();
+
+    await succession.updateSuccessor(did, newSuccessor).rpc();
+
+    expect(await succession.successorOf(did)).to.equal(newSuccessor);
+  });
+
+  it("cannot update the successor to a DID without being an authority on it", async () => {
+    const did = await createDID();
+    await succession.setSuccessor(did, await createDID()).rpc();
+    const newSuccessor = await createDIDAndAddKey(Keypair.generate().publicKey);
+
+    const shouldFail = succession.updateSuccessor(did, newSuccessor).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("can revoke the successor of a DID", async () => {
+    const did = await createDID();
+    await succession.setSuccessor(did, await createDID()).rpc();
+
+    await succession.revokeSuccessor(did).then((execution) => execution.rpc());
+
+    expect(await succession.successorOf(did)).to.be.null;
+  });
+
+  it("cannot revoke the successor of a DID without being an authority on it", async () => {
+    const did = await createDID();
+    await succession.setSuccessor(did, await createDID()).rpc();
+
+    const { authority: other } = createTestContext();
+    const shouldFail = DidSuccession.for(other, provider.connection, cluster)
+      .revokeSuccessor(did)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("replaces a superseded DID in a key registry", async () => {
+    const did = await createDID();
+    const successor = await createDID();
+    await registry.register(did).then((execution) => execution.rpc());
+    await succession.setSuccessor(did, successor).rpc();
+
+    await registry.replace(did).then((execution) => execution.rpc());
+
+    const registeredDids = await registry.listDIDs();
+    expect(registeredDids).to.deep.equal([successor]);
+  });
+
+  it("waits for the removal delay before replacing a DID", async () => {
+    const did = await createDID();
+    const successor = await createDID();
+    await registry.register(did).then((execution) => execution.rpc());
+    await registry.setRemovalDelay(2).rpc();
+    await succession.setSuccessor(did, successor).rpc();
+
+    const shouldFail = registry
+      .replace(did)
+      .then((execution) => execution.rpc());
+    await expect(shouldFail).to.be.rejectedWith(/RemovalDelayed/);
+
+    await registry.requestRemoval(did).rpc();
+    await waitForSlots(2);
+    await registry.replace(did).then((execution) => execution.rpc());
+
+    const registeredDids = await registry.listDIDs();
+    expect(registeredDids).to.deep.equal([successor]);
+  });
+
+  it("replaces a superseded DID in a controller registry", async () => {
+    // a DID controlling the superseded DID and its successor
+    const controllerDid = toDid(key.publicKey);
+    const controllerRegistry = ControllerRegistry.for(
+      key,
+      controllerDid,
+      provider.connection,
+      cluster
+    );
+
+    const did = await createDID(controllerDid);
+    const successor = await createDID(controllerDid);
+    await controllerRegistry
+      .register(did)
+      .then((execution) => execution.rpc());
+    await succession.setSuccessor(did, successor).rpc();
+
+    await controllerRegistry.replace(did).then((execution) => execution.rpc());
+
+    const registeredDids = await controllerRegistry.listDIDs();
+    expect(registeredDids).to.deep.equal([successor]);
+  });
+
+  it("cannot replace a DID with a successor the key is not an authority on", async () => {
+    // the DID's authority migrates it to a DID that the key is not on
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    await addKeyToDID(authority, key.publicKey);
+    const successor = await createDIDAndAddKey(authority.publicKey);
+    await DidSuccession.for(authority, provider.connection, cluster)
+      .setSuccessor(did, successor)
+      .rpc();
+
+    await registry.register(did).then((execution) => execution.rpc());
+    const shouldFail = registry
+      .replace(did)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("resolves a superseded DID to its latest successor", async () => {
+    const did = await createDID();
+    const successor = await createDID();
+    const latestSuccessor = await createDID();
+    await registry.register(did).then((execution) => execution.rpc());
+    await succession.setSuccessor(did, successor).rpc();
+    await succession.setSuccessor(successor, latestSuccessor).rpc();
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+    const resolvedDids = await registry.listResolvedDIDs();
+    expect(resolvedDids).to.deep.equal([latestSuccessor]);
+  });
+});
@@ -0,0 +1,119 @@
+import * as anchor from "@project-serum/anchor";
+import { ExternalControllerRegistry } from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { addControllerToDID, initializeDIDAccount } from "./util/did";
+import { createTestContext, fund, Wallet } from "./util/anchorUtils";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("External Controller Registry", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  const forController = (controller: string, wallet: Wallet) =>
+    ExternalControllerRegistry.for(
+      controller,
+      wallet,
+      provider.connection,
+      cluster
+    );
+
+  // create a DID, whose authority is returned alongside it
+  const createDID = async () => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    return { did, authority };
+  };
+
+  let controller: string;
+
+  beforeEach("create a controller DID", () => {
+    // randomise the controller, so that each test starts from an empty registry
+    controller = `did:web:example${Date.now()}.com`;
+  });
+
+  it("finds no DIDs registered by default for a controller", async () => {
+    const { authority } = await createDID();
+    expect(await forController(controller, authority).listDIDs()).to.be.empty;
+  });
+
+  it("can register a DID that lists the controller", async () => {
+    const { did, authority } = await createDID();
+    await addControllerToDID(authority, controller);
+
+    const registry = forController(controller, authority);
+    await registry.register(did).then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("can register many DIDs against the same controller", async () => {
+    const dids: string[] = [];
+    // more than the initial size, to exercise the auto-resize
+    for (let i = 0; i < 5; i++) {
+      const { did, authority } = await createDID();
+      await addControllerToDID(authority, controller);
+      await forController(controller, authority)
+        .register(did)
+        .then((execution) => execution.rpc());
+      dids.push(did);
+    }
+
+    const { authority } = await createDID();
+    const registered = await forController(controller, authority).listDIDs();
+    expect(registered).to.have.members(dids);
+  });
+
+  it("can remove a DID from a controller's registry", async () => {
+    const { did, authority } = await createDID();
+    await addControllerToDID(authority, controller);
+    const registry = forController(controller, authority);
+    await registry.register(did).then((execution) => execution.rpc());
+
+    await registry.remove(did).rpc();
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("fails to register a DID that does not list the controller", async () => {
+    const { did, authority } = await createDID();
+    await addControllerToDID(authority, "did:web:someone-else.com");
+
+    const shouldFail = forController(controller, authority)
+      .register(did)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotController/);
+  });
+
+  it("fails to register a DID if the signer is not an authority on it", async () => {
+    const { did, authority } = await createDID();
+    await addControllerToDID(authority, controller);
+    const { authority: someoneElse } = await createDID();
+
+    const shouldFail = forController(controller, someoneElse)
+      .register(did)
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("fails to remove a DID if the signer is not an authority on it", async () => {
+    const { did, authority } = await createDID();
+    await addControllerToDID(authority, controller);
+    await forController(controller, authority)
+      .register(did)
+      .then((execution) => execution.rpc());
+    const { authority: someoneElse } = await createDID();
+
+    const shouldFail = forController(controller, someoneElse).remove(did).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+});
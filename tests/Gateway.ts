@@ -0,0 +1,155 @@
+import * as anchor from "@project-serum/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  ControllerRegistry,
+  KeyRegistries,
+  ProgramConfig,
+  Registry,
+} from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import {
+  addKeyToDID,
+  createDIDAndAddController,
+  createDIDAndAddKey,
+  initializeDIDAccount,
+} from "./util/did";
+import { createTestContext, fund, Wallet } from "./util/anchorUtils";
+import { issueGatewayToken } from "./util/gateway";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Gateway token gating", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  // the provider wallet is the upgrade authority of the program, so it can initialise the config
+  const config = ProgramConfig.for(provider.wallet, provider.connection);
+
+  let gatekeeperNetwork: PublicKey;
+  let key: Wallet;
+  let registry: Registry;
+
+  before("initialise the config", async () => {
+    if (!(await config.fetch())) await config.initialize(4).rpc();
+  });
+
+  beforeEach("require a gateway token", async () => {
+    gatekeeperNetwork = Keypair.generate().publicKey;
+    await config.setGatekeeperNetwork(gatekeeperNetwork).rpc();
+
+    ({ authority: key } = createTestContext());
+    await fund(key.publicKey);
+
+    registry = Registry.for(key, provider.connection, cluster);
+  });
+
+  // remove the requirement, so that it does not affect other tests
+  afterEach("stop requiring a gateway token", () =>
+    config.setGatekeeperNetwork(null).rpc()
+  );
+
+  it("blocks registering a DID without a gateway token", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+
+    const shouldFail = (await registry.register(did)).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidGatewayToken/);
+  });
+
+  it("registers a DID with a gateway token", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await issueGatewayToken(key.publicKey, gatekeeperNetwork);
+
+    await (await registry.register(did)).rpc();
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("rejects a gateway token in a different gatekeeper network", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await issueGatewayToken(key.publicKey, Keypair.generate().publicKey);
+
+    const shouldFail = (await registry.register(did)).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidGatewayToken/);
+  });
+
+  it("rejects an expired gateway token", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await issueGatewayToken(
+      key.publicKey,
+      gatekeeperNetwork,
+      new Date(Date.now() - 60_000)
+    );
+
+    const shouldFail = (await registry.register(did)).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidGatewayToken/);
+  });
+
+  it("registers a controlled DID with a gateway token", async () => {
+    const controllerDid = await initializeDIDAccount(key);
+    const controlledDid = await createDIDAndAddController(controllerDid);
+    const controllerRegistry = ControllerRegistry.for(
+      key,
+      controllerDid,
+      provider.connection,
+      cluster
+    );
+
+    await expect(
+      (await controllerRegistry.register(controlledDid)).rpc()
+    ).to.be.rejectedWith(/InvalidGatewayToken/);
+
+    await issueGatewayToken(key.publicKey, gatekeeperNetwork);
+    await (await controllerRegistry.register(controlledDid)).rpc();
+
+    expect(await controllerRegistry.listDIDs()).to.deep.equal([controlledDid]);
+  });
+
+  it("requires a gateway token to accept an invitation", async () => {
+    const { authority: issuer } = createTestContext();
+    await fund(issuer.publicKey);
+    const did = await initializeDIDAccount(issuer);
+    await addKeyToDID(issuer, key.publicKey);
+    await Registry.forRelayer(
+      key.publicKey,
+      issuer,
+      provider.connection,
+      cluster
+    )
+      .invite(did)
+      .rpc();
+
+    await expect(
+      registry.acceptInvitation(did).then((execution) => execution.rpc())
+    ).to.be.rejectedWith(/InvalidGatewayToken/);
+
+    await issueGatewayToken(key.publicKey, gatekeeperNetwork);
+    await registry.acceptInvitation(did).then((execution) => execution.rpc());
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("blocks registering a DID against many keys at once", async () => {
+    const keypair = Keypair.generate();
+    const did = await createDIDAndAddKey(keypair.publicKey);
+    await issueGatewayToken(keypair.publicKey, gatekeeperNetwork);
+
+    const transaction = await KeyRegistries.for(
+      [keypair.publicKey],
+      provider.wallet,
+      provider.connection
+    )
+      .register(did)
+      .transaction();
+    const shouldFail = provider.sendAndConfirm(transaction, [keypair]);
+
+    return expect(shouldFail).to.be.rejectedWith(/GatewayTokenNotSupported/);
+  });
+});
@@ -1,23 +1,54 @@
 import * as anchor from "@project-serum/anchor";
 import { Program, Provider } from "@project-serum/anchor";
-import { Keypair, Transaction } from "@solana/web3.js";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
 import { Wallet as EthWallet } from "@ethersproject/wallet";
-import { EthRegistry, ReadOnlyRegistry, Registry } from "../src";
+import * as ed25519 from "@noble/ed25519";
+import {
+  EthRegistry,
+  KeyRegistries,
+  ReadOnlyRegistry,
+  Registry,
+} from "../src";
+import { configAccounts } from "../src/lib/util";
+import {
+  KEY_REGISTRY_DID_SIZE,
+  MAX_REMOVAL_DELAY,
+} from "../src/lib/constants";
 
 import { DidRegistry } from "../target/types/did_registry";
 import chai from "chai";
 import chaiAsPromised from "chai-as-promised";
 import {
+  addControllerToDID,
   addEthAddressToDID,
   addKeyToDID,
   addKeyToDIDExecution,
+  addSecp256k1KeyToDID,
+  closeDIDAccount,
+  createDIDAndAddController,
   createDIDAndAddKey,
   initializeDIDAccount,
   toDid,
 } from "./util/did";
-import { createTestContext, fund, Wallet } from "./util/anchorUtils";
-import { ExtendedCluster } from "@identity.com/sol-did-client";
+import {
+  balanceOf,
+  createTestContext,
+  fund,
+  waitForClusterTime,
+  waitForSlots,
+  Wallet,
+} from "./util/anchorUtils";
+import {
+  BitwiseVerificationMethodFlag,
+  ExtendedCluster,
+} from "@identity.com/sol-did-client";
 import { times } from "./util/lang";
+import { Execution } from "../src/types";
+import {
+  cryptidWallet,
+  getCryptidAccount,
+  wrapInCryptid,
+} from "./util/cryptid";
 
 chai.use(chaiAsPromised);
 const { expect } = chai;
@@ -250,6 +281,427 @@ describe("Key Registry", () => {
     return expect(shouldFail).to.be.rejectedWith(/DIDNotRegistered/);
   });
 
+  context("with a signed message", () => {
+    it("can register a DID submitted and paid for by a relayer", async () => {
+      // the user key has no funds, and does not sign the transaction
+      const userKeypair = Keypair.generate();
+      const did = toDid(userKeypair.publicKey);
+
+      const relayedRegistry = Registry.forRelayer(
+        userKeypair.publicKey,
+        provider.wallet,
+        provider.connection,
+        cluster
+      );
+
+      const message = await relayedRegistry.registrationMessage(did);
+      const signature = await ed25519.sign(
+        message,
+        userKeypair.secretKey.slice(0, 32)
+      );
+
+      await relayedRegistry
+        .registerWithSignedMessage(did, signature)
+        .then((execution) => execution.rpc());
+
+      expect(await relayedRegistry.listDIDs()).to.include(did);
+    });
+
+    it("cannot reuse a signed message", async () => {
+      const userKeypair = Keypair.generate();
+      const firstDid = toDid(userKeypair.publicKey);
+      const secondDid = await createDIDAndAddKey(userKeypair.publicKey);
+
+      const relayedRegistry = Registry.forRelayer(
+        userKeypair.publicKey,
+        provider.wallet,
+        provider.connection,
+        cluster
+      );
+
+      const message = await relayedRegistry.registrationMessage(firstDid);
+      const signature = await ed25519.sign(
+        message,
+        userKeypair.secretKey.slice(0, 32)
+      );
+
+      await relayedRegistry
+        .registerWithSignedMessage(firstDid, signature)
+        .then((execution) => execution.rpc());
+
+      // the nonce has been incremented, so the signature no longer matches the registration message
+      const shouldFail = relayedRegistry
+        .registerWithSignedMessage(secondDid, signature)
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejected;
+    });
+
+    it("cannot reuse a signed message once the registry is closed", async () => {
+      const userKeypair = Keypair.generate();
+      const did = toDid(userKeypair.publicKey);
+      const sign = (message: Buffer) =>
+        ed25519.sign(message, userKeypair.secretKey.slice(0, 32));
+
+      const relayedRegistry = Registry.forRelayer(
+        userKeypair.publicKey,
+        provider.wallet,
+        provider.connection,
+        cluster
+      );
+
+      const signature = await sign(
+        await relayedRegistry.registrationMessage(did)
+      );
+      await relayedRegistry
+        .registerWithSignedMessage(did, signature)
+        .then((execution) => execution.rpc());
+      await relayedRegistry
+        .removeWithSignedMessage(
+          did,
+          await sign(await relayedRegistry.removalMessage(did))
+        )
+        .then((execution) => execution.rpc());
+      await relayedRegistry
+        .closeWithSignedMessage(
+          await sign(await relayedRegistry.closeMessage())
+        )
+        .then((execution) => execution.rpc());
+
+      // the nonce survives closing the registry, so the signature cannot re-create it
+      const shouldFail = relayedRegistry
+        .registerWithSignedMessage(did, signature)
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejected;
+    });
+
+    it("can manage a registry for a key that never signs a transaction", async () => {
+      const userKeypair = Keypair.generate();
+      const did = await createDIDAndAddKey(userKeypair.publicKey);
+      const sign = (message: Buffer) =>
+        ed25519.sign(message, userKeypair.secretKey.slice(0, 32));
+
+      const relayedRegistry = Registry.forRelayer(
+        userKeypair.publicKey,
+        provider.wallet,
+        provider.connection,
+        cluster
+      );
+
+      await relayedRegistry
+        .registerWithSignedMessage(
+          did,
+          await sign(await relayedRegistry.registrationMessage(did))
+        )
+        .then((execution) => execution.rpc());
+
+      await relayedRegistry
+        .resizeWithSignedMessage(
+          8,
+          await sign(await relayedRegistry.resizeMessage(8))
+        )
+        .then((execution) => execution.rpc());
+      expect((await relayedRegistry.analyseSpace()).maxCount).to.equal(8);
+
+      await relayedRegistry
+        .removeWithSignedMessage(
+          did,
+          await sign(await relayedRegistry.removalMessage(did))
+        )
+        .then((execution) => execution.rpc());
+      expect(await relayedRegistry.listDIDs()).to.be.empty;
+
+      await relayedRegistry
+        .closeWithSignedMessage(
+          await sign(await relayedRegistry.closeMessage())
+        )
+        .then((execution) => execution.rpc());
+      expect((await relayedRegistry.analyseSpace()).sizeBytes).to.equal(0);
+    });
+
+    it("does not accept a registration signature for a removal", async () => {
+      const userKeypair = Keypair.generate();
+      const did = toDid(userKeypair.publicKey);
+
+      const relayedRegistry = Registry.forRelayer(
+        userKeypair.publicKey,
+        provider.wallet,
+        provider.connection,
+        cluster
+      );
+
+      await relayedRegistry
+        .registerWithSignedMessage(
+          did,
+          await ed25519.sign(
+            await relayedRegistry.registrationMessage(did),
+            userKeypair.secretKey.slice(0, 32)
+          )
+        )
+        .then((execution) => execution.rpc());
+
+      // a signature of a registration message with the current nonce, presented as a removal
+      const signature = await ed25519.sign(
+        await relayedRegistry.registrationMessage(did),
+        userKeypair.secretKey.slice(0, 32)
+      );
+      const shouldFail = relayedRegistry
+        .removeWithSignedMessage(did, signature)
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejected;
+    });
+  });
+
+  context("with required verification method flags", () => {
+    beforeEach("create the registry and require the OwnershipProof flag", async () => {
+      await registry
+        .register(toDid(provider.wallet.publicKey))
+        .then((execution) => execution.rpc());
+      await registry
+        .setRequiredFlags([BitwiseVerificationMethodFlag.OwnershipProof])
+        .rpc();
+    });
+
+    it("fails to register a DID if the key does not have the required flag", async () => {
+      const did = await createDIDAndAddKey(program.provider.publicKey, [
+        BitwiseVerificationMethodFlag.CapabilityInvocation,
+      ]);
+
+      const shouldFail = registry
+        .register(did)
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejectedWith(/MissingRequiredFlags/);
+    });
+
+    it("can register a DID if the key has the required flag", async () => {
+      const did = await createDIDAndAddKey(program.provider.publicKey, [
+        BitwiseVerificationMethodFlag.CapabilityInvocation,
+        BitwiseVerificationMethodFlag.OwnershipProof,
+      ]);
+
+      await registry.register(did).then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).to.include(did);
+    });
+  });
+
+  context("through a controller chain", () => {
+    // A DID on which the registry key is an authority, e.g. an organisation DID
+    let orgDid: string;
+
+    before("create the organisation DID", async () => {
+      orgDid = await createDIDAndAddKey(program.provider.publicKey);
+    });
+
+    it("fails to register a controlled DID without the controller chain", async () => {
+      const subsidiaryDid = await createDIDAndAddController(orgDid);
+
+      const shouldFail = registry
+        .register(subsidiaryDid)
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+    });
+
+    it("can register a DID controlled by a DID the key is an authority on", async () => {
+      const subsidiaryDid = await createDIDAndAddController(orgDid);
+
+      await registry
+        .register(subsidiaryDid, [orgDid])
+        .then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).to.include(subsidiaryDid);
+    });
+
+    it("can register a DID through a two-level controller chain", async () => {
+      const divisionDid = await createDIDAndAddController(orgDid);
+      const teamDid = await createDIDAndAddController(divisionDid);
+
+      await registry
+        .register(teamDid, [divisionDid, orgDid])
+        .then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).to.include(teamDid);
+    });
+
+    it("fails to register a DID through an unrelated controller chain", async () => {
+      const divisionDid = await createDIDAndAddController(orgDid);
+      const unrelatedDid = await createDIDAndAddController(
+        toDid(Keypair.generate().publicKey)
+      );
+
+      const shouldFail = registry
+        .register(unrelatedDid, [divisionDid, orgDid])
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejected;
+    });
+  });
+
+  context("via Cryptid", () => {
+    const registerViaCryptid = async (did: string, cryptidDid: PublicKey) => {
+      const cryptidRegistry = Registry.for(
+        cryptidWallet(getCryptidAccount(cryptidDid)),
+        provider.connection,
+        cluster,
+        provider.wallet.publicKey
+      );
+
+      const instructions = await cryptidRegistry
+        .register(did)
+        .then((execution) => execution.transaction())
+        .then((transaction) => transaction.instructions);
+      const wrappedInstructions = await Promise.all(
+        instructions.map((instruction) =>
+          wrapInCryptid(instruction, cryptidDid)
+        )
+      );
+      await provider.sendAndConfirm(
+        new Transaction().add(...wrappedInstructions)
+      );
+
+      return cryptidRegistry;
+    };
+
+    it("accepts a registration signed by the DID's Cryptid account", async () => {
+      const didKey = Keypair.generate().publicKey;
+      const did = toDid(didKey);
+
+      const cryptidRegistry = await registerViaCryptid(did, didKey);
+
+      expect(await cryptidRegistry.listDIDs()).to.include(did);
+    });
+
+    it("rejects a Cryptid account that does not derive from the DID", async () => {
+      const did = toDid(Keypair.generate().publicKey);
+      const otherDidKey = Keypair.generate().publicKey;
+
+      const shouldFail = registerViaCryptid(did, otherDidKey);
+
+      return expect(shouldFail).to.be.rejected;
+    });
+  });
+
+  context("with a removal delay", () => {
+    const removalDelay = 5;
+
+    it("cannot remove a DID directly", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+
+      const shouldFail = registry.remove(did).rpc();
+      await expect(shouldFail).to.be.rejectedWith(/RemovalDelayed/);
+
+      // clean up, so that the registry can be closed
+      await registry.requestRemoval(did).rpc();
+      await waitForSlots(removalDelay);
+      await registry.finalizeRemoval(did).then((execution) => execution.rpc());
+    });
+
+    it("cannot decrease the removal delay", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+
+      const shouldFail = registry.setRemovalDelay(removalDelay - 1).rpc();
+      await expect(shouldFail).to.be.rejectedWith(/RemovalDelayDecreased/);
+
+      // clean up, so that the registry can be closed
+      await registry.requestRemoval(did).rpc();
+      await waitForSlots(removalDelay);
+      await registry.finalizeRemoval(did).then((execution) => execution.rpc());
+    });
+
+    it("cannot set a removal delay above the maximum", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+
+      const shouldFail = registry.setRemovalDelay(MAX_REMOVAL_DELAY + 1).rpc();
+      return expect(shouldFail).to.be.rejectedWith(/RemovalDelayOverflow/);
+    });
+
+    it("removes a DID once the delay has elapsed", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+
+      await registry.requestRemoval(did).rpc();
+
+      // the removal is visible to clients, but the did is still registered
+      const pendingRemovals = await registry.listPendingRemovals();
+      expect(pendingRemovals.map((p) => p.did)).to.deep.equal([did]);
+      expect(await registry.listDIDs()).to.include(did);
+
+      const shouldFail = registry
+        .finalizeRemoval(did)
+        .then((execution) => execution.rpc());
+      await expect(shouldFail).to.be.rejectedWith(/RemovalLocked/);
+
+      await waitForSlots(removalDelay);
+      await registry.finalizeRemoval(did).then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).not.to.include(did);
+      expect(await registry.listPendingRemovals()).to.be.empty;
+    });
+
+    it("allows an authority on the DID to cancel a removal", async () => {
+      const { authority: secondAuthority, keypair: secondKeypair } =
+        createTestContext();
+      await fund(secondAuthority.publicKey);
+      const secondAuthorityDid = await initializeDIDAccount(secondAuthority);
+      await addKeyToDID(secondAuthority, program.provider.publicKey);
+
+      await registry
+        .register(secondAuthorityDid)
+        .then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+
+      await registry.requestRemoval(secondAuthorityDid).rpc();
+
+      // the owner of the DID cancels the removal
+      const cancelTx = await registry
+        .cancelRemoval(secondAuthorityDid, secondAuthority.publicKey)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(cancelTx, [secondKeypair]);
+
+      expect(await registry.listPendingRemovals()).to.be.empty;
+      await waitForSlots(removalDelay);
+      expect(await registry.listDIDs()).to.include(secondAuthorityDid);
+
+      // clean up, so that the registry can be closed
+      await registry.requestRemoval(secondAuthorityDid).rpc();
+      await waitForSlots(removalDelay);
+      await registry
+        .finalizeRemoval(secondAuthorityDid)
+        .then((execution) => execution.rpc());
+    });
+
+    it("does not allow a non-authority to cancel a removal", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+      await registry.setRemovalDelay(removalDelay).rpc();
+      await registry.requestRemoval(did).rpc();
+
+      const { authority: otherAuthority, keypair: otherKeypair } =
+        createTestContext();
+      await fund(otherAuthority.publicKey);
+
+      const cancelTx = await registry
+        .cancelRemoval(did, otherAuthority.publicKey)
+        .then((execution) => execution.transaction());
+      const shouldFail = provider.sendAndConfirm(cancelTx, [otherKeypair]);
+      await expect(shouldFail).to.be.rejected;
+
+      // clean up, so that the registry can be closed
+      await waitForSlots(removalDelay);
+      await registry.finalizeRemoval(did).then((execution) => execution.rpc());
+    });
+  });
+
   it("can register a DID against an eth key", async () => {
     const did = toDid(provider.wallet.publicKey);
 
@@ -261,6 +713,10 @@ describe("Key Registry", () => {
     const registeredDids = await ethRegistry.listDIDs();
 
     expect(registeredDids).to.include(did);
+    expect(await ethRegistry.listEthAddressEntries()).to.deep.include({
+      did,
+      form: "address",
+    });
   });
 
   it("can register a DID, signed with an eth key", async () => {
@@ -282,6 +738,96 @@ describe("Key Registry", () => {
     expect(registeredDids).to.include(did);
   });
 
+  it("can register a DID, signed with an eth key verified by the secp256k1 program", async () => {
+    const { authority: thirdAuthority } = createTestContext();
+    await fund(thirdAuthority.publicKey);
+    const did = toDid(thirdAuthority.publicKey);
+
+    await initializeDIDAccount(thirdAuthority);
+    await addEthAddressToDID(thirdAuthority, ethWallet.address);
+
+    await ethRegistry
+      .registerSignedViaPrecompile(did, ethWallet)
+      .then((execution) => execution.rpc());
+
+    const registeredDids = await ethRegistry.listDIDs();
+
+    expect(registeredDids).to.include(did);
+  });
+
+  it("rejects a secp256k1 signature of a message with a used nonce", async () => {
+    const nonceEthWallet = EthWallet.createRandom();
+    const nonceEthRegistry = EthRegistry.forEthAddress(
+      nonceEthWallet.address,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+    const createDIDWithEthAddress = async () => {
+      const { authority: didAuthority } = createTestContext();
+      await fund(didAuthority.publicKey);
+      await initializeDIDAccount(didAuthority);
+      await addEthAddressToDID(didAuthority, nonceEthWallet.address);
+      return toDid(didAuthority.publicKey);
+    };
+    const firstDid = await createDIDWithEthAddress();
+    const secondDid = await createDIDWithEthAddress();
+
+    // both messages are signed with the current nonce
+    const firstExecution = await nonceEthRegistry.registerSignedViaPrecompile(
+      firstDid,
+      nonceEthWallet
+    );
+    const secondExecution = await nonceEthRegistry.registerSignedViaPrecompile(
+      secondDid,
+      nonceEthWallet
+    );
+
+    await firstExecution.rpc();
+
+    const shouldFail = secondExecution.rpc();
+    return expect(shouldFail).to.be.rejectedWith(/WrongMessage/);
+  });
+
+  it("can register a DID that lists the secp256k1 public key of an eth address", async () => {
+    const { authority: didAuthority } = createTestContext();
+    await fund(didAuthority.publicKey);
+    const did = toDid(didAuthority.publicKey);
+    const keyEthWallet = EthWallet.createRandom();
+    const keyEthRegistry = EthRegistry.forEthAddress(
+      keyEthWallet.address,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+
+    await initializeDIDAccount(didAuthority);
+    await addSecp256k1KeyToDID(didAuthority, keyEthWallet.publicKey);
+
+    await keyEthRegistry
+      .registerSigned(did, keyEthWallet)
+      .then((execution) => execution.rpc());
+
+    expect(await keyEthRegistry.listEthAddressEntries()).to.deep.equal([
+      { did, form: "publicKey" },
+    ]);
+  });
+
+  it("rejects a secp256k1 signature by a different eth key", async () => {
+    const { authority: thirdAuthority } = createTestContext();
+    await fund(thirdAuthority.publicKey);
+    const did = toDid(thirdAuthority.publicKey);
+
+    await initializeDIDAccount(thirdAuthority);
+    await addEthAddressToDID(thirdAuthority, ethWallet.address);
+
+    const shouldFail = ethRegistry
+      .registerSignedViaPrecompile(did, EthWallet.createRandom())
+      .then((execution) => execution.rpc());
+
+    return expect(shouldFail).to.be.rejected;
+  });
+
   it("automatically resizes when registering more than four DIDs", async () => {
     const fiveDids = await Promise.all(
       times(5)(() => createDIDAndAddKey(program.provider.publicKey))
@@ -432,5 +978,707 @@ describe("Key Registry", () => {
 
       // we are happy as long as the tx passes
     });
+
+    it("records the payer as the rent payer of the registry", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      const setupTx = await registryWithSeparatePayer
+        .register(did)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(setupTx, [payerKeypair]);
+
+      const rentPayers = await registry.listRentPayers();
+
+      expect(rentPayers).to.have.lengthOf(1);
+      expect(rentPayers[0].payer.toBase58()).to.equal(
+        payerKeypair.publicKey.toBase58()
+      );
+      expect(rentPayers[0].lamports).to.equal(
+        await balanceOf(registry.getRegistryAddressAndBump()[0])
+      );
+    });
+
+    it("refunds the rent payer when the authority closes the registry", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      const setupTx = await registryWithSeparatePayer
+        .register(did)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(setupTx, [payerKeypair]);
+      const [{ lamports: rent }] = await registry.listRentPayers();
+      const balanceBefore = await balanceOf(payerKeypair.publicKey);
+
+      // the authority closes the registry, paying the transaction fee
+      await registry.close().rpc();
+
+      expect(await balanceOf(payerKeypair.publicKey)).to.equal(
+        balanceBefore + rent
+      );
+    });
+
+    it("refunds the rent payer when the authority shrinks the registry", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      const setupTx = await registryWithSeparatePayer
+        .register(did)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(setupTx, [payerKeypair]);
+      const resizeTx = await registryWithSeparatePayer.resize(10).transaction();
+      await provider.sendAndConfirm(resizeTx, [payerKeypair]);
+
+      const [{ lamports: rentBefore }] = await registry.listRentPayers();
+      const balanceBefore = await balanceOf(payerKeypair.publicKey);
+
+      await registry.resize(4).rpc();
+
+      const [{ lamports: rentAfter }] = await registry.listRentPayers();
+      expect(rentAfter).to.be.lessThan(rentBefore);
+      expect(await balanceOf(payerKeypair.publicKey)).to.equal(
+        balanceBefore + rentBefore - rentAfter
+      );
+    });
+
+    it("lets the authority keep the rent if the rent payer co-signs", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      const setupTx = await registryWithSeparatePayer
+        .register(did)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(setupTx, [payerKeypair]);
+      const balanceBefore = await balanceOf(payerKeypair.publicKey);
+
+      const closeTx = await registry
+        .close([payerKeypair.publicKey])
+        .transaction();
+      await provider.sendAndConfirm(closeTx, [payerKeypair]);
+
+      expect(await balanceOf(payerKeypair.publicKey)).to.equal(balanceBefore);
+    });
+
+    it("cannot close a registry without its rent payer", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      const setupTx = await registryWithSeparatePayer
+        .register(did)
+        .then((execution) => execution.transaction());
+      await provider.sendAndConfirm(setupTx, [payerKeypair]);
+
+      const shouldFail = program.methods
+        .closeKeyRegistry()
+        .accounts({
+          registry: registry.getRegistryAddressAndBump()[0],
+          authority: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/RentPayerMissing/);
+    });
+  });
+
+  describe("pruning", () => {
+    let pruner: Wallet;
+    let prunerRegistry: Registry;
+
+    // register a DID that the key is an authority on, and then close the DID,
+    // so that the key is no longer an authority on it
+    const registerClosedDID = async () => {
+      const { authority: didAuthority } = createTestContext();
+      await fund(didAuthority.publicKey);
+      const did = await initializeDIDAccount(didAuthority);
+      await addKeyToDID(didAuthority, provider.wallet.publicKey);
+
+      await registry.register(did).then((execution) => execution.rpc());
+      await closeDIDAccount(didAuthority);
+
+      return did;
+    };
+
+    before("fund the pruner", async () => {
+      ({ authority: pruner } = createTestContext());
+      await fund(pruner.publicKey);
+
+      // the pruner is not the key, so acts on the registry as a relayer
+      prunerRegistry = Registry.forRelayer(
+        provider.wallet.publicKey,
+        pruner,
+        provider.connection,
+        cluster
+      );
+    });
+
+    it("lets anyone prune a DID the key is no longer an authority on", async () => {
+      const did = await registerClosedDID();
+
+      await prunerRegistry.prune(did).rpc();
+
+      expect(await registry.listDIDs()).not.to.include(did);
+    });
+
+    it("cannot prune a DID the key is still an authority on", async () => {
+      const did = toDid(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+
+      const shouldFail = prunerRegistry.prune(did).rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/StillAuthority/);
+    });
+
+    context("with controllers", () => {
+      const keyDid = toDid(provider.wallet.publicKey);
+      let didAuthority: Wallet;
+      let did: string;
+
+      // the key is an authority on the DID through the controller
+      beforeEach("register a DID controlled by the key's DID", async () => {
+        ({ authority: didAuthority } = createTestContext());
+        await fund(didAuthority.publicKey);
+        did = await initializeDIDAccount(didAuthority);
+        await addControllerToDID(didAuthority, keyDid);
+
+        await registry
+          .register(did, [keyDid])
+          .then((execution) => execution.rpc());
+      });
+
+      it("can prune a DID once the key is not an authority on any of its controllers", async () => {
+        const otherController = toDid(Keypair.generate().publicKey);
+        await addControllerToDID(didAuthority, otherController);
+
+        await prunerRegistry.prune(did, false, [otherController]).rpc();
+
+        expect(await registry.listDIDs()).not.to.include(did);
+      });
+
+      it("cannot prune a DID the key is an authority on through a controller", () => {
+        const shouldFail = prunerRegistry.prune(did, false, [keyDid]).rpc();
+
+        return expect(shouldFail).to.be.rejectedWith(/StillAuthority/);
+      });
+
+      it("cannot prune a DID without passing its controllers", () => {
+        const shouldFail = prunerRegistry.prune(did).rpc();
+
+        return expect(shouldFail).to.be.rejectedWith(/DIDHasControllers/);
+      });
+    });
+
+    it("refunds the freed rent to the rent payer when shrinking the registry", async () => {
+      const did = await registerClosedDID();
+      await registry.resize(5).rpc();
+
+      const registryAddress = registry.getRegistryAddressAndBump()[0];
+      const spaceBefore = await registry.analyseSpace();
+      const registryBalanceBefore = await balanceOf(registryAddress);
+      const keyBalanceBefore = await balanceOf(provider.wallet.publicKey);
+
+      await prunerRegistry.prune(did, true).rpc();
+
+      const spaceAfter = await registry.analyseSpace();
+      const rentFreed =
+        registryBalanceBefore - (await balanceOf(registryAddress));
+
+      expect(spaceAfter.sizeBytes).to.equal(
+        spaceBefore.sizeBytes - KEY_REGISTRY_DID_SIZE
+      );
+      // the key paid for the registry, so it is refunded, rather than the pruner
+      expect(rentFreed).to.be.gt(0);
+      expect(await balanceOf(provider.wallet.publicKey)).to.equal(
+        keyBalanceBefore + rentFreed
+      );
+    });
+  });
+
+  describe("expiring registrations", () => {
+    const inSeconds = (seconds: number) =>
+      new Date(Date.now() + seconds * 1000);
+
+    it("stops listing a DID once it has expired", async () => {
+      const did = await createDIDAndAddKey(provider.wallet.publicKey);
+      const expiresAt = inSeconds(2);
+      await registry
+        .registerWithExpiry(did, expiresAt)
+        .then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).to.include(did);
+
+      await waitForClusterTime(expiresAt);
+
+      expect(await registry.listDIDs()).not.to.include(did);
+    });
+
+    it("rejects an expiry in the past", async () => {
+      const did = await createDIDAndAddKey(provider.wallet.publicKey);
+
+      const shouldFail = registry
+        .registerWithExpiry(did, inSeconds(-60))
+        .then((execution) => execution.rpc());
+
+      return expect(shouldFail).to.be.rejectedWith(/InvalidExpiry/);
+    });
+
+    it("lets anyone sweep expired DIDs from the registry", async () => {
+      const permanentDid = await createDIDAndAddKey(provider.wallet.publicKey);
+      const expiringDid = await createDIDAndAddKey(provider.wallet.publicKey);
+      const expiresAt = inSeconds(2);
+      await registry
+        .register(permanentDid)
+        .then((execution) => execution.rpc());
+      await registry
+        .registerWithExpiry(expiringDid, expiresAt)
+        .then((execution) => execution.rpc());
+      await waitForClusterTime(expiresAt);
+
+      const { authority: sweeper } = createTestContext();
+      await fund(sweeper.publicKey);
+      await Registry.forRelayer(
+        provider.wallet.publicKey,
+        sweeper,
+        provider.connection,
+        cluster
+      )
+        .sweep()
+        .rpc();
+
+      const space = await registry.analyseSpace();
+      expect(space.count).to.equal(1);
+      expect(await registry.listDIDs()).to.deep.equal([permanentDid]);
+    });
+
+    it("refunds the rent payer when sweeping frees rent", async () => {
+      const did = await createDIDAndAddKey(provider.wallet.publicKey);
+      const expiresAt = inSeconds(2);
+      await registry
+        .registerWithExpiry(did, expiresAt)
+        .then((execution) => execution.rpc());
+      // the key pays for the extra space, which sweeping frees again
+      await registry.resize(10).rpc();
+      await waitForClusterTime(expiresAt);
+
+      const registryAddress = registry.getRegistryAddressAndBump()[0];
+      const registryBalanceBefore = await balanceOf(registryAddress);
+      const keyBalanceBefore = await balanceOf(provider.wallet.publicKey);
+
+      const { authority: sweeper } = createTestContext();
+      await fund(sweeper.publicKey);
+      await Registry.forRelayer(
+        provider.wallet.publicKey,
+        sweeper,
+        provider.connection,
+        cluster
+      )
+        .sweep()
+        .rpc();
+
+      const rentFreed =
+        registryBalanceBefore - (await balanceOf(registryAddress));
+      expect(rentFreed).to.be.gt(0);
+      expect(await balanceOf(provider.wallet.publicKey)).to.equal(
+        keyBalanceBefore + rentFreed
+      );
+    });
+
+    it("cannot sweep a registry without expired DIDs", async () => {
+      const did = await createDIDAndAddKey(provider.wallet.publicKey);
+      await registry.register(did).then((execution) => execution.rpc());
+
+      const shouldFail = registry.sweep().rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/NoExpiredDids/);
+    });
+  });
+
+  describe("invitations", () => {
+    // an issuer, inviting the key to register a DID it is an authority on
+    let issuer: Wallet;
+    let issuerRegistry: Registry;
+    let did: string;
+
+    beforeEach("create a DID with the key", async () => {
+      ({ authority: issuer } = createTestContext());
+      await fund(issuer.publicKey);
+      did = await initializeDIDAccount(issuer);
+      await addKeyToDID(issuer, provider.wallet.publicKey);
+
+      issuerRegistry = Registry.forRelayer(
+        provider.wallet.publicKey,
+        issuer,
+        provider.connection,
+        cluster
+      );
+    });
+
+    const invitedDids = async () =>
+      (await registry.listInvitations()).map((invitation) => invitation.did);
+
+    it("lets an authority on a DID invite a key to register it", async () => {
+      await issuerRegistry.invite(did).rpc();
+
+      expect(await invitedDids()).to.include(did);
+      expect(await registry.listDIDs()).not.to.include(did);
+    });
+
+    it("does not allow a non-authority to invite a key", async () => {
+      const otherDid = await createDIDAndAddKey(provider.wallet.publicKey);
+
+      const shouldFail = issuerRegistry.invite(otherDid).rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+    });
+
+    it("registers the DID and refunds the inviter when the key accepts", async () => {
+      await issuerRegistry.invite(did).rpc();
+      const balanceBefore = await balanceOf(issuer.publicKey);
+
+      await registry.acceptInvitation(did).then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).to.include(did);
+      expect(await invitedDids()).not.to.include(did);
+      expect(await balanceOf(issuer.publicKey)).to.be.gt(balanceBefore);
+    });
+
+    it("pays the deposit to the key when it rejects", async () => {
+      await issuerRegistry.invite(did).rpc();
+      const balanceBefore = await balanceOf(provider.wallet.publicKey);
+
+      await registry.rejectInvitation(did).then((execution) => execution.rpc());
+
+      expect(await registry.listDIDs()).not.to.include(did);
+      expect(await invitedDids()).not.to.include(did);
+      // the deposit exceeds the transaction fee
+      const balanceAfter = await balanceOf(provider.wallet.publicKey);
+      expect(balanceAfter).to.be.gt(balanceBefore);
+    });
+
+    it("lets the inviter cancel an invitation, refunding its deposit", async () => {
+      await issuerRegistry.invite(did).rpc();
+      const balanceBefore = await balanceOf(issuer.publicKey);
+
+      await issuerRegistry.cancelInvitation(did).rpc();
+
+      expect(await invitedDids()).not.to.include(did);
+      expect(await balanceOf(issuer.publicKey)).to.be.gt(balanceBefore);
+    });
+
+    it("does not allow anyone but the inviter to cancel an invitation", async () => {
+      await issuerRegistry.invite(did).rpc();
+      const { authority: someoneElse } = createTestContext();
+      await fund(someoneElse.publicKey);
+
+      const shouldFail = Registry.forRelayer(
+        provider.wallet.publicKey,
+        someoneElse,
+        provider.connection,
+        cluster
+      )
+        .cancelInvitation(did)
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/ConstraintHasOne/);
+    });
+  });
+
+  describe("history", () => {
+    let key: Wallet;
+    let keyProgram: Program<DidRegistry>;
+    let keyRegistry: Registry;
+    let did: string;
+
+    const actions = async () =>
+      (await keyRegistry.listHistory()).map((entry) => entry.action);
+
+    beforeEach("create a registry with a history", async () => {
+      ({ authority: key, program: keyProgram } = createTestContext());
+      await fund(key.publicKey);
+      keyRegistry = Registry.for(key, provider.connection, cluster);
+
+      // the key is an authority on its own generative DID
+      did = toDid(key.publicKey);
+      await keyRegistry.register(did).then((execution) => execution.rpc());
+      await keyRegistry.createHistory(2).rpc();
+    });
+
+    it("records DIDs added to and removed from the registry", async () => {
+      await keyRegistry.remove(did).rpc();
+      await keyRegistry.register(did).then((execution) => execution.rpc());
+
+      const history = await keyRegistry.listHistory();
+      expect(history.map((entry) => entry.action)).to.deep.equal([
+        "remove",
+        "add",
+      ]);
+      expect(history[1].did).to.equal(did);
+      expect(history[1].signer.toBase58()).to.equal(key.publicKey.toBase58());
+      expect(history[1].slot).to.be.gt(history[0].slot);
+    });
+
+    it("overwrites the oldest entries once full", async () => {
+      await keyRegistry.remove(did).rpc();
+      await keyRegistry.register(did).then((execution) => execution.rpc());
+      await keyRegistry.remove(did).rpc();
+
+      expect(await actions()).to.deep.equal(["add", "remove"]);
+    });
+
+    it("keeps its entries when resized", async () => {
+      await keyRegistry.remove(did).rpc();
+      await keyRegistry.register(did).then((execution) => execution.rpc());
+      await keyRegistry.remove(did).rpc();
+
+      await keyRegistry.resizeHistory(3).rpc();
+      await keyRegistry.register(did).then((execution) => execution.rpc());
+
+      expect(await actions()).to.deep.equal(["add", "remove", "add"]);
+    });
+
+    it("records the removal of the remaining DIDs when the registry is closed", async () => {
+      await keyRegistry.close().rpc();
+
+      const history = await keyRegistry.listHistory();
+      expect(history.map((entry) => entry.action)).to.deep.equal(["remove"]);
+      expect(history[0].did).to.equal(did);
+    });
+
+    it("cannot be shrunk", () => {
+      const shouldFail = keyRegistry.resizeHistory(1).rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/InvalidHistoryCapacity/);
+    });
+
+    it("must be passed when changing the registry", () => {
+      const shouldFail = keyProgram.methods
+        .removeDid()
+        .accounts({
+          config: configAccounts(),
+          registry: keyRegistry.getRegistryAddressAndBump()[0],
+          authority: key.publicKey,
+          did: key.publicKey,
+        })
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/HistoryRequired/);
+    });
+  });
+
+  describe("registering against many keys", () => {
+    let keypairs: Keypair[];
+    let did: string;
+
+    const registryFor = (keypair: Keypair) =>
+      Registry.for(new anchor.Wallet(keypair), provider.connection, cluster);
+
+    const registriesFor = (keys: Keypair[]) =>
+      KeyRegistries.for(
+        keys.map((keypair) => keypair.publicKey),
+        provider.wallet,
+        provider.connection
+      );
+
+    const sendSignedByKeys = (execution: Execution, keys = keypairs) =>
+      execution
+        .transaction()
+        .then((transaction) => provider.sendAndConfirm(transaction, keys));
+
+    beforeEach("create a DID with three keys", async () => {
+      const { authority, keypair } = createTestContext();
+      await fund(authority.publicKey);
+      did = await initializeDIDAccount(authority);
+
+      keypairs = [keypair, Keypair.generate(), Keypair.generate()];
+      await addKeyToDID(authority, keypairs[1].publicKey);
+      await addKeyToDID(authority, keypairs[2].publicKey);
+    });
+
+    it("can register a DID against all its keys in one transaction", async () => {
+      await sendSignedByKeys(registriesFor(keypairs).register(did));
+
+      for (const keypair of keypairs) {
+        expect(await registryFor(keypair).listDIDs()).to.deep.equal([did]);
+      }
+    });
+
+    it("appends to registries that already exist", async () => {
+      const otherDid = await createDIDAndAddKey(keypairs[1].publicKey);
+      await fund(keypairs[1].publicKey);
+      await registryFor(keypairs[1])
+        .register(otherDid)
+        .then((execution) => execution.rpc());
+
+      await sendSignedByKeys(registriesFor(keypairs).register(did));
+
+      expect(await registryFor(keypairs[1]).listDIDs()).to.have.members([
+        otherDid,
+        did,
+      ]);
+    });
+
+    it("can remove a DID from all its keys in one transaction", async () => {
+      await sendSignedByKeys(registriesFor(keypairs).register(did));
+
+      await sendSignedByKeys(registriesFor(keypairs).remove(did));
+
+      for (const keypair of keypairs) {
+        expect(await registryFor(keypair).listDIDs()).to.be.empty;
+      }
+    });
+
+    it("records the changes in the history of registries that have one", async () => {
+      await sendSignedByKeys(registriesFor(keypairs).register(did));
+      const registryWithHistory = registryFor(keypairs[1]);
+      await fund(keypairs[1].publicKey);
+      await registryWithHistory.createHistory(2).rpc();
+
+      await sendSignedByKeys(registriesFor(keypairs).remove(did));
+      await sendSignedByKeys(registriesFor(keypairs).register(did));
+
+      const history = await registryWithHistory.listHistory();
+      expect(history.map((entry) => entry.action)).to.deep.equal([
+        "remove",
+        "add",
+      ]);
+      expect(history[1].did).to.equal(did);
+      expect(history[1].signer.toBase58()).to.equal(
+        keypairs[1].publicKey.toBase58()
+      );
+    });
+
+    it("fails to register a DID if one of the keys is not an authority", async () => {
+      const keys = [...keypairs, Keypair.generate()];
+
+      const shouldFail = sendSignedByKeys(
+        registriesFor(keys).register(did),
+        keys
+      );
+
+      return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+    });
+  });
+
+  describe("namespaces", () => {
+    let key: Wallet;
+    let keypair: Keypair;
+    let did: string;
+    let gamingRegistry: Registry;
+
+    beforeEach("create a key", async () => {
+      ({ authority: key, keypair } = createTestContext());
+      await fund(key.publicKey);
+      gamingRegistry = Registry.for(
+        key,
+        provider.connection,
+        cluster,
+        undefined,
+        "gaming"
+      );
+
+      // the key is an authority on its own generative DID
+      did = toDid(key.publicKey);
+    });
+
+    it("keeps a separate registry for each namespace", async () => {
+      const defaultRegistry = Registry.for(key, provider.connection, cluster);
+      const kycRegistry = Registry.for(
+        key,
+        provider.connection,
+        cluster,
+        undefined,
+        "kyc"
+      );
+
+      await gamingRegistry.register(did).then((execution) => execution.rpc());
+
+      expect(await gamingRegistry.listDIDs()).to.deep.equal([did]);
+      expect(await kycRegistry.listDIDs()).to.be.empty;
+      expect(await defaultRegistry.listDIDs()).to.be.empty;
+      expect(
+        await ReadOnlyRegistry.for(
+          key.publicKey,
+          provider.connection,
+          cluster,
+          "gaming"
+        ).listDIDs()
+      ).to.deep.equal([did]);
+    });
+
+    it("can remove a DID and close a namespaced registry", async () => {
+      await gamingRegistry.register(did).then((execution) => execution.rpc());
+
+      await gamingRegistry.remove(did).rpc();
+      expect(await gamingRegistry.listDIDs()).to.be.empty;
+
+      await gamingRegistry.close().rpc();
+      expect((await gamingRegistry.analyseSpace()).sizeBytes).to.equal(0);
+    });
+
+    it("can register a DID against many keys in a namespace", async () => {
+      await KeyRegistries.for(
+        [key.publicKey],
+        provider.wallet,
+        provider.connection,
+        "gaming"
+      )
+        .register(did)
+        .transaction()
+        .then((transaction) => provider.sendAndConfirm(transaction, [keypair]));
+
+      expect(await gamingRegistry.listDIDs()).to.deep.equal([did]);
+      expect(
+        await Registry.for(key, provider.connection, cluster).listDIDs()
+      ).to.be.empty;
+    });
+
+    it("rejects a namespace longer than 32 bytes", () => {
+      const namespace = "x".repeat(33);
+
+      expect(() =>
+        Registry.for(key, provider.connection, cluster, undefined, namespace)
+      ).to.throw(/at most 32 bytes/);
+    });
+
+    it("rejects a namespace longer than 32 bytes on-chain", () => {
+      // the client refuses such a namespace, so the instruction is built directly
+      const shouldFail = program.methods
+        .createKeyRegistry(255, Buffer.alloc(33))
+        .accounts({
+          config: configAccounts(),
+          registry: Keypair.generate().publicKey,
+          payer: key.publicKey,
+          authority: key.publicKey,
+        })
+        .signers([keypair])
+        .rpc();
+
+      return expect(shouldFail).to.be.rejectedWith(/NamespaceTooLong/);
+    });
+  });
+
+  context("with a full registry in the layout of the first release", () => {
+    // loaded into the validator from tests/fixtures/legacy-key-registry.json
+    const legacyKey = new PublicKey(
+      "DHnFb7H2JUkz67a52kzmegEN4o1cUqVXM2qFDZBcbLuY"
+    );
+    const legacyDids = [
+      "8KR9Rsr7zSXszvchC12G43dmJSmca7mkLNMPDnVH2TDy",
+      "35NHJiB1RZLw7jtmk8ELnoysBNoojxjsxYbXGQztqDyz",
+      "4hrVmL6tPHAM2ch3kZDzGqmdtYbne7sXyCoNn5Wd6KpY",
+      "6WEkukYoKu4WQuAL5SisZrgztHqmSiL6aPyeV1cZamPM",
+    ].map((key) => toDid(new PublicKey(key)));
+
+    const legacyRegistry = Registry.forRelayer(
+      legacyKey,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+
+    it("migrates the registry, keeping its DIDs", async () => {
+      // the registry has no room for the fields appended since the first release
+      await expect(legacyRegistry.listDIDs()).to.be.rejected;
+
+      await legacyRegistry.migrate().rpc();
+
+      expect(await legacyRegistry.listDIDs()).to.deep.equal(legacyDids);
+      expect(
+        (await legacyRegistry.listRentPayers()).map((p) => p.payer)
+      ).to.deep.equal([provider.wallet.publicKey]);
+
+      const shouldFail = legacyRegistry.migrate().rpc();
+      return expect(shouldFail).to.be.rejectedWith(/RegistryNotLegacy/);
+    });
   });
 });
@@ -0,0 +1,174 @@
+import * as anchor from "@project-serum/anchor";
+import { PasskeyRegistry } from "../src";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { addPasskeyToDID, initializeDIDAccount } from "./util/did";
+import { createTestContext, fund } from "./util/anchorUtils";
+import { createPasskey } from "./util/passkey";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Passkey Registry", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  const passkey = createPasskey();
+
+  const registry = PasskeyRegistry.for(
+    passkey.key,
+    provider.wallet,
+    provider.connection,
+    cluster
+  );
+
+  const createDIDWithPasskey = async () => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+    await addPasskeyToDID(authority, passkey.key);
+    return did;
+  };
+
+  // catch-all to ensure all tests start from an empty registry
+  afterEach("close registry", async () => {
+    const { sizeBytes } = await registry.analyseSpace();
+    if (sizeBytes === 0) return; // the test did not create the registry
+
+    const assertion = passkey.assert(await registry.closeChallenge());
+    await (await registry.close(assertion)).rpc();
+  });
+
+  it("finds no DIDs registered by default for a passkey", async () => {
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("can register a DID with a passkey assertion", async () => {
+    const did = await createDIDWithPasskey();
+
+    const assertion = passkey.assert(await registry.registrationChallenge(did));
+    await registry.register(did, assertion).rpc();
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("fails to register a DID if the passkey is not an authority", async () => {
+    const { authority } = createTestContext();
+    await fund(authority.publicKey);
+    const did = await initializeDIDAccount(authority);
+
+    const assertion = passkey.assert(await registry.registrationChallenge(did));
+    const shouldFail = registry.register(did, assertion).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotAuthority/);
+  });
+
+  it("rejects an assertion over a different challenge", async () => {
+    const did = await createDIDWithPasskey();
+    const otherDid = await createDIDWithPasskey();
+
+    const assertion = passkey.assert(
+      await registry.registrationChallenge(otherDid)
+    );
+    const shouldFail = registry.register(did, assertion).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongMessage/);
+  });
+
+  it("rejects an assertion by a different passkey", async () => {
+    const did = await createDIDWithPasskey();
+
+    const assertion = createPasskey().assert(
+      await registry.registrationChallenge(did)
+    );
+    const shouldFail = registry.register(did, assertion).rpc();
+
+    return expect(shouldFail).to.be.rejected;
+  });
+
+  it("does not allow an assertion to be replayed", async () => {
+    const did = await createDIDWithPasskey();
+
+    const registration = passkey.assert(
+      await registry.registrationChallenge(did)
+    );
+    await registry.register(did, registration).rpc();
+    await registry
+      .remove(did, passkey.assert(await registry.removalChallenge(did)))
+      .rpc();
+
+    const shouldFail = registry.register(did, registration).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongMessage/);
+  });
+
+  it("does not allow an assertion to be replayed once the registry is closed", async () => {
+    const did = await createDIDWithPasskey();
+
+    const registration = passkey.assert(
+      await registry.registrationChallenge(did)
+    );
+    await registry.register(did, registration).rpc();
+    await registry
+      .remove(did, passkey.assert(await registry.removalChallenge(did)))
+      .rpc();
+    await registry
+      .close(passkey.assert(await registry.closeChallenge()))
+      .then((execution) => execution.rpc());
+
+    // the nonce survives closing the registry, so the assertion cannot re-create it
+    const shouldFail = registry.register(did, registration).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/WrongMessage/);
+  });
+
+  it("can remove a DID with a passkey assertion", async () => {
+    const did = await createDIDWithPasskey();
+    await registry
+      .register(did, passkey.assert(await registry.registrationChallenge(did)))
+      .rpc();
+
+    await registry
+      .remove(did, passkey.assert(await registry.removalChallenge(did)))
+      .rpc();
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("can resize the registry with a passkey assertion", async () => {
+    const did = await createDIDWithPasskey();
+    await registry
+      .register(did, passkey.assert(await registry.registrationChallenge(did)))
+      .rpc();
+
+    await registry
+      .resize(10, passkey.assert(await registry.resizeChallenge(10)))
+      .then((execution) => execution.rpc());
+
+    const { count, maxCount } = await registry.analyseSpace();
+    expect(count).to.equal(1);
+    expect(maxCount).to.equal(10);
+  });
+
+  it("records the wallet as the rent payer of the registry", async () => {
+    const did = await createDIDWithPasskey();
+    await registry
+      .register(did, passkey.assert(await registry.registrationChallenge(did)))
+      .rpc();
+    const [{ lamports: rentBefore }] = await registry.listRentPayers();
+
+    await registry
+      .resize(10, passkey.assert(await registry.resizeChallenge(10)))
+      .then((execution) => execution.rpc());
+
+    const rentPayers = await registry.listRentPayers();
+    expect(rentPayers).to.have.lengthOf(1);
+    expect(rentPayers[0].payer.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+    expect(rentPayers[0].lamports).to.be.greaterThan(rentBefore);
+  });
+});
@@ -0,0 +1,218 @@
+import * as anchor from "@project-serum/anchor";
+import { PasskeyRegistry, ProgramConfig, Registry } from "../src";
+import { getConfigAddress } from "../src/lib/util";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import {
+  addPasskeyToDID,
+  createDIDAndAddKey,
+  initializeDIDAccount,
+  toDid,
+} from "./util/did";
+import { createPasskey } from "./util/passkey";
+import { balanceOf, createTestContext, fund, Wallet } from "./util/anchorUtils";
+import { ExtendedCluster } from "@identity.com/sol-did-client";
+import { Keypair } from "@solana/web3.js";
+import * as ed25519 from "@noble/ed25519";
+
+chai.use(chaiAsPromised);
+const { expect } = chai;
+
+describe("Program Config", () => {
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const cluster: ExtendedCluster = "localnet";
+
+  // the provider wallet is the upgrade authority of the program, so it can initialise the config
+  const config = ProgramConfig.for(provider.wallet, provider.connection);
+
+  let key: Wallet;
+  let registry: Registry;
+
+  before("initialise the config", async () => {
+    if (!(await config.fetch())) await config.initialize(4).rpc();
+  });
+
+  beforeEach("create a key", async () => {
+    ({ authority: key } = createTestContext());
+    await fund(key.publicKey);
+
+    registry = Registry.for(key, provider.connection, cluster);
+  });
+
+  // restore the defaults, so that the config does not affect other tests
+  afterEach("reset the config", async () => {
+    await config.setPaused(false).rpc();
+    await config.setLimits(4, 0, 0).rpc();
+  });
+
+  it("makes the upgrade authority the admin", async () => {
+    const state = await config.fetch();
+
+    expect(state?.admin.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+    expect(state?.pendingAdmin).to.be.null;
+  });
+
+  it("cannot be initialised twice", () => {
+    const shouldFail = config.initialize(4).rpc();
+
+    return expect(shouldFail).to.be.rejected;
+  });
+
+  it("blocks registrations while paused", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+
+    await config.setPaused(true).rpc();
+
+    const shouldFail = (await registry.register(did)).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/ProgramPaused/);
+  });
+
+  it("allows registries to be closed while paused", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await (await registry.register(did)).rpc();
+
+    await config.setPaused(true).rpc();
+    await registry.close().rpc();
+
+    expect(await registry.listDIDs()).to.be.empty;
+  });
+
+  it("resumes registrations once unpaused", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+
+    await config.setPaused(true).rpc();
+    await config.setPaused(false).rpc();
+    await (await registry.register(did)).rpc();
+
+    expect(await registry.listDIDs()).to.deep.equal([did]);
+  });
+
+  it("cannot be paused by anyone other than the admin", () => {
+    const shouldFail = ProgramConfig.for(key, provider.connection)
+      .setPaused(true)
+      .rpc();
+
+    return expect(shouldFail).to.be.rejected;
+  });
+
+  it("blocks resizing a registry beyond the maximum", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await (await registry.register(did)).rpc();
+
+    await config.setLimits(4, 4, 0).rpc();
+
+    const shouldFail = registry.resize(5).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/RegistryLimitExceeded/);
+  });
+
+  it("rejects a maximum below the initial capacity", () => {
+    const shouldFail = config.setLimits(4, 2, 0).rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/InvalidConfig/);
+  });
+
+  it("charges a fee for creating a registry", async () => {
+    const fee = 1_000_000;
+    const did = await createDIDAndAddKey(key.publicKey);
+    await config.setLimits(4, 0, fee).rpc();
+
+    const balanceBefore = await balanceOf(getConfigAddress());
+    await (await registry.register(did)).rpc();
+
+    expect(await balanceOf(getConfigAddress())).to.equal(balanceBefore + fee);
+  });
+
+  it("charges the fee when a relayer creates a registry", async () => {
+    const fee = 1_000_000;
+    const userKeypair = Keypair.generate();
+    const did = toDid(userKeypair.publicKey);
+    await config.setLimits(4, 0, fee).rpc();
+
+    const relayedRegistry = Registry.forRelayer(
+      userKeypair.publicKey,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+    const signature = await ed25519.sign(
+      await relayedRegistry.registrationMessage(did),
+      userKeypair.secretKey.slice(0, 32)
+    );
+
+    const balanceBefore = await balanceOf(getConfigAddress());
+    await relayedRegistry
+      .registerWithSignedMessage(did, signature)
+      .then((execution) => execution.rpc());
+
+    expect(await balanceOf(getConfigAddress())).to.equal(balanceBefore + fee);
+  });
+
+  it("charges the fee when a registry is created for a passkey", async () => {
+    const fee = 1_000_000;
+    const passkey = createPasskey();
+    const did = await initializeDIDAccount(key);
+    await addPasskeyToDID(key, passkey.key);
+    await config.setLimits(4, 0, fee).rpc();
+
+    const passkeyRegistry = PasskeyRegistry.for(
+      passkey.key,
+      provider.wallet,
+      provider.connection,
+      cluster
+    );
+    const assertion = passkey.assert(
+      await passkeyRegistry.registrationChallenge(did)
+    );
+
+    const balanceBefore = await balanceOf(getConfigAddress());
+    await passkeyRegistry.register(did, assertion).rpc();
+
+    expect(await balanceOf(getConfigAddress())).to.equal(balanceBefore + fee);
+  });
+
+  it("lets the admin withdraw fees", async () => {
+    const did = await createDIDAndAddKey(key.publicKey);
+    await config.setLimits(4, 0, 1_000_000).rpc();
+    await (await registry.register(did)).rpc();
+
+    const { authority: recipient } = createTestContext();
+    await config.withdrawFees(recipient.publicKey).rpc();
+
+    expect(await balanceOf(recipient.publicKey)).to.be.at.least(1_000_000);
+  });
+
+  it("hands over to a new admin once it accepts", async () => {
+    const newAdmin = ProgramConfig.for(key, provider.connection);
+
+    await config.proposeAdmin(key.publicKey).rpc();
+    expect((await config.fetch())?.pendingAdmin?.toBase58()).to.equal(
+      key.publicKey.toBase58()
+    );
+
+    await newAdmin.acceptAdmin().rpc();
+    expect((await config.fetch())?.admin.toBase58()).to.equal(
+      key.publicKey.toBase58()
+    );
+
+    // hand back to the provider wallet
+    await newAdmin.proposeAdmin(provider.wallet.publicKey).rpc();
+    await config.acceptAdmin().rpc();
+    expect((await config.fetch())?.admin.toBase58()).to.equal(
+      provider.wallet.publicKey.toBase58()
+    );
+  });
+
+  it("cannot be accepted by a key that was not proposed", () => {
+    const shouldFail = ProgramConfig.for(key, provider.connection)
+      .acceptAdmin()
+      .rpc();
+
+    return expect(shouldFail).to.be.rejectedWith(/NotPendingAdmin/);
+  });
+});
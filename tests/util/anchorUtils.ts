@@ -40,6 +40,23 @@ export const balanceOf = (publicKey: PublicKey): Promise<number> =>
     .getAccountInfo(publicKey)
     .then((a) => (a ? a.lamports : 0));
 
+export const waitForSlots = async (slots: number) => {
+  const targetSlot = (await envProvider.connection.getSlot()) + slots;
+  while ((await envProvider.connection.getSlot()) < targetSlot) {
+    await new Promise((resolve) => setTimeout(resolve, 200));
+  }
+};
+
+// wait until the cluster's clock has passed the given time
+export const waitForClusterTime = async (time: Date) => {
+  const target = time.getTime() / 1000;
+  const blockTime = async () =>
+    envProvider.connection.getBlockTime(await envProvider.connection.getSlot());
+  while (((await blockTime()) ?? 0) <= target) {
+    await new Promise((resolve) => setTimeout(resolve, 200));
+  }
+};
+
 export type TestContext = {
   program: Program<DidRegistry>;
   provider: Provider;
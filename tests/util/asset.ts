@@ -0,0 +1,62 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import * as anchor from "@project-serum/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+} from "@solana/spl-token";
+
+const connection = anchor.AnchorProvider.env().connection;
+
+/**
+ * Mint tokens with no decimals into the holder's associated token account
+ */
+export const createToken = async (
+  holder: Keypair,
+  amount: number
+): Promise<PublicKey> => {
+  const mint = await createMint(connection, holder, holder.publicKey, null, 0);
+  const tokenAccount = await getOrCreateAssociatedTokenAccount(
+    connection,
+    holder,
+    mint,
+    holder.publicKey
+  );
+  await mintTo(connection, holder, mint, tokenAccount.address, holder, amount);
+  return mint;
+};
+
+/**
+ * Mint an NFT (a supply of 1, with no decimals) into the holder's associated token account
+ */
+export const createNFT = (holder: Keypair): Promise<PublicKey> =>
+  createToken(holder, 1);
+
+/**
+ * Transfer an NFT from the holder's associated token account to the recipient's
+ */
+export const transferNFT = async (
+  mint: PublicKey,
+  holder: Keypair,
+  recipient: Keypair
+) => {
+  const [source, destination] = await Promise.all(
+    [holder, recipient].map((owner) =>
+      getOrCreateAssociatedTokenAccount(
+        connection,
+        recipient,
+        mint,
+        owner.publicKey
+      )
+    )
+  );
+  await transfer(
+    connection,
+    recipient,
+    source.address,
+    destination.address,
+    holder,
+    1
+  );
+};
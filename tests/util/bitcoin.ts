@@ -0,0 +1,94 @@
+import * as secp from "@noble/secp256k1";
+import { sha256 } from "@noble/hashes/sha256";
+import { ripemd160 } from "@noble/hashes/ripemd160";
+import { utils } from "@project-serum/anchor";
+import {
+  BECH32_CHARSET,
+  bech32HrpExpand,
+  bech32Polymod,
+  BtcAddressType,
+} from "../../src/lib/bitcoin";
+
+export type BtcKey = {
+  // the uncompressed public key, as listed on a DID
+  publicKey: Uint8Array;
+  pubkeyHash: Uint8Array;
+  p2pkhAddress: string;
+  p2wpkhAddress: string;
+  // create a BIP-137 signature of the message, as a wallet would for the given address type
+  signMessage(message: Uint8Array, type: BtcAddressType): Promise<Uint8Array>;
+};
+
+const sha256d = (data: Uint8Array) => sha256(sha256(data));
+
+// convert bytes to the 5-bit groups of a bech32 payload
+const toWords = (bytes: Uint8Array): number[] => {
+  let acc = 0;
+  let bits = 0;
+  const words: number[] = [];
+  bytes.forEach((byte) => {
+    acc = (acc << 8) | byte;
+    bits += 8;
+    while (bits >= 5) {
+      bits -= 5;
+      words.push((acc >> bits) & 31);
+    }
+  });
+  if (bits > 0) words.push((acc << (5 - bits)) & 31);
+  return words;
+};
+
+const encodeP2wpkh = (pubkeyHash: Uint8Array): string => {
+  const data = [0, ...toWords(pubkeyHash)];
+  const polymod =
+    bech32Polymod([...bech32HrpExpand("bc"), ...data, 0, 0, 0, 0, 0, 0]) ^ 1;
+  const checksum = [0, 1, 2, 3, 4, 5].map(
+    (i) => (polymod >> (5 * (5 - i))) & 31
+  );
+  return (
+    "bc1" + [...data, ...checksum].map((word) => BECH32_CHARSET[word]).join("")
+  );
+};
+
+const encodeP2pkh = (pubkeyHash: Uint8Array): string => {
+  const payload = Uint8Array.from([0x00, ...pubkeyHash]);
+  const checksum = sha256d(payload).slice(0, 4);
+  return utils.bytes.bs58.encode(Buffer.from([...payload, ...checksum]));
+};
+
+// the double-sha256 of a message with the Bitcoin message prefix, as signed by Bitcoin wallets
+const bitcoinMessageHash = (message: Uint8Array): Uint8Array =>
+  sha256d(
+    Uint8Array.from([
+      ...Buffer.from("\x18Bitcoin Signed Message:\n"),
+      message.length, // messages in the tests are shorter than 253 bytes
+      ...message,
+    ])
+  );
+
+/**
+ * Create a random Bitcoin key, with its (compressed) P2PKH and P2WPKH addresses
+ */
+export const createBtcKey = (): BtcKey => {
+  const privateKey = secp.utils.randomPrivateKey();
+  const pubkeyHash = ripemd160(sha256(secp.getPublicKey(privateKey, true)));
+
+  const signMessage = async (message: Uint8Array, type: BtcAddressType) => {
+    const [signature, recovery] = await secp.sign(
+      bitcoinMessageHash(message),
+      privateKey,
+      { recovered: true, der: false }
+    );
+    // BIP-137 headers: 31-34 for compressed P2PKH, 39-42 for P2WPKH
+    const header = (type === BtcAddressType.P2pkh ? 31 : 39) + recovery;
+    return Uint8Array.from([header, ...signature]);
+  };
+
+  return {
+    publicKey: secp.getPublicKey(privateKey, false),
+    pubkeyHash,
+    p2pkhAddress: encodeP2pkh(pubkeyHash),
+    p2wpkhAddress: encodeP2wpkh(pubkeyHash),
+    signMessage,
+  };
+};
@@ -0,0 +1,70 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import {
+  PublicKey,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import BN from "bn.js";
+import { Wallet } from "./anchorUtils";
+
+// The cryptid stub is loaded at the Cryptid program address (see Anchor.toml)
+export const CRYPTID_PROGRAM_ID = new PublicKey(
+  "cryptJTh61jY5kbUmBEXyc86tBUyueBDrLuNSZWmUcs"
+);
+const DID_PROGRAM_ID = new PublicKey(
+  "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc"
+);
+
+export const getCryptidAccount = (did: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("cryptid_account"),
+      DID_PROGRAM_ID.toBuffer(),
+      did.toBuffer(),
+      new BN(0).toArrayLike(Buffer, "le", 4),
+    ],
+    CRYPTID_PROGRAM_ID
+  )[0];
+
+// A wallet for a cryptid account - it cannot sign transactions directly, only via the cryptid program
+export const cryptidWallet = (cryptidAccount: PublicKey): Wallet => ({
+  publicKey: cryptidAccount,
+  signTransaction: async (): Promise<Transaction> => {
+    throw new Error("Cryptid accounts sign via CPI");
+  },
+  signAllTransactions: async (): Promise<Transaction[]> => {
+    throw new Error("Cryptid accounts sign via CPI");
+  },
+});
+
+/**
+ * Wrap an instruction so that it is invoked by the cryptid stub, signed by the default cryptid account of the DID
+ */
+export const wrapInCryptid = (
+  instruction: TransactionInstruction,
+  did: PublicKey
+): Promise<TransactionInstruction> => {
+  const cryptidStub = new Program(
+    anchor.workspace.CryptidStub.idl,
+    CRYPTID_PROGRAM_ID,
+    anchor.getProvider()
+  );
+  const cryptidAccount = getCryptidAccount(did);
+
+  return cryptidStub.methods
+    .execute(instruction.data)
+    .accounts({
+      cryptidAccount,
+      did,
+      targetProgram: instruction.programId,
+    })
+    .remainingAccounts(
+      instruction.keys.map((key) => ({
+        ...key,
+        // the cryptid account is signed by the stub, not the transaction
+        isSigner: key.isSigner && !key.pubkey.equals(cryptidAccount),
+      }))
+    )
+    .instruction();
+};
@@ -10,13 +10,19 @@ import {
 } from "@identity.com/sol-did-client";
 import { CLUSTER } from "./constants";
 import { createTestContext, fund, Wallet } from "./anchorUtils";
-import { arrayify } from "@ethersproject/bytes";
+import { arrayify, BytesLike } from "@ethersproject/bytes";
 
-export const addKeyToDIDExecution = (authority: Wallet, key: PublicKey) => {
+export const addKeyToDIDExecution = (
+  authority: Wallet,
+  key: PublicKey,
+  flags: BitwiseVerificationMethodFlag[] = [
+    BitwiseVerificationMethodFlag.CapabilityInvocation,
+  ]
+) => {
   const did = DidSolIdentifier.create(authority.publicKey, CLUSTER);
   const didSolService = DidSolService.build(did, { wallet: authority });
   const newKeyVerificationMethod: AddVerificationMethodParams = {
-    flags: [BitwiseVerificationMethodFlag.CapabilityInvocation],
+    flags,
     fragment: `key${Date.now()}`, // randomise fragment name, so that we can add multiple keys in multiple tests.
     keyData: key.toBytes(),
     methodType: VerificationMethodType.Ed25519VerificationKey2018,
@@ -27,8 +33,11 @@ export const addKeyToDIDExecution = (authority: Wallet, key: PublicKey) => {
     .addVerificationMethod(newKeyVerificationMethod);
 };
 
-export const addKeyToDID = async (authority: Wallet, key: PublicKey) =>
-  addKeyToDIDExecution(authority, key).rpc();
+export const addKeyToDID = async (
+  authority: Wallet,
+  key: PublicKey,
+  flags?: BitwiseVerificationMethodFlag[]
+) => addKeyToDIDExecution(authority, key, flags).rpc();
 
 export const addControllerToDID = async (
   authority: Wallet,
@@ -59,6 +68,55 @@ export const addEthAddressToDID = async (
   console.log(doc);
 };
 
+export const addSecp256k1KeyToDID = async (
+  authority: Wallet,
+  publicKey: BytesLike
+) => {
+  const did = DidSolIdentifier.create(authority.publicKey, CLUSTER);
+  const didSolService = DidSolService.build(did, { wallet: authority });
+  const newKeyVerificationMethod = {
+    flags: [BitwiseVerificationMethodFlag.CapabilityInvocation],
+    fragment: `secp256k1_key${Date.now()}`, // randomise fragment name, so that we can add multiple keys in multiple tests.
+    // the uncompressed public key, without the 0x04 prefix
+    keyData: Buffer.from(arrayify(publicKey).slice(1)),
+    methodType: VerificationMethodType.EcdsaSecp256k1VerificationKey2019,
+  };
+
+  await didSolService
+    .withAutomaticAlloc(authority.publicKey)
+    .addVerificationMethod(newKeyVerificationMethod)
+    .rpc();
+};
+
+export const addPasskeyToDID = async (authority: Wallet, key: Uint8Array) => {
+  const did = DidSolIdentifier.create(authority.publicKey, CLUSTER);
+  const didSolService = DidSolService.build(did, { wallet: authority });
+  // the registry matches verification methods by key data, so any method type holding the compressed key works here
+  const newKeyVerificationMethod = {
+    flags: [BitwiseVerificationMethodFlag.CapabilityInvocation],
+    fragment: `passkey${Date.now()}`, // randomise fragment name, so that we can add multiple keys in multiple tests.
+    keyData: Buffer.from(key),
+    methodType: VerificationMethodType.EcdsaSecp256k1VerificationKey2019,
+  };
+
+  await didSolService
+    .withAutomaticAlloc(authority.publicKey)
+    .addVerificationMethod(newKeyVerificationMethod)
+    .rpc();
+};
+
+export const removeControllersFromDID = async (authority: Wallet) => {
+  const did = DidSolIdentifier.create(authority.publicKey, CLUSTER);
+  const didSolService = DidSolService.build(did, { wallet: authority });
+  await didSolService.setControllers([]).rpc();
+};
+
+export const closeDIDAccount = async (authority: Wallet) => {
+  const did = DidSolIdentifier.create(authority.publicKey, CLUSTER);
+  const didSolService = DidSolService.build(did, { wallet: authority });
+  await didSolService.close(authority.publicKey).rpc();
+};
+
 export const getDIDAccount = (authority: PublicKey): PublicKey => {
   const did = DidSolIdentifier.create(authority, CLUSTER);
   return did.dataAccount()[0];
@@ -78,13 +136,16 @@ export const initializeDIDAccount = async (
   return toDid(authority.publicKey, cluster);
 };
 
-export const createDIDAndAddKey = async (keyToAdd: PublicKey) => {
+export const createDIDAndAddKey = async (
+  keyToAdd: PublicKey,
+  flags?: BitwiseVerificationMethodFlag[]
+) => {
   const { authority: didAuthority } = createTestContext();
   await fund(didAuthority.publicKey);
 
   const did = await initializeDIDAccount(didAuthority);
 
-  await addKeyToDID(didAuthority, keyToAdd);
+  await addKeyToDID(didAuthority, keyToAdd, flags);
 
   return did;
 };
@@ -0,0 +1,33 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { GATEWAY_PROGRAM_ID } from "../../src/lib/constants";
+import { getGatewayTokenAddress } from "../../src/lib/util";
+
+/**
+ * Issue a gateway token to the owner in the gatekeeper network, using the gateway stub,
+ * which is loaded at the gateway program address (see Anchor.toml)
+ */
+export const issueGatewayToken = async (
+  owner: PublicKey,
+  gatekeeperNetwork: PublicKey,
+  expiresAt?: Date
+): Promise<void> => {
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const gatewayStub = new Program(
+    anchor.workspace.GatewayStub.idl,
+    GATEWAY_PROGRAM_ID,
+    provider
+  );
+
+  await gatewayStub.methods
+    .issue(expiresAt ? new BN(Math.floor(expiresAt.getTime() / 1000)) : null)
+    .accounts({
+      gatewayToken: getGatewayTokenAddress(owner, gatekeeperNetwork),
+      payer: provider.wallet.publicKey,
+      owner,
+      gatekeeperNetwork,
+    })
+    .rpc();
+};
@@ -0,0 +1,49 @@
+import { createHash, generateKeyPairSync, sign } from "crypto";
+import { WebAuthnAssertion } from "../../src/types";
+
+export type Passkey = {
+  // the compressed public key
+  key: Buffer;
+  assert(challenge: Uint8Array): WebAuthnAssertion;
+};
+
+const sha256 = (data: Uint8Array) => createHash("sha256").update(data).digest();
+
+/**
+ * Simulate a passkey (WebAuthn authenticator) with a P-256 key
+ */
+export const createPasskey = (): Passkey => {
+  const { privateKey, publicKey } = generateKeyPairSync("ec", {
+    namedCurve: "prime256v1",
+  });
+  // the uncompressed point is at the end of the DER-encoded public key
+  const point = publicKey.export({ type: "spki", format: "der" }).subarray(-65);
+  const key = Buffer.concat([
+    Buffer.from([point[64] & 1 ? 0x03 : 0x02]),
+    point.subarray(1, 33),
+  ]);
+
+  const assert = (challenge: Uint8Array): WebAuthnAssertion => {
+    const authenticatorData = Buffer.concat([
+      sha256(Buffer.from("localhost")), // rpIdHash
+      Buffer.from([0x05]), // flags: user present, user verified
+      Buffer.alloc(4), // signature counter
+    ]);
+    const clientDataJSON = Buffer.from(
+      JSON.stringify({
+        type: "webauthn.get",
+        challenge: Buffer.from(challenge).toString("base64url"),
+        origin: "http://localhost",
+      })
+    );
+    const signature = sign(
+      "sha256",
+      Buffer.concat([authenticatorData, sha256(clientDataJSON)]),
+      privateKey
+    );
+
+    return { authenticatorData, clientDataJSON, signature };
+  };
+
+  return { key, assert };
+};